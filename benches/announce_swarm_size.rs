@@ -0,0 +1,128 @@
+//! Measures allocations and time per announce for growing swarm sizes, they should not grow with the swarm.
+//!
+//! Run with `cargo bench --bench announce_swarm_size [-- <announces>]`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use torrust_actix::common::structs::number_of_bytes::NumberOfBytes;
+use torrust_actix::config::structs::configuration::Configuration;
+use torrust_actix::tracker::enums::announce_event::AnnounceEvent;
+use torrust_actix::tracker::structs::info_hash::InfoHash;
+use torrust_actix::tracker::structs::peer_id::PeerId;
+use torrust_actix::tracker::structs::torrent_peer::TorrentPeer;
+use torrust_actix::tracker::structs::torrent_tracker::TorrentTracker;
+use torrust_actix::udp::enums::request::Request;
+use torrust_actix::udp::structs::announce_request::AnnounceRequest;
+use torrust_actix::udp::structs::number_of_peers::NumberOfPeers;
+use torrust_actix::udp::structs::peer_key::PeerKey;
+use torrust_actix::udp::structs::port::Port;
+use torrust_actix::udp::structs::transaction_id::TransactionId;
+use torrust_actix::udp::structs::udp_server::UdpServer;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8
+    {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout)
+    {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const SWARM_SIZES: [u32; 4] = [10, 1_000, 10_000, 50_000];
+const ANNOUNCING_PEERS: u32 = 1_000;
+
+fn remote_addr(index: u32) -> SocketAddr
+{
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::from(0x0a00_0000 + index)), 6881)
+}
+
+async fn announce_packet(tracker: Arc<TorrentTracker>, info_hash: InfoHash, index: u32) -> (SocketAddr, Vec<u8>)
+{
+    let remote_addr = remote_addr(index);
+    let mut peer_id = [0u8; 20];
+    peer_id[..4].copy_from_slice(&index.to_be_bytes());
+    let mut bytes = vec![];
+    Request::from(AnnounceRequest {
+        connection_id: UdpServer::get_connection_id(&remote_addr, tracker).await,
+        transaction_id: TransactionId(index as i32),
+        info_hash,
+        peer_id: PeerId(peer_id),
+        bytes_downloaded: NumberOfBytes(0),
+        bytes_uploaded: NumberOfBytes(0),
+        bytes_left: NumberOfBytes(if index.is_multiple_of(2) { 0 } else { 1 }),
+        event: AnnounceEvent::Started,
+        ip_address: None,
+        key: PeerKey(index),
+        peers_wanted: NumberOfPeers(-1),
+        port: Port(6881),
+        options: vec![],
+        path: String::new(),
+    }).write(&mut bytes).unwrap();
+    (remote_addr, bytes)
+}
+
+#[tokio::main]
+async fn main()
+{
+    let count: u32 = std::env::args().skip(1).find_map(|argument| argument.parse().ok()).unwrap_or(10_000);
+    let mut config = Configuration::init();
+    config.database.path = String::from("sqlite::memory:");
+    let udp_trackers_config = Arc::new(config.udp_server[0].clone());
+    let tracker = Arc::new(TorrentTracker::new(Arc::new(config), false).await);
+
+    for (swarm, swarm_size) in SWARM_SIZES.iter().enumerate() {
+        let info_hash = InfoHash([swarm as u8; 20]);
+        for index in 0..*swarm_size {
+            let mut peer_id = [0u8; 20];
+            peer_id[..4].copy_from_slice(&index.to_be_bytes());
+            tracker.add_torrent_peer(info_hash, PeerId(peer_id), TorrentPeer {
+                peer_id: PeerId(peer_id),
+                peer_addr: remote_addr(index),
+                peer_addr_alternate: None,
+                tracker_id: None,
+                updated: std::time::Instant::now(),
+                uploaded: NumberOfBytes(0),
+                downloaded: NumberOfBytes(0),
+                left: NumberOfBytes(if index.is_multiple_of(2) { 0 } else { 1 }),
+                event: AnnounceEvent::Started,
+            }, false);
+        }
+        let mut packets = Vec::with_capacity(ANNOUNCING_PEERS as usize);
+        for index in 0..ANNOUNCING_PEERS.min(*swarm_size) {
+            packets.push(announce_packet(tracker.clone(), info_hash, index).await);
+        }
+
+        let (allocations, allocated_bytes) = (ALLOCATIONS.load(Ordering::Relaxed), ALLOCATED_BYTES.load(Ordering::Relaxed));
+        let start = Instant::now();
+        for index in 0..count {
+            let (remote_addr, payload) = &packets[index as usize % packets.len()];
+            let _ = UdpServer::handle_packet(*remote_addr, payload, tracker.clone(), udp_trackers_config.clone(), Arc::new(vec![])).await;
+        }
+        let elapsed = start.elapsed();
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+        let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated_bytes;
+        println!(
+            "swarm {:>6} peers: {:>8.1} allocations and {:>8.0} bytes per announce, {:>10.0} announces/s",
+            swarm_size,
+            allocations as f64 / count as f64,
+            allocated_bytes as f64 / count as f64,
+            count as f64 / elapsed.as_secs_f64()
+        );
+    }
+}
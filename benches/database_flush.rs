@@ -0,0 +1,61 @@
+//! Measures how long the SQLite engine takes to flush dirty torrents, one row per statement against multi-row batches.
+//!
+//! Run with `cargo bench --bench database_flush [-- <torrents>]`.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
+use torrust_actix::config::structs::configuration::Configuration;
+use torrust_actix::database::structs::database_connector_sqlite::DatabaseConnectorSQLite;
+use torrust_actix::tracker::enums::updates_action::UpdatesAction;
+use torrust_actix::tracker::structs::info_hash::InfoHash;
+use torrust_actix::tracker::structs::torrent_entry::TorrentEntry;
+use torrust_actix::tracker::structs::torrent_tracker::TorrentTracker;
+
+const BATCH_SIZES: [u64; 4] = [1, 100, 1_000, 4_096];
+
+fn dirty_torrents(count: u32, completed: u64) -> BTreeMap<InfoHash, (TorrentEntry, UpdatesAction)>
+{
+    (0..count).map(|index| {
+        let mut info_hash = [0u8; 20];
+        info_hash[..4].copy_from_slice(&index.to_be_bytes());
+        let mut torrent_entry = TorrentEntry::new();
+        torrent_entry.completed = completed;
+        (InfoHash(info_hash), (torrent_entry, UpdatesAction::Update))
+    }).collect()
+}
+
+#[tokio::main]
+async fn main()
+{
+    let count: u32 = std::env::args().skip(1).find_map(|argument| argument.parse().ok()).unwrap_or(100_000);
+    for batch_size in BATCH_SIZES {
+        let path = std::env::temp_dir().join(format!("torrust-actix-flush-{}-{}.db", std::process::id(), batch_size));
+        let _ = std::fs::remove_file(&path);
+        let mut config = Configuration::init();
+        config.database.path = format!("sqlite://{}", path.display());
+        config.database.insert_vacant = true;
+        config.database.update_peers = true;
+        config.database.batch_size = batch_size;
+        let tracker = Arc::new(TorrentTracker::new(Arc::new(config.clone()), false).await);
+        let pool = DatabaseConnectorSQLite::create(config.database.path.as_str()).await.unwrap();
+        sqlx::query("CREATE TABLE `torrents` (`infohash` BLOB PRIMARY KEY NOT NULL, `seeds` INTEGER DEFAULT 0, `peers` INTEGER DEFAULT 0, `completed` INTEGER DEFAULT 0)").execute(&pool).await.unwrap();
+
+        for (label, completed) in [("insert", 1), ("update", 2)] {
+            let torrents = dirty_torrents(count, completed);
+            let start = Instant::now();
+            tracker.sqlx.save_torrents(tracker.clone(), torrents).await.unwrap();
+            let elapsed = start.elapsed();
+            println!(
+                "batch {:>5} {}: {} torrents in {:>8.3} s, {:>9.0} torrents/s",
+                batch_size,
+                label,
+                count,
+                elapsed.as_secs_f64(),
+                count as f64 / elapsed.as_secs_f64()
+            );
+        }
+        pool.close().await;
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,103 @@
+//! Measures how many packets per second `UdpServer::handle_packet` processes, without any socket I/O.
+//!
+//! Run with `cargo bench --bench udp_handle_packet [-- <packets>]`.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Instant;
+use torrust_actix::common::structs::number_of_bytes::NumberOfBytes;
+use torrust_actix::config::structs::configuration::Configuration;
+use torrust_actix::config::structs::udp_trackers_config::UdpTrackersConfig;
+use torrust_actix::tracker::enums::announce_event::AnnounceEvent;
+use torrust_actix::tracker::structs::info_hash::InfoHash;
+use torrust_actix::tracker::structs::peer_id::PeerId;
+use torrust_actix::tracker::structs::torrent_tracker::TorrentTracker;
+use torrust_actix::udp::enums::request::Request;
+use torrust_actix::udp::structs::announce_request::AnnounceRequest;
+use torrust_actix::udp::structs::connect_request::ConnectRequest;
+use torrust_actix::udp::structs::number_of_peers::NumberOfPeers;
+use torrust_actix::udp::structs::peer_key::PeerKey;
+use torrust_actix::udp::structs::port::Port;
+use torrust_actix::udp::structs::scrape_request::ScrapeRequest;
+use torrust_actix::udp::structs::transaction_id::TransactionId;
+use torrust_actix::udp::structs::udp_server::UdpServer;
+
+const TORRENTS: u32 = 10_000;
+const CLIENTS: u32 = 4_096;
+
+fn remote_addr(index: u32) -> SocketAddr
+{
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::from(0x0a00_0000 + index)), 6881)
+}
+
+fn info_hash(index: u32) -> InfoHash
+{
+    let mut info_hash = [0u8; 20];
+    info_hash[..4].copy_from_slice(&index.to_be_bytes());
+    InfoHash(info_hash)
+}
+
+fn encode(request: Request) -> Vec<u8>
+{
+    let mut bytes = vec![];
+    request.write(&mut bytes).unwrap();
+    bytes
+}
+
+async fn run(name: &str, packets: &[(SocketAddr, Vec<u8>)], tracker: Arc<TorrentTracker>, udp_trackers_config: Arc<UdpTrackersConfig>)
+{
+    let start = Instant::now();
+    for (remote_addr, payload) in packets {
+        let _ = UdpServer::handle_packet(*remote_addr, payload, tracker.clone(), udp_trackers_config.clone(), Arc::new(vec![])).await;
+    }
+    let elapsed = start.elapsed();
+    println!("{:<10} {:>10} packets in {:>8.3}s = {:>12.0} packets/s", name, packets.len(), elapsed.as_secs_f64(), packets.len() as f64 / elapsed.as_secs_f64());
+}
+
+#[tokio::main]
+async fn main()
+{
+    let count: u32 = std::env::args().skip(1).find_map(|argument| argument.parse().ok()).unwrap_or(300_000);
+    let mut config = Configuration::init();
+    config.database.path = String::from("sqlite::memory:");
+    let udp_trackers_config = Arc::new(config.udp_server[0].clone());
+    let tracker = Arc::new(TorrentTracker::new(Arc::new(config), false).await);
+
+    let connects: Vec<(SocketAddr, Vec<u8>)> = (0..count).map(|index| {
+        (remote_addr(index % CLIENTS), encode(Request::from(ConnectRequest { transaction_id: TransactionId(index as i32) })))
+    }).collect();
+
+    let mut announces = Vec::with_capacity(count as usize);
+    let mut scrapes = Vec::with_capacity(count as usize);
+    for index in 0..count {
+        let remote_addr = remote_addr(index % CLIENTS);
+        let connection_id = UdpServer::get_connection_id(&remote_addr, tracker.clone()).await;
+        let mut peer_id = [0u8; 20];
+        peer_id[..4].copy_from_slice(&(index % CLIENTS).to_be_bytes());
+        announces.push((remote_addr, encode(Request::from(AnnounceRequest {
+            connection_id,
+            transaction_id: TransactionId(index as i32),
+            info_hash: info_hash(index % TORRENTS),
+            peer_id: PeerId(peer_id),
+            bytes_downloaded: NumberOfBytes(0),
+            bytes_uploaded: NumberOfBytes(0),
+            bytes_left: NumberOfBytes(if index % 2 == 0 { 0 } else { 1 }),
+            event: AnnounceEvent::Started,
+            ip_address: None,
+            key: PeerKey(index),
+            peers_wanted: NumberOfPeers(-1),
+            port: Port(6881),
+            options: vec![],
+            path: String::new(),
+        }))));
+        scrapes.push((remote_addr, encode(Request::from(ScrapeRequest {
+            connection_id,
+            transaction_id: TransactionId(index as i32),
+            info_hashes: (0..10).map(|offset| info_hash((index + offset) % TORRENTS)).collect(),
+        }))));
+    }
+
+    run("connect", &connects, tracker.clone(), udp_trackers_config.clone()).await;
+    run("announce", &announces, tracker.clone(), udp_trackers_config.clone()).await;
+    run("scrape", &scrapes, tracker.clone(), udp_trackers_config.clone()).await;
+}
@@ -17,7 +17,7 @@ use serde_json::json;
 use utoipa_swagger_ui::{Config, SwaggerUi};
 use crate::api::api_blacklists::{api_service_blacklist_delete, api_service_blacklist_get, api_service_blacklist_post, api_service_blacklists_delete, api_service_blacklists_get, api_service_blacklists_post};
 use crate::api::api_keys::{api_service_key_delete, api_service_key_get, api_service_key_post, api_service_keys_delete, api_service_keys_get, api_service_keys_post};
-use crate::api::api_stats::{api_service_prom_get, api_service_stats_get};
+use crate::api::api_stats::{api_service_prom_get, api_service_stats_get, api_service_stats_shards_get};
 use crate::api::api_torrents::{api_service_torrent_delete, api_service_torrent_get, api_service_torrent_post, api_service_torrents_delete, api_service_torrents_get, api_service_torrents_post};
 use crate::api::api_users::{api_service_user_delete, api_service_user_get, api_service_user_post, api_service_users_delete, api_service_users_get, api_service_users_post};
 use crate::api::api_whitelists::{api_service_whitelist_delete, api_service_whitelist_get, api_service_whitelist_post, api_service_whitelists_delete, api_service_whitelists_get, api_service_whitelists_post};
@@ -49,6 +49,9 @@ pub fn api_service_routes(data: Arc<ApiServiceData>) -> Box<dyn Fn(&mut ServiceC
         cfg.service(web::resource("stats")
             .route(web::get().to(api_service_stats_get))
         );
+        cfg.service(web::resource("stats/shards")
+            .route(web::get().to(api_service_stats_shards_get))
+        );
         cfg.service(web::resource("metrics")
             .route(web::get().to(api_service_prom_get))
         );
@@ -5,6 +5,7 @@ use actix_web::web::Data;
 use crate::api::api::{api_service_token, api_validation};
 use crate::api::structs::api_service_data::ApiServiceData;
 use crate::api::structs::query_token::QueryToken;
+use crate::stats::enums::error_family::ErrorFamily;
 
 #[tracing::instrument(level = "debug")]
 pub async fn api_service_stats_get(request: HttpRequest, data: Data<Arc<ApiServiceData>>) -> HttpResponse
@@ -16,9 +17,23 @@ pub async fn api_service_stats_get(request: HttpRequest, data: Data<Arc<ApiServi
     let params = web::Query::<QueryToken>::from_query(request.query_string()).unwrap();
     if let Some(response) = api_service_token(params.token.clone(), data.torrent_tracker.config.clone()).await { return response; }
 
+    data.torrent_tracker.update_memory_stats();
     HttpResponse::Ok().content_type(ContentType::json()).json(data.torrent_tracker.get_stats())
 }
 
+#[tracing::instrument(level = "debug")]
+pub async fn api_service_stats_shards_get(request: HttpRequest, data: Data<Arc<ApiServiceData>>) -> HttpResponse
+{
+    // Validate client
+    if let Some(error_return) = api_validation(&request, &data).await { return error_return; }
+
+    // Parse the Params
+    let params = web::Query::<QueryToken>::from_query(request.query_string()).unwrap();
+    if let Some(response) = api_service_token(params.token.clone(), data.torrent_tracker.config.clone()).await { return response; }
+
+    HttpResponse::Ok().content_type(ContentType::json()).json(data.torrent_tracker.get_shard_stats())
+}
+
 #[tracing::instrument(level = "debug")]
 pub async fn api_service_prom_get(request: HttpRequest, data: Data<Arc<ApiServiceData>>) -> HttpResponse
 {
@@ -30,6 +45,7 @@ pub async fn api_service_prom_get(request: HttpRequest, data: Data<Arc<ApiServic
     if let Some(response) = api_service_token(params.token.clone(), data.torrent_tracker.config.clone()).await { return response; }
 
     // Get stats
+    data.torrent_tracker.update_memory_stats();
     let stats = data.torrent_tracker.get_stats();
 
     // Build Prometheus Output
@@ -49,6 +65,22 @@ pub async fn api_service_prom_get(request: HttpRequest, data: Data<Arc<ApiServic
     string_output.extend(api_service_prom_generate_line(prometheus_id, "gauge", "blacklist_updates", stats.blacklist_updates, false, None));
     string_output.extend(api_service_prom_generate_line(prometheus_id, "gauge", "keys", stats.keys, false, None));
     string_output.extend(api_service_prom_generate_line(prometheus_id, "gauge", "keys_updates", stats.keys_updates, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "gauge", "memory_torrents", stats.memory_torrents, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "gauge", "memory_whitelist", stats.memory_whitelist, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "gauge", "memory_blacklist", stats.memory_blacklist, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "gauge", "memory_keys", stats.memory_keys, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "gauge", "memory_users", stats.memory_users, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "gauge", "memory_updates", stats.memory_updates, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "gauge", "flush_queue", stats.flush_queue, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "flush_failures", stats.flush_failures, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "gauge", "flush_journal", stats.flush_journal, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "flush_replayed", stats.flush_replayed, false, None));
+    for (shard, shard_stats) in data.torrent_tracker.get_shard_stats().iter().enumerate() {
+        string_output.extend(api_service_prom_generate_shard_line(prometheus_id, "shard_torrents", shard, shard_stats.torrents));
+        string_output.extend(api_service_prom_generate_shard_line(prometheus_id, "shard_seeds", shard, shard_stats.seeds));
+        string_output.extend(api_service_prom_generate_shard_line(prometheus_id, "shard_peers", shard, shard_stats.peers));
+        string_output.extend(api_service_prom_generate_shard_line(prometheus_id, "shard_memory", shard, shard_stats.memory));
+    }
 
     string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "tcp4_not_found", stats.tcp4_not_found, true, Some(format!("{} counter metrics", prometheus_id).as_str())));
     string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "tcp4_failure", stats.tcp4_failure, false, None));
@@ -62,6 +94,8 @@ pub async fn api_service_prom_get(request: HttpRequest, data: Data<Arc<ApiServic
     string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "tcp6_api_handled", stats.tcp6_api_handled, false, None));
     string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "tcp6_announces_handled", stats.tcp6_announces_handled, false, None));
     string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "tcp6_scrapes_handled", stats.tcp6_scrapes_handled, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "tcp_tracker_id_issued", stats.tcp_tracker_id_issued, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "tcp_tracker_id_recognized", stats.tcp_tracker_id_recognized, false, None));
 
     string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "udp4_bad_request", stats.udp4_bad_request, true, Some(format!("{} counter metrics", prometheus_id).as_str())));
     string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "udp4_invalid_request", stats.udp4_invalid_request, false, None));
@@ -73,6 +107,21 @@ pub async fn api_service_prom_get(request: HttpRequest, data: Data<Arc<ApiServic
     string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "udp6_connections_handled", stats.udp6_connections_handled, false, None));
     string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "udp6_announces_handled", stats.udp6_announces_handled, false, None));
     string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "udp6_scrapes_handled", stats.udp6_scrapes_handled, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "udp_connection_id_invalid", stats.udp_connection_id_invalid, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "udp_announce_ip_overridden", stats.udp_announce_ip_overridden, false, None));
+
+    for family in ErrorFamily::ALL {
+        for (error, count) in stats.errors.family(family) {
+            string_output.extend(api_service_prom_generate_error_line(prometheus_id, family.label(), error.kind(), count));
+        }
+    }
+
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "announces_throttled", stats.announces_throttled, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "gauge", "expiry_queue", stats.expiry_queue, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "peers_expired", stats.peers_expired, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "torrents_expired", stats.torrents_expired, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "expiry_stale", stats.expiry_stale, false, None));
+    string_output.extend(api_service_prom_generate_line(prometheus_id, "counter", "torrents_evicted", stats.torrents_evicted, false, None));
 
     HttpResponse::Ok().content_type(ContentType::plaintext()).body(string_output.join("\n"))
 }
@@ -89,4 +138,18 @@ pub fn api_service_prom_generate_line(id: &str, type_metric: &str, metric: &str,
     vec![
         format!("{}_{}{{metric=\"{}\"}} {}", id, type_metric, metric, value).to_string(),
     ]
-}
\ No newline at end of file
+}
+
+pub fn api_service_prom_generate_error_line(id: &str, family: &str, kind: &str, value: i64) -> Vec<String>
+{
+    vec![
+        format!("{}_counter{{metric=\"errors\",family=\"{}\",kind=\"{}\"}} {}", id, family, kind, value).to_string(),
+    ]
+}
+
+pub fn api_service_prom_generate_shard_line(id: &str, metric: &str, shard: usize, value: i64) -> Vec<String>
+{
+    vec![
+        format!("{}_gauge{{metric=\"{}\",shard=\"{}\"}} {}", id, metric, shard, value).to_string(),
+    ]
+}
@@ -102,6 +102,7 @@ pub async fn api_service_torrent_post(request: HttpRequest, path: web::Path<(Str
             peers: BTreeMap::new(),
             completed,
             updated: std::time::Instant::now(),
+            peers_cursor: 0,
         };
 
         if data.torrent_tracker.config.database.clone().persistent {
@@ -150,6 +151,7 @@ pub async fn api_service_torrents_post(request: HttpRequest, payload: web::Paylo
                 peers: BTreeMap::new(),
                 completed,
                 updated: std::time::Instant::now(),
+                peers_cursor: 0,
             };
 
             if data.torrent_tracker.config.database.clone().persistent {
@@ -254,6 +256,7 @@ pub fn api_service_torrents_return_torrent_json(torrent: TorrentEntry) -> Value
         json!({
             "peer_id": peer_id.clone(),
             "peer_addr": torrent_peer.peer_addr.clone(),
+            "peer_addr_alternate": torrent_peer.peer_addr_alternate,
             "updated": timestamp_final,
             "uploaded": torrent_peer.uploaded.0 as u64,
             "downloaded": torrent_peer.downloaded.0 as u64,
@@ -268,6 +271,7 @@ pub fn api_service_torrents_return_torrent_json(torrent: TorrentEntry) -> Value
         json!({
             "peer_id": peer_id.clone(),
             "peer_addr": torrent_peer.peer_addr.clone(),
+            "peer_addr_alternate": torrent_peer.peer_addr_alternate,
             "updated": timestamp_final,
             "uploaded": torrent_peer.uploaded.0 as u64,
             "downloaded": torrent_peer.downloaded.0 as u64,
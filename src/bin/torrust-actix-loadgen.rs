@@ -0,0 +1,21 @@
+use clap::Parser;
+use torrust_actix::client::structs::load_generator::LoadGenerator;
+
+#[tokio::main]
+async fn main()
+{
+    let load_generator = LoadGenerator::parse();
+    match load_generator.run().await {
+        Ok(report) => {
+            let seconds = report.elapsed.as_secs_f64();
+            println!("Announces: {} ({:.0}/s)", report.announces, report.announces as f64 / seconds);
+            println!("Scrapes: {} ({:.0}/s)", report.scrapes, report.scrapes as f64 / seconds);
+            println!("Errors: {}", report.errors);
+            println!("Elapsed: {:.3}s", seconds);
+        }
+        Err(error) => {
+            eprintln!("Load generator failed: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod client_error;
+pub mod tracker_client;
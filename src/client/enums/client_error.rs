@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("http error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("tracker did not answer in time")]
+    Timeout,
+
+    #[error("tracker failure: {0}")]
+    Failure(String),
+
+    #[error("invalid tracker response: {0}")]
+    InvalidResponse(String),
+
+    #[error("invalid tracker url: {0}")]
+    InvalidUrl(String),
+}
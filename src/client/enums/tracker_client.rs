@@ -0,0 +1,8 @@
+use crate::client::structs::http_tracker_client::HttpTrackerClient;
+use crate::client::structs::udp_tracker_client::UdpTrackerClient;
+
+#[derive(Debug)]
+pub enum TrackerClient {
+    Udp(UdpTrackerClient),
+    Http(HttpTrackerClient),
+}
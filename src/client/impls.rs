@@ -0,0 +1,4 @@
+pub mod udp_tracker_client;
+pub mod http_tracker_client;
+pub mod tracker_client;
+pub mod load_generator;
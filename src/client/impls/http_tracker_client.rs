@@ -0,0 +1,156 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use bip_bencode::{BDecodeOpt, BRefAccess, BencodeRef};
+use percent_encoding::{percent_encode, NON_ALPHANUMERIC};
+use crate::client::enums::client_error::ClientError;
+use crate::client::structs::client_announce_request::ClientAnnounceRequest;
+use crate::client::structs::client_announce_response::ClientAnnounceResponse;
+use crate::client::structs::client_scrape_response::{ClientScrapeResponse, ClientScrapeStatistics};
+use crate::client::structs::http_tracker_client::HttpTrackerClient;
+use crate::tracker::enums::announce_event::AnnounceEvent;
+use crate::tracker::structs::info_hash::InfoHash;
+
+impl HttpTrackerClient {
+    pub fn new(announce_url: &str, timeout: Duration) -> Result<HttpTrackerClient, ClientError>
+    {
+        if !announce_url.starts_with("http://") && !announce_url.starts_with("https://") {
+            return Err(ClientError::InvalidUrl(announce_url.to_string()));
+        }
+        Ok(HttpTrackerClient {
+            client: reqwest::Client::builder().timeout(timeout).build()?,
+            announce_url: announce_url.to_string(),
+        })
+    }
+
+    /// The scrape URL derived from the announce URL by the BEP 48 convention, if the last path segment starts with `announce`.
+    pub fn scrape_url(&self) -> Option<String>
+    {
+        let (base, query) = match self.announce_url.split_once('?') {
+            Some((base, query)) => { (base, Some(query)) }
+            None => { (self.announce_url.as_str(), None) }
+        };
+        let position = base.rfind('/')?;
+        let (prefix, segment) = base.split_at(position + 1);
+        let rest = segment.strip_prefix("announce")?;
+        let mut url = format!("{}scrape{}", prefix, rest);
+        if let Some(query) = query {
+            url = format!("{}?{}", url, query);
+        }
+        Some(url)
+    }
+
+    pub async fn announce(&self, announce: &ClientAnnounceRequest) -> Result<ClientAnnounceResponse, ClientError>
+    {
+        let mut query = vec![
+            format!("info_hash={}", percent_encode(&announce.info_hash.0, NON_ALPHANUMERIC)),
+            format!("peer_id={}", percent_encode(&announce.peer_id.0, NON_ALPHANUMERIC)),
+            format!("port={}", announce.port),
+            format!("uploaded={}", announce.uploaded),
+            format!("downloaded={}", announce.downloaded),
+            format!("left={}", announce.left),
+            format!("key={:08x}", announce.key),
+            String::from("compact=1"),
+        ];
+        match announce.event {
+            AnnounceEvent::Started => { query.push(String::from("event=started")); }
+            AnnounceEvent::Stopped => { query.push(String::from("event=stopped")); }
+            AnnounceEvent::Completed => { query.push(String::from("event=completed")); }
+            AnnounceEvent::None => {}
+        }
+        if let Some(ip) = announce.ip {
+            query.push(format!("ip={}", ip));
+        }
+        if let Some(numwant) = announce.numwant {
+            query.push(format!("numwant={}", numwant));
+        }
+        let body = self.get(&self.announce_url, &query.join("&")).await?;
+        HttpTrackerClient::decode_announce(&body)
+    }
+
+    pub async fn scrape(&self, info_hashes: &[InfoHash]) -> Result<ClientScrapeResponse, ClientError>
+    {
+        let scrape_url = self.scrape_url().ok_or_else(|| ClientError::InvalidUrl(self.announce_url.clone()))?;
+        let query: Vec<String> = info_hashes.iter().map(|info_hash| format!("info_hash={}", percent_encode(&info_hash.0, NON_ALPHANUMERIC))).collect();
+        let body = self.get(&scrape_url, &query.join("&")).await?;
+        HttpTrackerClient::decode_scrape(&body)
+    }
+
+    async fn get(&self, url: &str, query: &str) -> Result<Vec<u8>, ClientError>
+    {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let response = self.client.get(format!("{}{}{}", url, separator, query)).send().await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Decodes a bencoded announce response, accepting compact and dictionary peer lists.
+    pub fn decode_announce(body: &[u8]) -> Result<ClientAnnounceResponse, ClientError>
+    {
+        let decoded = BencodeRef::decode(body, BDecodeOpt::default()).map_err(|error| ClientError::InvalidResponse(error.to_string()))?;
+        let dict = decoded.dict().ok_or_else(|| ClientError::InvalidResponse(String::from("response is not a dictionary")))?;
+        if let Some(reason) = dict.lookup(b"failure reason") {
+            return Err(ClientError::Failure(String::from_utf8_lossy(reason.bytes().unwrap_or_default()).to_string()));
+        }
+        let int = |key: &[u8]| dict.lookup(key).and_then(|value| value.int());
+        let string = |key: &[u8]| dict.lookup(key).and_then(|value| value.bytes()).map(|value| String::from_utf8_lossy(value).to_string());
+
+        let mut peers = vec![];
+        if let Some(value) = dict.lookup(b"peers") {
+            match (value.bytes(), value.list()) {
+                (Some(compact), _) => {
+                    peers.extend(compact.chunks_exact(6).map(|chunk| {
+                        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(chunk[0], chunk[1], chunk[2], chunk[3])), u16::from_be_bytes([chunk[4], chunk[5]]))
+                    }));
+                }
+                (None, Some(list)) => {
+                    for peer in list.into_iter() {
+                        let peer = peer.dict().ok_or_else(|| ClientError::InvalidResponse(String::from("peer is not a dictionary")))?;
+                        let ip = peer.lookup(b"ip").and_then(|ip| ip.str()).and_then(|ip| ip.parse::<IpAddr>().ok());
+                        let port = peer.lookup(b"port").and_then(|port| port.int());
+                        if let (Some(ip), Some(port)) = (ip, port) {
+                            peers.push(SocketAddr::new(ip, port as u16));
+                        }
+                    }
+                }
+                (None, None) => { return Err(ClientError::InvalidResponse(String::from("peers is neither a string nor a list"))); }
+            }
+        }
+        if let Some(compact) = dict.lookup(b"peers6").and_then(|value| value.bytes()) {
+            peers.extend(compact.chunks_exact(18).map(|chunk| {
+                let ip: [u8; 16] = chunk[..16].try_into().unwrap();
+                SocketAddr::new(IpAddr::V6(Ipv6Addr::from(ip)), u16::from_be_bytes([chunk[16], chunk[17]]))
+            }));
+        }
+
+        Ok(ClientAnnounceResponse {
+            interval: int(b"interval").ok_or_else(|| ClientError::InvalidResponse(String::from("missing interval")))?,
+            min_interval: int(b"min interval"),
+            seeders: int(b"complete").unwrap_or_default(),
+            leechers: int(b"incomplete").unwrap_or_default(),
+            peers,
+            tracker_id: string(b"tracker id"),
+            warning_message: string(b"warning message"),
+        })
+    }
+
+    pub fn decode_scrape(body: &[u8]) -> Result<ClientScrapeResponse, ClientError>
+    {
+        let decoded = BencodeRef::decode(body, BDecodeOpt::default()).map_err(|error| ClientError::InvalidResponse(error.to_string()))?;
+        let dict = decoded.dict().ok_or_else(|| ClientError::InvalidResponse(String::from("response is not a dictionary")))?;
+        if let Some(reason) = dict.lookup(b"failure reason") {
+            return Err(ClientError::Failure(String::from_utf8_lossy(reason.bytes().unwrap_or_default()).to_string()));
+        }
+        let files = dict.lookup(b"files").and_then(|files| files.dict()).ok_or_else(|| ClientError::InvalidResponse(String::from("missing files")))?;
+        let mut result = vec![];
+        for (info_hash, statistics) in files.to_list() {
+            let info_hash = <[u8; 20]>::try_from(*info_hash).map_err(|_| ClientError::InvalidResponse(String::from("invalid info_hash")))?;
+            let statistics = statistics.dict().ok_or_else(|| ClientError::InvalidResponse(String::from("file is not a dictionary")))?;
+            let int = |key: &[u8]| statistics.lookup(key).and_then(|value| value.int()).unwrap_or_default();
+            result.push((InfoHash(info_hash), ClientScrapeStatistics {
+                seeders: int(b"complete"),
+                completed: int(b"downloaded"),
+                leechers: int(b"incomplete"),
+            }));
+        }
+        Ok(ClientScrapeResponse { files: result })
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+use log::debug;
+use sha1::{Digest, Sha1};
+use crate::client::enums::client_error::ClientError;
+use crate::client::enums::tracker_client::TrackerClient;
+use crate::client::structs::client_announce_request::ClientAnnounceRequest;
+use crate::client::structs::load_generator::LoadGenerator;
+use crate::client::structs::load_generator_report::LoadGeneratorReport;
+use crate::tracker::enums::announce_event::AnnounceEvent;
+use crate::tracker::structs::info_hash::InfoHash;
+use crate::tracker::structs::peer_id::PeerId;
+use crate::udp::udp::MAX_SCRAPE_TORRENTS;
+
+impl LoadGenerator {
+    /// Spreads the peers over `concurrency` clients and lets every client announce its peers for
+    /// `rounds` rounds, replacing a `churn` fraction of them (stopped, then started fresh) after each round.
+    pub async fn run(&self) -> Result<LoadGeneratorReport, ClientError>
+    {
+        let start = Instant::now();
+        let concurrency = self.concurrency.max(1);
+        let mut handles = Vec::with_capacity(concurrency as usize);
+        for worker in 0..concurrency {
+            let client = TrackerClient::new(&self.tracker, Duration::from_secs(self.timeout), self.retransmit_max).await?;
+            let announces: Vec<ClientAnnounceRequest> = (worker..self.peers).step_by(concurrency as usize).map(|index| ClientAnnounceRequest {
+                info_hash: LoadGenerator::swarm_info_hash(index % self.swarms.max(1)),
+                peer_id: PeerId(rand::random()),
+                port: 10000 + (index % 50000) as u16,
+                uploaded: 0,
+                downloaded: 0,
+                left: if rand::random_bool(self.seed_ratio.clamp(0.0, 1.0)) { 0 } else { 1_000_000 },
+                event: AnnounceEvent::Started,
+                ip: None,
+                numwant: None,
+                key: rand::random(),
+            }).collect();
+            handles.push(tokio::spawn(LoadGenerator::run_worker(client, announces, self.rounds, self.churn.clamp(0.0, 1.0))));
+        }
+
+        let mut report = LoadGeneratorReport::default();
+        for handle in handles {
+            let worker_report = handle.await.map_err(|error| ClientError::Io(std::io::Error::other(error)))?;
+            report.announces += worker_report.announces;
+            report.scrapes += worker_report.scrapes;
+            report.errors += worker_report.errors;
+        }
+        report.elapsed = start.elapsed();
+        Ok(report)
+    }
+
+    async fn run_worker(mut client: TrackerClient, mut announces: Vec<ClientAnnounceRequest>, rounds: u32, churn: f64) -> LoadGeneratorReport
+    {
+        let mut report = LoadGeneratorReport::default();
+        let info_hashes: Vec<InfoHash> = announces.iter().map(|announce| announce.info_hash).collect::<BTreeSet<InfoHash>>().into_iter().take(MAX_SCRAPE_TORRENTS as usize).collect();
+        for _round in 0..rounds {
+            for announce in announces.iter_mut() {
+                LoadGenerator::record(&mut report, client.announce(announce).await.map(|_| ()));
+                report.announces += 1;
+                announce.event = AnnounceEvent::None;
+            }
+            for announce in announces.iter_mut() {
+                if !rand::random_bool(churn) {
+                    continue;
+                }
+                announce.event = AnnounceEvent::Stopped;
+                LoadGenerator::record(&mut report, client.announce(announce).await.map(|_| ()));
+                report.announces += 1;
+                announce.peer_id = PeerId(rand::random());
+                announce.event = AnnounceEvent::Started;
+            }
+            if !info_hashes.is_empty() {
+                LoadGenerator::record(&mut report, client.scrape(&info_hashes).await.map(|_| ()));
+                report.scrapes += 1;
+            }
+        }
+        report
+    }
+
+    fn record(report: &mut LoadGeneratorReport, result: Result<(), ClientError>)
+    {
+        if let Err(error) = result {
+            debug!("[LOADGEN] Request failed: {}", error);
+            report.errors += 1;
+        }
+    }
+
+    /// Deterministic info_hash per swarm, hashed so swarms spread over all shards.
+    pub fn swarm_info_hash(swarm: u32) -> InfoHash
+    {
+        InfoHash(Sha1::digest(swarm.to_be_bytes()).into())
+    }
+}
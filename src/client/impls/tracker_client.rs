@@ -0,0 +1,42 @@
+use std::time::Duration;
+use crate::client::enums::client_error::ClientError;
+use crate::client::enums::tracker_client::TrackerClient;
+use crate::client::structs::client_announce_request::ClientAnnounceRequest;
+use crate::client::structs::client_announce_response::ClientAnnounceResponse;
+use crate::client::structs::client_scrape_response::ClientScrapeResponse;
+use crate::client::structs::http_tracker_client::HttpTrackerClient;
+use crate::client::structs::udp_tracker_client::UdpTrackerClient;
+use crate::tracker::structs::info_hash::InfoHash;
+
+impl TrackerClient {
+    /// Picks the UDP or HTTP client from the URL scheme, e.g. `udp://tracker:6969/announce` or `http://tracker/announce`.
+    /// The `retransmit_max` only applies to UDP, HTTP requests are sent once.
+    pub async fn new(url: &str, timeout: Duration, retransmit_max: u32) -> Result<TrackerClient, ClientError>
+    {
+        if let Some(rest) = url.strip_prefix("udp://") {
+            let (host, path) = match rest.find('/') {
+                Some(position) => { rest.split_at(position) }
+                None => { (rest, "") }
+            };
+            let tracker_addr = tokio::net::lookup_host(host).await?.next().ok_or_else(|| ClientError::InvalidUrl(url.to_string()))?;
+            return Ok(TrackerClient::Udp(UdpTrackerClient::new(tracker_addr, path, timeout, retransmit_max).await?));
+        }
+        Ok(TrackerClient::Http(HttpTrackerClient::new(url, timeout)?))
+    }
+
+    pub async fn announce(&mut self, announce: &ClientAnnounceRequest) -> Result<ClientAnnounceResponse, ClientError>
+    {
+        match self {
+            TrackerClient::Udp(client) => { client.announce(announce).await }
+            TrackerClient::Http(client) => { client.announce(announce).await }
+        }
+    }
+
+    pub async fn scrape(&mut self, info_hashes: &[InfoHash]) -> Result<ClientScrapeResponse, ClientError>
+    {
+        match self {
+            TrackerClient::Udp(client) => { client.scrape(info_hashes).await }
+            TrackerClient::Http(client) => { client.scrape(info_hashes).await }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+use crate::client::enums::client_error::ClientError;
+use crate::client::structs::client_announce_request::ClientAnnounceRequest;
+use crate::client::structs::client_announce_response::ClientAnnounceResponse;
+use crate::client::structs::client_scrape_response::{ClientScrapeResponse, ClientScrapeStatistics};
+use crate::client::structs::udp_tracker_client::UdpTrackerClient;
+use crate::common::structs::number_of_bytes::NumberOfBytes;
+use crate::tracker::structs::info_hash::InfoHash;
+use crate::udp::enums::announce_option::AnnounceOption;
+use crate::udp::enums::request::Request;
+use crate::udp::enums::response::Response;
+use crate::udp::structs::announce_request::AnnounceRequest;
+use crate::udp::structs::connect_request::ConnectRequest;
+use crate::udp::structs::connection_id::ConnectionId;
+use crate::udp::structs::number_of_peers::NumberOfPeers;
+use crate::udp::structs::peer_key::PeerKey;
+use crate::udp::structs::port::Port;
+use crate::udp::structs::scrape_request::ScrapeRequest;
+use crate::udp::structs::transaction_id::TransactionId;
+use crate::udp::udp::MAX_SCRAPE_TORRENTS;
+
+impl UdpTrackerClient {
+    /// BEP 15 lets a client reuse a connection id for one minute.
+    pub const CONNECTION_ID_LIFETIME: Duration = Duration::from_secs(60);
+    /// BEP 15 waits `15 * 2 ^ n` seconds for the response to the n-th transmission of a request.
+    pub const RETRANSMIT_TIMEOUT: Duration = Duration::from_secs(15);
+    /// BEP 15 gives up after the request was retransmitted 8 times.
+    pub const RETRANSMIT_MAX: u32 = 8;
+
+    /// Binds an ephemeral socket of the tracker's address family. The `path` is sent as BEP 41 URL data. A request is
+    /// retransmitted when no response arrived within `timeout * 2 ^ n`, up to `retransmit_max` times.
+    pub async fn new(tracker_addr: SocketAddr, path: &str, timeout: Duration, retransmit_max: u32) -> Result<UdpTrackerClient, ClientError>
+    {
+        let bind_address = match tracker_addr {
+            SocketAddr::V4(_) => { SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0) }
+            SocketAddr::V6(_) => { SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0) }
+        };
+        Ok(UdpTrackerClient {
+            socket: UdpSocket::bind(bind_address).await?,
+            tracker_addr,
+            path: path.to_string(),
+            connection_id: None,
+            timeout,
+            retransmit_max,
+        })
+    }
+
+    pub async fn connect(&mut self) -> Result<ConnectionId, ClientError>
+    {
+        let transaction_id = TransactionId(rand::random());
+        let response = self.send_request(Request::from(ConnectRequest { transaction_id }), &mut 0, None).await?;
+        match response.ok_or(ClientError::Timeout)? {
+            Response::Connect(response) => {
+                self.connection_id = Some((response.connection_id, Instant::now()));
+                Ok(response.connection_id)
+            }
+            response => { Err(ClientError::InvalidResponse(format!("expected a connect response, got {:?}", response))) }
+        }
+    }
+
+    pub async fn announce(&mut self, announce: &ClientAnnounceRequest) -> Result<ClientAnnounceResponse, ClientError>
+    {
+        let path = self.path.clone();
+        let response = self.send_connected_request(|connection_id| Request::from(AnnounceRequest {
+            connection_id,
+            transaction_id: TransactionId(rand::random()),
+            info_hash: announce.info_hash,
+            peer_id: announce.peer_id,
+            bytes_downloaded: NumberOfBytes(announce.downloaded as i64),
+            bytes_uploaded: NumberOfBytes(announce.uploaded as i64),
+            bytes_left: NumberOfBytes(announce.left as i64),
+            event: announce.event,
+            ip_address: announce.ip,
+            key: PeerKey(announce.key),
+            peers_wanted: NumberOfPeers(announce.numwant.map_or(-1, |numwant| numwant as i32)),
+            port: Port(announce.port),
+            options: path.as_bytes().chunks(u8::MAX as usize).map(|chunk| AnnounceOption::UrlData(chunk.to_vec())).collect(),
+            path: path.clone(),
+        })).await?;
+        let (interval, leechers, seeders, peers) = match response {
+            Response::AnnounceIpv4(response) => {
+                let peers = response.peers.iter().map(|peer| SocketAddr::new(IpAddr::V4(peer.ip_address), peer.port.0)).collect();
+                (response.announce_interval.0, response.leechers.0, response.seeders.0, peers)
+            }
+            Response::AnnounceIpv6(response) => {
+                let peers = response.peers.iter().map(|peer| SocketAddr::new(IpAddr::V6(peer.ip_address), peer.port.0)).collect();
+                (response.announce_interval.0, response.leechers.0, response.seeders.0, peers)
+            }
+            response => { return Err(ClientError::InvalidResponse(format!("expected an announce response, got {:?}", response))); }
+        };
+        Ok(ClientAnnounceResponse {
+            interval: interval as i64,
+            min_interval: None,
+            seeders: seeders as i64,
+            leechers: leechers as i64,
+            peers,
+            tracker_id: None,
+            warning_message: None,
+        })
+    }
+
+    pub async fn scrape(&mut self, info_hashes: &[InfoHash]) -> Result<ClientScrapeResponse, ClientError>
+    {
+        let mut files = Vec::with_capacity(info_hashes.len());
+        for chunk in info_hashes.chunks(MAX_SCRAPE_TORRENTS as usize) {
+            let response = self.send_connected_request(|connection_id| Request::from(ScrapeRequest {
+                connection_id,
+                transaction_id: TransactionId(rand::random()),
+                info_hashes: chunk.to_vec(),
+            })).await?;
+            match response {
+                Response::Scrape(response) => {
+                    for (info_hash, statistics) in chunk.iter().zip(response.torrent_stats.iter()) {
+                        files.push((*info_hash, ClientScrapeStatistics {
+                            seeders: statistics.seeders.0 as i64,
+                            completed: statistics.completed.0 as i64,
+                            leechers: statistics.leechers.0 as i64,
+                        }));
+                    }
+                }
+                response => { return Err(ClientError::InvalidResponse(format!("expected a scrape response, got {:?}", response))); }
+            }
+        }
+        Ok(ClientScrapeResponse { files })
+    }
+
+    /// Sends a request built around a connection id younger than `CONNECTION_ID_LIFETIME`. When the connection id
+    /// expires while the request is being retransmitted, a new one is requested and the retransmissions go on with it.
+    async fn send_connected_request(&mut self, build_request: impl Fn(ConnectionId) -> Request) -> Result<Response, ClientError>
+    {
+        let mut transmissions = 0;
+        loop {
+            let (connection_id, issued) = match self.connection_id {
+                Some((connection_id, issued)) if issued.elapsed() < Self::CONNECTION_ID_LIFETIME => { (connection_id, issued) }
+                _ => { (self.connect().await?, Instant::now()) }
+            };
+            let expires = issued + Self::CONNECTION_ID_LIFETIME;
+            if let Some(response) = self.send_request(build_request(connection_id), &mut transmissions, Some(expires)).await? {
+                return Ok(response);
+            }
+        }
+    }
+
+    /// Sends the request and waits for the response carrying the same transaction id, retransmitting it after
+    /// `timeout * 2 ^ n` for the n-th transmission counted in `transmissions`. Returns None once `expires` passed
+    /// without a response, and turns tracker error responses into [`ClientError::Failure`].
+    async fn send_request(&mut self, request: Request, transmissions: &mut u32, expires: Option<Instant>) -> Result<Option<Response>, ClientError>
+    {
+        let transaction_id = match &request {
+            Request::Connect(request) => { request.transaction_id }
+            Request::Announce(request) => { request.transaction_id }
+            Request::Scrape(request) => { request.transaction_id }
+        };
+        let mut bytes = vec![];
+        request.write(&mut bytes)?;
+
+        while *transmissions <= self.retransmit_max {
+            if expires.is_some_and(|expires| Instant::now() >= expires) {
+                return Ok(None);
+            }
+            self.socket.send_to(&bytes, self.tracker_addr).await?;
+            let wait = self.timeout.saturating_mul(1u32 << (*transmissions).min(16));
+            *transmissions += 1;
+            if let Some(response) = self.receive_response(transaction_id, Instant::now() + wait).await? {
+                return Ok(Some(response));
+            }
+        }
+        Err(ClientError::Timeout)
+    }
+
+    /// Waits until `deadline` for the response carrying the transaction id, returns None when none arrived.
+    async fn receive_response(&mut self, transaction_id: TransactionId, deadline: Instant) -> Result<Option<Response>, ClientError>
+    {
+        let mut buffer = [0u8; 2048];
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let (length, remote_addr) = match tokio::time::timeout(remaining, self.socket.recv_from(&mut buffer)).await {
+                Ok(result) => { result? }
+                Err(_) => { return Ok(None); }
+            };
+            if remote_addr != self.tracker_addr {
+                continue;
+            }
+            let response = Response::from_bytes(&buffer[..length], self.tracker_addr.is_ipv4())?;
+            let response_transaction_id = match &response {
+                Response::Connect(response) => { response.transaction_id }
+                Response::AnnounceIpv4(response) => { response.transaction_id }
+                Response::AnnounceIpv6(response) => { response.transaction_id }
+                Response::Scrape(response) => { response.transaction_id }
+                Response::Error(response) => { response.transaction_id }
+            };
+            if response_transaction_id != transaction_id {
+                continue;
+            }
+            if let Response::Error(response) = response {
+                if response.message.contains("connection id") {
+                    self.connection_id = None;
+                }
+                return Err(ClientError::Failure(response.message.to_string()));
+            }
+            return Ok(Some(response));
+        }
+    }
+}
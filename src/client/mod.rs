@@ -0,0 +1,4 @@
+pub mod enums;
+pub mod impls;
+pub mod structs;
+pub mod tests;
@@ -0,0 +1,7 @@
+pub mod client_announce_request;
+pub mod client_announce_response;
+pub mod client_scrape_response;
+pub mod udp_tracker_client;
+pub mod http_tracker_client;
+pub mod load_generator;
+pub mod load_generator_report;
@@ -0,0 +1,18 @@
+use std::net::Ipv4Addr;
+use crate::tracker::enums::announce_event::AnnounceEvent;
+use crate::tracker::structs::info_hash::InfoHash;
+use crate::tracker::structs::peer_id::PeerId;
+
+#[derive(Debug, Clone)]
+pub struct ClientAnnounceRequest {
+    pub info_hash: InfoHash,
+    pub peer_id: PeerId,
+    pub port: u16,
+    pub uploaded: u64,
+    pub downloaded: u64,
+    pub left: u64,
+    pub event: AnnounceEvent,
+    pub ip: Option<Ipv4Addr>,
+    pub numwant: Option<u32>,
+    pub key: u32,
+}
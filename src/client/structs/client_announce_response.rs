@@ -0,0 +1,12 @@
+use std::net::SocketAddr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientAnnounceResponse {
+    pub interval: i64,
+    pub min_interval: Option<i64>,
+    pub seeders: i64,
+    pub leechers: i64,
+    pub peers: Vec<SocketAddr>,
+    pub tracker_id: Option<String>,
+    pub warning_message: Option<String>,
+}
@@ -0,0 +1,13 @@
+use crate::tracker::structs::info_hash::InfoHash;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientScrapeResponse {
+    pub files: Vec<(InfoHash, ClientScrapeStatistics)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientScrapeStatistics {
+    pub seeders: i64,
+    pub completed: i64,
+    pub leechers: i64,
+}
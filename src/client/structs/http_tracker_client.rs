@@ -0,0 +1,5 @@
+#[derive(Debug, Clone)]
+pub struct HttpTrackerClient {
+    pub(crate) client: reqwest::Client,
+    pub(crate) announce_url: String,
+}
@@ -0,0 +1,33 @@
+use clap::Parser;
+
+#[derive(Debug, Clone, Parser)]
+#[command(author, version, about = "Load generator for UDP and HTTP BitTorrent trackers", long_about = None)]
+pub struct LoadGenerator {
+    /// Announce URL of the tracker, e.g. udp://127.0.0.1:6969/announce or http://127.0.0.1:6969/announce
+    #[arg(long)]
+    pub tracker: String,
+    /// Number of simulated swarms (torrents)
+    #[arg(long, default_value_t = 100)]
+    pub swarms: u32,
+    /// Number of simulated peers, spread evenly over the swarms
+    #[arg(long, default_value_t = 1000)]
+    pub peers: u32,
+    /// Fraction of peers leaving and being replaced by a new peer every round
+    #[arg(long, default_value_t = 0.05)]
+    pub churn: f64,
+    /// Fraction of peers announcing as seeders
+    #[arg(long, default_value_t = 0.5)]
+    pub seed_ratio: f64,
+    /// Number of announce rounds every peer goes through
+    #[arg(long, default_value_t = 10)]
+    pub rounds: u32,
+    /// Number of concurrent clients, each driving its own share of the peers
+    #[arg(long, default_value_t = 16)]
+    pub concurrency: u32,
+    /// Seconds to wait for a tracker response, UDP waits twice as long after every retransmission
+    #[arg(long, default_value_t = 5)]
+    pub timeout: u64,
+    /// Number of times an unanswered UDP request is retransmitted, BEP 15 allows up to 8
+    #[arg(long, default_value_t = 2)]
+    pub retransmit_max: u32,
+}
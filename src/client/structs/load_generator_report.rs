@@ -0,0 +1,9 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadGeneratorReport {
+    pub announces: u64,
+    pub scrapes: u64,
+    pub errors: u64,
+    pub elapsed: Duration,
+}
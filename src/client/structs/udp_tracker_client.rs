@@ -0,0 +1,14 @@
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+use crate::udp::structs::connection_id::ConnectionId;
+
+#[derive(Debug)]
+pub struct UdpTrackerClient {
+    pub(crate) socket: UdpSocket,
+    pub(crate) tracker_addr: SocketAddr,
+    pub(crate) path: String,
+    pub(crate) connection_id: Option<(ConnectionId, Instant)>,
+    pub(crate) timeout: Duration,
+    pub(crate) retransmit_max: u32,
+}
@@ -0,0 +1,124 @@
+#![cfg(test)]
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use bip_bencode::{ben_bytes, ben_int, ben_list, ben_map};
+use tokio::net::UdpSocket;
+use crate::client::enums::client_error::ClientError;
+use crate::client::structs::client_scrape_response::ClientScrapeStatistics;
+use crate::client::structs::http_tracker_client::HttpTrackerClient;
+use crate::client::structs::udp_tracker_client::UdpTrackerClient;
+use crate::tracker::structs::info_hash::InfoHash;
+use crate::udp::enums::request::Request;
+use crate::udp::enums::response::Response;
+use crate::udp::structs::connect_response::ConnectResponse;
+use crate::udp::structs::connection_id::ConnectionId;
+use crate::udp::structs::scrape_response::ScrapeResponse;
+use crate::udp::udp::MAX_SCRAPE_TORRENTS;
+
+#[test]
+fn http_client_decodes_compact_and_dictionary_peers()
+{
+    let body = ben_map! {
+        "interval" => ben_int!(1800),
+        "min interval" => ben_int!(900),
+        "complete" => ben_int!(2),
+        "incomplete" => ben_int!(1),
+        "peers" => ben_bytes!(vec![192u8, 0, 2, 1, 0x1a, 0xe1]),
+        "peers6" => ben_bytes!([[0x20u8, 0x01, 0x0d, 0xb8].as_slice(), &[0u8; 11], &[1u8, 0x1a, 0xe2]].concat()),
+        "tracker id" => ben_bytes!("00000000000000ff")
+    }.encode();
+    let response = HttpTrackerClient::decode_announce(&body).unwrap();
+    assert_eq!(response.interval, 1800);
+    assert_eq!(response.min_interval, Some(900));
+    assert_eq!((response.seeders, response.leechers), (2, 1));
+    assert_eq!(response.peers, vec![
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)), 6881),
+        SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)), 6882),
+    ]);
+    assert_eq!(response.tracker_id.as_deref(), Some("00000000000000ff"));
+
+    let body = ben_map! {
+        "interval" => ben_int!(1800),
+        "peers" => ben_list!(ben_map! { "ip" => ben_bytes!("192.0.2.7"), "port" => ben_int!(6883) })
+    }.encode();
+    let response = HttpTrackerClient::decode_announce(&body).unwrap();
+    assert_eq!(response.peers, vec![SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 7)), 6883)]);
+
+    let body = ben_map! { "failure reason" => ben_bytes!("unknown key") }.encode();
+    assert!(matches!(HttpTrackerClient::decode_announce(&body), Err(ClientError::Failure(reason)) if reason == "unknown key"));
+    assert!(matches!(HttpTrackerClient::decode_announce(b"garbage"), Err(ClientError::InvalidResponse(_))));
+}
+
+#[test]
+fn http_client_decodes_scrapes_and_derives_scrape_urls()
+{
+    let body = ben_map! {
+        "files" => ben_map! {
+            "aaaaaaaaaaaaaaaaaaaa" => ben_map! { "complete" => ben_int!(3), "downloaded" => ben_int!(7), "incomplete" => ben_int!(4) }
+        }
+    }.encode();
+    let response = HttpTrackerClient::decode_scrape(&body).unwrap();
+    assert_eq!(response.files, vec![(InfoHash([b'a'; 20]), ClientScrapeStatistics { seeders: 3, completed: 7, leechers: 4 })]);
+
+    let client = HttpTrackerClient::new("http://127.0.0.1:6969/0123/announce?passkey=1", Duration::from_secs(1)).unwrap();
+    assert_eq!(client.scrape_url().as_deref(), Some("http://127.0.0.1:6969/0123/scrape?passkey=1"));
+    let client = HttpTrackerClient::new("http://127.0.0.1:6969/tracker", Duration::from_secs(1)).unwrap();
+    assert_eq!(client.scrape_url(), None);
+    assert!(HttpTrackerClient::new("udp://127.0.0.1:6969/announce", Duration::from_secs(1)).is_err());
+}
+
+/// Answers connect and scrape requests like a tracker, except for the first datagram it receives, which is dropped.
+async fn lossy_udp_tracker(socket: UdpSocket, received: Arc<AtomicUsize>)
+{
+    let mut buffer = [0u8; 2048];
+    loop {
+        let (length, remote_addr) = socket.recv_from(&mut buffer).await.unwrap();
+        if received.fetch_add(1, Ordering::SeqCst) == 0 {
+            continue;
+        }
+        let response = match Request::from_bytes(&buffer[..length], MAX_SCRAPE_TORRENTS).unwrap() {
+            Request::Connect(request) => { Response::from(ConnectResponse { connection_id: ConnectionId(7), transaction_id: request.transaction_id }) }
+            Request::Scrape(request) => { Response::from(ScrapeResponse { transaction_id: request.transaction_id, torrent_stats: vec![] }) }
+            Request::Announce(_) => { continue; }
+        };
+        let mut bytes = vec![];
+        response.write(&mut bytes).unwrap();
+        socket.send_to(&bytes, remote_addr).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn udp_client_retransmits_and_reuses_the_connection_id()
+{
+    let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let tracker_addr = socket.local_addr().unwrap();
+    let received = Arc::new(AtomicUsize::new(0));
+    let tracker = tokio::spawn(lossy_udp_tracker(socket, received.clone()));
+
+    let mut client = UdpTrackerClient::new(tracker_addr, "", Duration::from_millis(100), 1).await.unwrap();
+    assert!(client.scrape(&[InfoHash([1u8; 20])]).await.unwrap().files.is_empty());
+    // The dropped connect, its retransmission and the scrape.
+    assert_eq!(received.load(Ordering::SeqCst), 3);
+
+    assert!(client.scrape(&[InfoHash([2u8; 20])]).await.unwrap().files.is_empty());
+    // The connection id is reused, only the scrape went out.
+    assert_eq!(received.load(Ordering::SeqCst), 4);
+    tracker.abort();
+}
+
+#[tokio::test]
+async fn udp_client_gives_up_after_retransmit_max()
+{
+    let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let tracker_addr = socket.local_addr().unwrap();
+    let received = Arc::new(AtomicUsize::new(0));
+    let tracker = tokio::spawn(lossy_udp_tracker(socket, received.clone()));
+
+    let mut client = UdpTrackerClient::new(tracker_addr, "", Duration::from_millis(50), 0).await.unwrap();
+    assert!(matches!(client.connect().await, Err(ClientError::Timeout)));
+    assert_eq!(received.load(Ordering::SeqCst), 1);
+    tracker.abort();
+}
@@ -1 +1,2 @@
-pub mod custom_error;
\ No newline at end of file
+pub mod custom_error;
+pub mod cidr;
\ No newline at end of file
@@ -0,0 +1,50 @@
+use std::net::IpAddr;
+use std::str::FromStr;
+use crate::common::structs::cidr::Cidr;
+use crate::common::structs::custom_error::CustomError;
+
+impl Cidr {
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            (IpAddr::V4(_), IpAddr::V6(ip)) => {
+                ip.to_ipv4_mapped().is_some_and(|ipv4| self.contains(IpAddr::V4(ipv4)))
+            }
+            (IpAddr::V6(_), IpAddr::V4(_)) => { false }
+        }
+    }
+
+    pub fn contains_any(cidrs: &[Cidr], ip: IpAddr) -> bool {
+        cidrs.iter().any(|cidr| cidr.contains(ip))
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = CustomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (network, prefix) = match s.trim().split_once('/') {
+            None => { (s.trim(), None) }
+            Some((network, prefix)) => { (network, Some(prefix)) }
+        };
+        let network = IpAddr::from_str(network).map_err(|_| CustomError::new("invalid cidr network"))?;
+        let prefix_max = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            None => { prefix_max }
+            Some(prefix) => {
+                match prefix.parse::<u8>() {
+                    Ok(prefix) if prefix <= prefix_max => { prefix }
+                    _ => { return Err(CustomError::new("invalid cidr prefix")); }
+                }
+            }
+        };
+        Ok(Cidr { network, prefix })
+    }
+}
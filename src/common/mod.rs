@@ -1,4 +1,5 @@
 pub mod structs;
 #[allow(clippy::module_inception)]
 pub mod common;
-pub mod impls;
\ No newline at end of file
+pub mod impls;
+pub mod tests;
\ No newline at end of file
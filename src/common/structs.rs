@@ -2,4 +2,5 @@ pub mod custom_error;
 pub mod number_of_bytes;
 pub mod number_of_bytes_def;
 pub mod get_torrents_api;
-pub mod get_torrent_api;
\ No newline at end of file
+pub mod get_torrent_api;
+pub mod cidr;
\ No newline at end of file
@@ -0,0 +1,7 @@
+use std::net::IpAddr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cidr {
+    pub(crate) network: IpAddr,
+    pub(crate) prefix: u8,
+}
@@ -0,0 +1,24 @@
+#![cfg(test)]
+
+use std::net::IpAddr;
+use std::str::FromStr;
+use crate::common::structs::cidr::Cidr;
+
+#[test]
+fn cidr_contains_matches_prefix_and_family()
+{
+    let cidr = Cidr::from_str("10.1.0.0/16").unwrap();
+    assert!(cidr.contains(IpAddr::from_str("10.1.200.3").unwrap()));
+    assert!(!cidr.contains(IpAddr::from_str("10.2.0.1").unwrap()));
+    assert!(cidr.contains(IpAddr::from_str("::ffff:10.1.0.1").unwrap()));
+    assert!(!cidr.contains(IpAddr::from_str("2001:db8::1").unwrap()));
+
+    let cidr = Cidr::from_str("2001:db8::/32").unwrap();
+    assert!(cidr.contains(IpAddr::from_str("2001:db8:ffff::1").unwrap()));
+    assert!(!cidr.contains(IpAddr::from_str("10.1.0.1").unwrap()));
+
+    assert!(Cidr::from_str("0.0.0.0/0").unwrap().contains(IpAddr::from_str("192.0.2.1").unwrap()));
+    assert_eq!(Cidr::from_str("192.0.2.1").unwrap().prefix, 32);
+    assert!(Cidr::from_str("192.0.2.0/33").is_err());
+    assert!(Cidr::from_str("not-an-ip/8").is_err());
+}
@@ -1,8 +1,10 @@
 use std::fs::File;
 use std::io::Write;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::thread::available_parallelism;
 use regex::Regex;
+use crate::common::structs::cidr::Cidr;
 use crate::common::structs::custom_error::CustomError;
 use crate::config::enums::configuration_error::ConfigurationError;
 use crate::config::structs::api_trackers_config::ApiTrackersConfig;
@@ -11,6 +13,7 @@ use crate::config::structs::database_config::DatabaseConfig;
 use crate::config::structs::database_structure_config::DatabaseStructureConfig;
 use crate::config::structs::database_structure_config_blacklist::DatabaseStructureConfigBlacklist;
 use crate::config::structs::database_structure_config_keys::DatabaseStructureConfigKeys;
+use crate::config::structs::database_structure_config_schema_version::DatabaseStructureConfigSchemaVersion;
 use crate::config::structs::database_structure_config_torrents::DatabaseStructureConfigTorrents;
 use crate::config::structs::database_structure_config_users::DatabaseStructureConfigUsers;
 use crate::config::structs::database_structure_config_whitelist::DatabaseStructureConfigWhitelist;
@@ -19,9 +22,27 @@ use crate::config::structs::sentry_config::SentryConfig;
 use crate::config::structs::tracker_config::TrackerConfig;
 use crate::config::structs::udp_trackers_config::UdpTrackersConfig;
 use crate::database::enums::database_drivers::DatabaseDrivers;
+use crate::tracker::enums::peers_selection::PeersSelection;
+use crate::tracker::enums::request_interval_throttle::RequestIntervalThrottle;
+use crate::http::enums::client_address_policy::ClientAddressPolicy;
+use crate::udp::enums::ip_address_policy::IpAddressPolicy;
 use std::env;
 
 impl Configuration {
+    // Defaults of the settings added after the first releases, shared by `init` and serde so an older config.toml
+    // keeps loading with the same values a freshly generated one would have.
+    pub fn default_shards() -> u64 { 256 }
+    pub fn default_peers_wanted_max() -> u64 { 72 }
+    pub fn default_full_scrape_cidrs() -> Vec<String> { vec![String::from("127.0.0.1/32"), String::from("::1/128")] }
+    pub fn default_full_scrape_interval() -> u64 { 300 }
+    pub fn default_peers_snapshot_file() -> String { String::from("peers_snapshot.json") }
+    pub fn default_peers_snapshot_interval() -> u64 { 300 }
+    pub fn default_batch_size() -> u64 { 1000 }
+    pub fn default_flush_backoff_max() -> u64 { 900 }
+    pub fn default_flush_journal_after() -> u64 { 300 }
+    pub fn default_flush_journal_file() -> String { String::from("flush_journal.json") }
+    pub fn default_schema_version() -> DatabaseStructureConfigSchemaVersion { DatabaseStructureConfigSchemaVersion { table_name: String::from("schema_version") } }
+
     #[tracing::instrument(level = "debug")]
     pub fn init() -> Configuration {
         Configuration {
@@ -36,9 +57,21 @@ impl Configuration {
                 users_enabled: false,
                 request_interval: 1800,
                 request_interval_minimum: 1800,
+                request_interval_throttle: RequestIntervalThrottle::disabled,
                 peers_timeout: 2700,
                 peers_cleanup_interval: 900,
                 peers_cleanup_threads: 256,
+                torrent_ttl: 0,
+                shards: Configuration::default_shards(),
+                peers_numwant_max: Configuration::default_peers_wanted_max(),
+                peers_selection: PeersSelection::random,
+                full_scrape_enabled: false,
+                full_scrape_key: String::from(""),
+                full_scrape_cidrs: Configuration::default_full_scrape_cidrs(),
+                full_scrape_interval: Configuration::default_full_scrape_interval(),
+                peers_snapshot_enabled: false,
+                peers_snapshot_file: Configuration::default_peers_snapshot_file(),
+                peers_snapshot_interval: Configuration::default_peers_snapshot_interval(),
                 total_downloads: 0,
                 swagger: false,
                 prometheus_id: String::from("torrust_actix")
@@ -62,6 +95,11 @@ impl Configuration {
                 remove_action: false,
                 update_completed: true,
                 update_peers: false,
+                batch_size: Configuration::default_batch_size(),
+                flush_backoff_max: Configuration::default_flush_backoff_max(),
+                flush_journal_after: Configuration::default_flush_journal_after(),
+                flush_journal_file: Configuration::default_flush_journal_file(),
+                migrate_on_boot: false,
             },
             database_structure: DatabaseStructureConfig {
                 torrents: DatabaseStructureConfigTorrents {
@@ -100,13 +138,15 @@ impl Configuration {
                     column_downloaded: String::from("downloaded"),
                     column_completed: String::from("completed"),
                     column_updated: String::from("updated"),
-                }
+                },
+                schema_version: Configuration::default_schema_version()
             },
             http_server: vec!(
                 HttpTrackersConfig {
                     enabled: true,
                     bind_address: String::from("0.0.0.0:6969"),
                     real_ip: String::from("X-Real-IP"),
+                    client_address_policy: ClientAddressPolicy::ignore,
                     keep_alive: 60,
                     request_timeout: 15,
                     disconnect_timeout: 15,
@@ -123,6 +163,9 @@ impl Configuration {
                     enabled: true,
                     bind_address: String::from("0.0.0.0:6969"),
                     threads: available_parallelism().unwrap().get() as u64,
+                    peers_wanted_max: Configuration::default_peers_wanted_max(),
+                    ip_address_policy: IpAddressPolicy::ignore,
+                    trusted_proxies: vec![],
                 }
             ),
             api_server: vec!(
@@ -178,6 +221,15 @@ impl Configuration {
         if let Ok(value) = env::var("TRACKER__REQUEST_INTERVAL_MINIMUM") {
             config.tracker_config.request_interval_minimum = value.parse::<u64>().unwrap_or(1800u64);
         }
+        if let Ok(value) = env::var("TRACKER__REQUEST_INTERVAL_THROTTLE") {
+            config.tracker_config.request_interval_throttle = match value.as_str() {
+                "disabled" => { RequestIntervalThrottle::disabled }
+                "cached" => { RequestIntervalThrottle::cached }
+                "failure" => { RequestIntervalThrottle::failure }
+                "warning" => { RequestIntervalThrottle::warning }
+                _ => { RequestIntervalThrottle::disabled }
+            };
+        }
         if let Ok(value) = env::var("TRACKER__PEERS_TIMEOUT") {
             config.tracker_config.peers_timeout = value.parse::<u64>().unwrap_or(2700u64);
         }
@@ -187,6 +239,44 @@ impl Configuration {
         if let Ok(value) = env::var("TRACKER__PEERS_CLEANUP_THREADS") {
             config.tracker_config.peers_cleanup_threads = value.parse::<u64>().unwrap_or(256u64);
         }
+        if let Ok(value) = env::var("TRACKER__TORRENT_TTL") {
+            config.tracker_config.torrent_ttl = value.parse::<u64>().unwrap_or(0u64);
+        }
+        if let Ok(value) = env::var("TRACKER__SHARDS") {
+            config.tracker_config.shards = value.parse::<u64>().unwrap_or(256u64);
+        }
+        if let Ok(value) = env::var("TRACKER__PEERS_NUMWANT_MAX") {
+            config.tracker_config.peers_numwant_max = value.parse::<u64>().unwrap_or(72u64);
+        }
+        if let Ok(value) = env::var("TRACKER__PEERS_SELECTION") {
+            config.tracker_config.peers_selection = match value.as_str() {
+                "random" => { PeersSelection::random }
+                "round_robin" => { PeersSelection::round_robin }
+                "prefer_seeds" => { PeersSelection::prefer_seeds }
+                _ => { PeersSelection::random }
+            };
+        }
+        if let Ok(value) = env::var("TRACKER__FULL_SCRAPE_ENABLED") {
+            config.tracker_config.full_scrape_enabled = match value.as_str() { "true" => { true } "false" => { false } _ => { false } };
+        }
+        if let Ok(value) = env::var("TRACKER__FULL_SCRAPE_KEY") {
+            config.tracker_config.full_scrape_key = value;
+        }
+        if let Ok(value) = env::var("TRACKER__FULL_SCRAPE_CIDRS") {
+            config.tracker_config.full_scrape_cidrs = value.split(',').map(|cidr| cidr.trim().to_string()).filter(|cidr| !cidr.is_empty()).collect();
+        }
+        if let Ok(value) = env::var("TRACKER__FULL_SCRAPE_INTERVAL") {
+            config.tracker_config.full_scrape_interval = value.parse::<u64>().unwrap_or(300u64);
+        }
+        if let Ok(value) = env::var("TRACKER__PEERS_SNAPSHOT_ENABLED") {
+            config.tracker_config.peers_snapshot_enabled = match value.as_str() { "true" => { true } "false" => { false } _ => { false } };
+        }
+        if let Ok(value) = env::var("TRACKER__PEERS_SNAPSHOT_FILE") {
+            config.tracker_config.peers_snapshot_file = value;
+        }
+        if let Ok(value) = env::var("TRACKER__PEERS_SNAPSHOT_INTERVAL") {
+            config.tracker_config.peers_snapshot_interval = value.parse::<u64>().unwrap_or(300u64);
+        }
         if let Ok(value) = env::var("TRACKER__PROMETHEUS_ID") {
             config.tracker_config.prometheus_id = value;
         }
@@ -247,6 +337,21 @@ impl Configuration {
         if let Ok(value) = env::var("DATABASE__PERSISTENT_INTERVAL") {
             config.database.persistent_interval = value.parse::<u64>().unwrap_or(60u64);
         }
+        if let Ok(value) = env::var("DATABASE__BATCH_SIZE") {
+            config.database.batch_size = value.parse::<u64>().unwrap_or(1000u64);
+        }
+        if let Ok(value) = env::var("DATABASE__FLUSH_BACKOFF_MAX") {
+            config.database.flush_backoff_max = value.parse::<u64>().unwrap_or(900u64);
+        }
+        if let Ok(value) = env::var("DATABASE__FLUSH_JOURNAL_AFTER") {
+            config.database.flush_journal_after = value.parse::<u64>().unwrap_or(300u64);
+        }
+        if let Ok(value) = env::var("DATABASE__FLUSH_JOURNAL_FILE") {
+            config.database.flush_journal_file = value;
+        }
+        if let Ok(value) = env::var("DATABASE__MIGRATE_ON_BOOT") {
+            config.database.migrate_on_boot = match value.as_str() { "true" => { true } "false" => { false } _ => { false } };
+        }
 
         // Database Structure Torrents config
         if let Ok(value) = env::var("DATABASE_STRUCTURE__TORRENTS__BIN_TYPE_INFOHASH") {
@@ -339,6 +444,11 @@ impl Configuration {
             config.database_structure.users.column_updated = value;
         }
 
+        // Database Structure Schema Version config
+        if let Ok(value) = env::var("DATABASE_STRUCTURE__SCHEMA_VERSION__TABLE_NAME") {
+            config.database_structure.schema_version.table_name = value;
+        }
+
         // Possible overrides for the API stack
         let mut api_iteration = 0;
         loop {
@@ -408,6 +518,14 @@ impl Configuration {
                     if let Ok(value) = env::var(format!("HTTP_{}_REAL_IP", http_iteration)) {
                         block.real_ip = value;
                     }
+                    if let Ok(value) = env::var(format!("HTTP_{}_CLIENT_ADDRESS_POLICY", http_iteration)) {
+                        block.client_address_policy = match value.as_str() {
+                            "ignore" => { ClientAddressPolicy::ignore }
+                            "other_family" => { ClientAddressPolicy::other_family }
+                            "trust" => { ClientAddressPolicy::trust }
+                            _ => { ClientAddressPolicy::ignore }
+                        };
+                    }
                     if let Ok(value) = env::var(format!("HTTP_{}_SSL_KEY", http_iteration)) {
                         block.ssl_key = value;
                     }
@@ -454,6 +572,20 @@ impl Configuration {
                     if let Ok(value) = env::var(format!("UDP_{}_THREADS", udp_iteration)) {
                         block.threads = value.parse::<u64>().unwrap_or(available_parallelism().unwrap().get() as u64);
                     }
+                    if let Ok(value) = env::var(format!("UDP_{}_PEERS_WANTED_MAX", udp_iteration)) {
+                        block.peers_wanted_max = value.parse::<u64>().unwrap_or(72u64);
+                    }
+                    if let Ok(value) = env::var(format!("UDP_{}_IP_ADDRESS_POLICY", udp_iteration)) {
+                        block.ip_address_policy = match value.as_str() {
+                            "ignore" => { IpAddressPolicy::ignore }
+                            "trust" => { IpAddressPolicy::trust }
+                            "trust_proxies" => { IpAddressPolicy::trust_proxies }
+                            _ => { IpAddressPolicy::ignore }
+                        };
+                    }
+                    if let Ok(value) = env::var(format!("UDP_{}_TRUSTED_PROXIES", udp_iteration)) {
+                        block.trusted_proxies = value.split(',').map(|cidr| cidr.trim().to_string()).filter(|cidr| !cidr.is_empty()).collect();
+                    }
                 }
             }
             udp_iteration += 1;
@@ -548,6 +680,7 @@ impl Configuration {
         let check_map = vec![
             ("[TRACKER_CONFIG] prometheus_id", config.tracker_config.clone().prometheus_id, r"^[a-zA-Z0-9_]+$".to_string()),
             
+            // Table and column names are interpolated into the SQL queries, every value is bound
             ("[DB: torrents]", config.database_structure.clone().torrents.table_name, r"^[a-z_][a-z0-9_]{0,30}$".to_string()),
             ("[DB: torrents] Column: infohash", config.database_structure.clone().torrents.column_infohash, r"^[a-z_][a-z0-9_]{0,30}$".to_string()),
             ("[DB: torrents] Column: seeds", config.database_structure.clone().torrents.column_seeds, r"^[a-z_][a-z0-9_]{0,30}$".to_string()),
@@ -569,12 +702,37 @@ impl Configuration {
             ("[DB: users] Column: completed", config.database_structure.clone().users.column_completed, r"^[a-z_][a-z0-9_]{0,30}$".to_string()),
             ("[DB: users] Column: active", config.database_structure.clone().users.column_active, r"^[a-z_][a-z0-9_]{0,30}$".to_string()),
             ("[DB: users] Column: updated", config.database_structure.clone().users.column_updated, r"^[a-z_][a-z0-9_]{0,30}$".to_string()),
+            ("[DB: schema_version]", config.database_structure.clone().schema_version.table_name, r"^[a-z_][a-z0-9_]{0,30}$".to_string()),
         ];
 
         // Validation
         for (name, value, regex) in check_map {
             Self::validate_value(name, value, regex);
         }
+        if !(1..=4096).contains(&config.database.batch_size) {
+            panic!("[VALIDATE CONFIG] Error checking [DATABASE] batch_size [:] Value: \"{}\" [:] Must be from 1 to 4096", config.database.batch_size);
+        }
+        if config.database.flush_backoff_max < config.database.persistent_interval {
+            panic!("[VALIDATE CONFIG] Error checking [DATABASE] flush_backoff_max [:] Value: \"{}\" [:] Must not be lower than persistent_interval", config.database.flush_backoff_max);
+        }
+        if config.database.flush_journal_file.is_empty() {
+            panic!("[VALIDATE CONFIG] Error checking [DATABASE] flush_journal_file [:] Value: \"{}\" [:] Must not be empty", config.database.flush_journal_file);
+        }
+        if !config.tracker_config.shards.is_power_of_two() || !(16..=4096).contains(&config.tracker_config.shards) {
+            panic!("[VALIDATE CONFIG] Error checking [TRACKER_CONFIG] shards [:] Value: \"{}\" [:] Must be a power of two from 16 to 4096", config.tracker_config.shards);
+        }
+        for cidr in config.tracker_config.full_scrape_cidrs.iter() {
+            if Cidr::from_str(cidr).is_err() {
+                panic!("[VALIDATE CONFIG] Error checking [TRACKER_CONFIG] full_scrape_cidrs [:] Name: \"{}\" [:] Not a valid CIDR", cidr);
+            }
+        }
+        for udp_server in config.udp_server.iter() {
+            for cidr in udp_server.trusted_proxies.iter() {
+                if Cidr::from_str(cidr).is_err() {
+                    panic!("[VALIDATE CONFIG] Error checking [UDP_SERVER] trusted_proxies [:] Name: \"{}\" [:] Not a valid CIDR", cidr);
+                }
+            }
+        }
     }
 
     #[tracing::instrument(level = "debug")]
@@ -1,3 +1,4 @@
 pub mod enums;
 pub mod structs;
-pub mod impls;
\ No newline at end of file
+pub mod impls;
+pub mod tests;
\ No newline at end of file
@@ -5,6 +5,7 @@ pub mod http_trackers_config;
 pub mod udp_trackers_config;
 pub mod database_structure_config_blacklist;
 pub mod database_structure_config_keys;
+pub mod database_structure_config_schema_version;
 pub mod database_structure_config_torrents;
 pub mod database_structure_config_users;
 pub mod database_structure_config_whitelist;
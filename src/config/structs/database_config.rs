@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use crate::config::structs::configuration::Configuration;
 use crate::database::enums::database_drivers::DatabaseDrivers;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -10,5 +11,15 @@ pub struct DatabaseConfig {
     pub insert_vacant: bool,
     pub remove_action: bool,
     pub update_completed: bool,
-    pub update_peers: bool
+    pub update_peers: bool,
+    #[serde(default = "Configuration::default_batch_size")]
+    pub batch_size: u64,
+    #[serde(default = "Configuration::default_flush_backoff_max")]
+    pub flush_backoff_max: u64,
+    #[serde(default = "Configuration::default_flush_journal_after")]
+    pub flush_journal_after: u64,
+    #[serde(default = "Configuration::default_flush_journal_file")]
+    pub flush_journal_file: String,
+    #[serde(default)]
+    pub migrate_on_boot: bool
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
+use crate::config::structs::configuration::Configuration;
 use crate::config::structs::database_structure_config_blacklist::DatabaseStructureConfigBlacklist;
 use crate::config::structs::database_structure_config_keys::DatabaseStructureConfigKeys;
+use crate::config::structs::database_structure_config_schema_version::DatabaseStructureConfigSchemaVersion;
 use crate::config::structs::database_structure_config_torrents::DatabaseStructureConfigTorrents;
 use crate::config::structs::database_structure_config_users::DatabaseStructureConfigUsers;
 use crate::config::structs::database_structure_config_whitelist::DatabaseStructureConfigWhitelist;
@@ -11,5 +13,7 @@ pub struct DatabaseStructureConfig {
     pub whitelist: DatabaseStructureConfigWhitelist,
     pub blacklist: DatabaseStructureConfigBlacklist,
     pub keys: DatabaseStructureConfigKeys,
-    pub users: DatabaseStructureConfigUsers
+    pub users: DatabaseStructureConfigUsers,
+    #[serde(default = "Configuration::default_schema_version")]
+    pub schema_version: DatabaseStructureConfigSchemaVersion
 }
\ No newline at end of file
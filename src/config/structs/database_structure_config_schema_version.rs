@@ -0,0 +1,6 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DatabaseStructureConfigSchemaVersion {
+    pub table_name: String
+}
\ No newline at end of file
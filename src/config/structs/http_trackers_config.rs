@@ -1,10 +1,13 @@
 use serde::{Deserialize, Serialize};
+use crate::http::enums::client_address_policy::ClientAddressPolicy;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct HttpTrackersConfig {
     pub enabled: bool,
     pub bind_address: String,
     pub real_ip: String,
+    #[serde(default)]
+    pub client_address_policy: ClientAddressPolicy,
     pub keep_alive: u64,
     pub request_timeout: u64,
     pub disconnect_timeout: u64,
@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use crate::config::structs::configuration::Configuration;
+use crate::tracker::enums::peers_selection::PeersSelection;
+use crate::tracker::enums::request_interval_throttle::RequestIntervalThrottle;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TrackerConfig {
@@ -10,9 +13,33 @@ pub struct TrackerConfig {
     pub users_enabled: bool,
     pub request_interval: u64,
     pub request_interval_minimum: u64,
+    #[serde(default)]
+    pub request_interval_throttle: RequestIntervalThrottle,
     pub peers_timeout: u64,
     pub peers_cleanup_interval: u64,
     pub peers_cleanup_threads: u64,
+    #[serde(default)]
+    pub torrent_ttl: u64,
+    #[serde(default = "Configuration::default_shards")]
+    pub shards: u64,
+    #[serde(default = "Configuration::default_peers_wanted_max")]
+    pub peers_numwant_max: u64,
+    #[serde(default)]
+    pub peers_selection: PeersSelection,
+    #[serde(default)]
+    pub full_scrape_enabled: bool,
+    #[serde(default)]
+    pub full_scrape_key: String,
+    #[serde(default = "Configuration::default_full_scrape_cidrs")]
+    pub full_scrape_cidrs: Vec<String>,
+    #[serde(default = "Configuration::default_full_scrape_interval")]
+    pub full_scrape_interval: u64,
+    #[serde(default)]
+    pub peers_snapshot_enabled: bool,
+    #[serde(default = "Configuration::default_peers_snapshot_file")]
+    pub peers_snapshot_file: String,
+    #[serde(default = "Configuration::default_peers_snapshot_interval")]
+    pub peers_snapshot_interval: u64,
     pub total_downloads: u64,
     pub swagger: bool,
     pub prometheus_id: String,
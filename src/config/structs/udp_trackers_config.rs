@@ -1,8 +1,16 @@
 use serde::{Deserialize, Serialize};
+use crate::config::structs::configuration::Configuration;
+use crate::udp::enums::ip_address_policy::IpAddressPolicy;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UdpTrackersConfig {
     pub enabled: bool,
     pub bind_address: String,
-    pub threads: u64
+    pub threads: u64,
+    #[serde(default = "Configuration::default_peers_wanted_max")]
+    pub peers_wanted_max: u64,
+    #[serde(default)]
+    pub ip_address_policy: IpAddressPolicy,
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>
 }
\ No newline at end of file
@@ -0,0 +1,29 @@
+#![cfg(test)]
+
+use crate::config::structs::configuration::Configuration;
+
+#[test]
+fn config_without_the_newer_settings_loads_the_init_defaults()
+{
+    let init = Configuration::init();
+    let mut table: toml::Table = toml::from_str(&toml::to_string(&init).unwrap()).unwrap();
+    for (section, keys) in [
+        ("tracker_config", &["request_interval_throttle", "torrent_ttl", "shards", "peers_numwant_max", "peers_selection", "full_scrape_enabled", "full_scrape_key", "full_scrape_cidrs", "full_scrape_interval", "peers_snapshot_enabled", "peers_snapshot_file", "peers_snapshot_interval"][..]),
+        ("database", &["batch_size", "flush_backoff_max", "flush_journal_after", "flush_journal_file", "migrate_on_boot"][..]),
+        ("database_structure", &["schema_version"][..]),
+    ] {
+        let section = table[section].as_table_mut().unwrap();
+        for key in keys {
+            assert!(section.remove(*key).is_some(), "{key}");
+        }
+    }
+    for (server, keys) in [("http_server", &["client_address_policy"][..]), ("udp_server", &["peers_wanted_max", "ip_address_policy", "trusted_proxies"][..])] {
+        let block = table[server].as_array_mut().unwrap()[0].as_table_mut().unwrap();
+        for key in keys {
+            assert!(block.remove(*key).is_some(), "{key}");
+        }
+    }
+
+    let loaded = Configuration::load(toml::to_string(&table).unwrap().as_bytes()).unwrap();
+    assert_eq!(toml::to_string(&loaded).unwrap(), toml::to_string(&init).unwrap());
+}
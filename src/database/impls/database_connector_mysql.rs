@@ -5,15 +5,18 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use async_std::task;
+use async_trait::async_trait;
 use futures_util::TryStreamExt;
 use log::{error, info};
 use sha1::{Digest, Sha1};
-use sqlx::{ConnectOptions, Error, MySql, Pool, Row, Transaction};
-use sqlx::mysql::{MySqlConnectOptions, MySqlPoolOptions};
+use sqlx::{ConnectOptions, Error, MySql, Pool, QueryBuilder, Row, Transaction};
+use sqlx::mysql::{MySqlArguments, MySqlConnectOptions, MySqlPoolOptions};
+use sqlx::query::Query;
+use sqlx::query_builder::Separated;
 use crate::config::structs::configuration::Configuration;
-use crate::database::enums::database_drivers::DatabaseDrivers;
-use crate::database::structs::database_connector::DatabaseConnector;
+use crate::database::structs::database_migration::DatabaseMigration;
 use crate::database::structs::database_connector_mysql::DatabaseConnectorMySQL;
+use crate::database::traits::DatabaseBackend;
 use crate::stats::enums::stats_event::StatsEvent;
 use crate::tracker::enums::updates_action::UpdatesAction;
 use crate::tracker::structs::info_hash::InfoHash;
@@ -34,7 +37,7 @@ impl DatabaseConnectorMySQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn database_connector(config: Arc<Configuration>, create_database: bool) -> DatabaseConnector
+    pub async fn database_connector(config: Arc<Configuration>, create_database: bool) -> Box<dyn DatabaseBackend>
     {
         let mysql_connect = DatabaseConnectorMySQL::create(config.database.clone().path.as_str()).await;
         if mysql_connect.is_err() {
@@ -43,244 +46,147 @@ impl DatabaseConnectorMySQL {
             exit(1);
         }
 
-        let mut structure = DatabaseConnector { mysql: None, sqlite: None, pgsql: None, engine: None };
-        structure.mysql = Some(DatabaseConnectorMySQL { pool: mysql_connect.unwrap() });
-        structure.engine = Some(DatabaseDrivers::mysql);
+        let structure = DatabaseConnectorMySQL { pool: mysql_connect.unwrap(), schema_version_table: config.database_structure.schema_version.table_name.clone() };
 
         if create_database {
-            let pool = &structure.mysql.clone().unwrap().pool;
             info!("[BOOT] Database creation triggered for MySQL.");
 
-            // Create Torrent DB
-            info!("[BOOT MySQL] Creating table {}", config.database_structure.clone().torrents.table_name);
-            match config.database_structure.clone().torrents.bin_type_infohash {
-                true => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE `{}` (`{}` BINARY(20) NOT NULL, `{}` INT NOT NULL DEFAULT 0, `{}` INT NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
-                            config.database_structure.clone().torrents.table_name,
-                            config.database_structure.clone().torrents.column_infohash,
-                            config.database_structure.clone().torrents.column_seeds,
-                            config.database_structure.clone().torrents.column_peers,
-                            config.database_structure.clone().torrents.column_completed,
-                            config.database_structure.clone().torrents.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[MySQL] Error: {}", error); }
-                    }
-                }
-                false => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE `{}` (`{}` VARCHAR(40) NOT NULL, `{}` INT NOT NULL DEFAULT 0, `{}` INT NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
-                            config.database_structure.clone().torrents.table_name,
-                            config.database_structure.clone().torrents.column_infohash,
-                            config.database_structure.clone().torrents.column_seeds,
-                            config.database_structure.clone().torrents.column_peers,
-                            config.database_structure.clone().torrents.column_completed,
-                            config.database_structure.clone().torrents.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[MySQL] Error: {}", error); }
-                    }
-                }
+            if let Err(error) = structure.apply_migrations(DatabaseConnectorMySQL::migrations(&config), false).await {
+                panic!("[MySQL] Error: {}", error);
             }
+            info!("[BOOT] Created the database and tables, restart without the parameter to start the app.");
+            task::sleep(Duration::from_secs(1)).await;
+            exit(0);
+        }
 
-            // Create Whitelist DB
-            info!("[BOOT MySQL] Creating table {}", config.database_structure.clone().whitelist.table_name);
-            match config.database_structure.clone().whitelist.bin_type_infohash {
-                true => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE `{}` (`{}` BINARY(20) NOT NULL, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
-                            config.database_structure.clone().whitelist.table_name,
-                            config.database_structure.clone().whitelist.column_infohash,
-                            config.database_structure.clone().whitelist.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[MySQL] Error: {}", error); }
-                    }
-                }
-                false => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE `{}` (`{}` VARCHAR(40) NOT NULL, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
-                            config.database_structure.clone().whitelist.table_name,
-                            config.database_structure.clone().whitelist.column_infohash,
-                            config.database_structure.clone().whitelist.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[MySQL] Error: {}", error); }
-                    }
-                }
-            }
+        Box::new(structure)
+    }
 
-            // Create Blacklist DB
-            info!("[BOOT MySQL] Creating table {}", config.database_structure.clone().blacklist.table_name);
-            match config.database_structure.clone().blacklist.bin_type_infohash {
-                true => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE `{}` (`{}` BINARY(20) NOT NULL, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
-                            config.database_structure.clone().blacklist.table_name,
-                            config.database_structure.clone().blacklist.column_infohash,
-                            config.database_structure.clone().blacklist.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[MySQL] Error: {}", error); }
-                    }
-                }
-                false => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE `{}` (`{}` VARCHAR(40) NOT NULL, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
-                            config.database_structure.clone().blacklist.table_name,
-                            config.database_structure.clone().blacklist.column_infohash,
-                            config.database_structure.clone().blacklist.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[MySQL] Error: {}", error); }
-                    }
-                }
-            }
+    /// The ordered schema migrations for MySQL, rendered with the configured table and column names.
+    #[tracing::instrument(level = "debug")]
+    pub fn migrations(config: &Configuration) -> Vec<DatabaseMigration>
+    {
+        let structure = &config.database_structure;
+        let hash_type = |bin_type: bool| match bin_type { true => "BINARY(20)", false => "VARCHAR(40)" };
+        let (users_id, users_id_column) = match structure.users.id_uuid {
+            true => ("VARCHAR(36) NOT NULL", &structure.users.column_uuid),
+            false => ("BIGINT UNSIGNED NOT NULL AUTO_INCREMENT", &structure.users.column_id)
+        };
+        vec![
+            DatabaseMigration {
+                version: 1,
+                name: String::from("initial_schema"),
+                statements: vec![
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS `{}` (`{}` {} NOT NULL, `{}` INT NOT NULL DEFAULT 0, `{}` INT NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
+                        structure.torrents.table_name,
+                        structure.torrents.column_infohash,
+                        hash_type(structure.torrents.bin_type_infohash),
+                        structure.torrents.column_seeds,
+                        structure.torrents.column_peers,
+                        structure.torrents.column_completed,
+                        structure.torrents.column_infohash
+                    ),
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS `{}` (`{}` {} NOT NULL, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
+                        structure.whitelist.table_name,
+                        structure.whitelist.column_infohash,
+                        hash_type(structure.whitelist.bin_type_infohash),
+                        structure.whitelist.column_infohash
+                    ),
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS `{}` (`{}` {} NOT NULL, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
+                        structure.blacklist.table_name,
+                        structure.blacklist.column_infohash,
+                        hash_type(structure.blacklist.bin_type_infohash),
+                        structure.blacklist.column_infohash
+                    ),
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS `{}` (`{}` {} NOT NULL, `{}` INT NOT NULL DEFAULT 0, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
+                        structure.keys.table_name,
+                        structure.keys.column_hash,
+                        hash_type(structure.keys.bin_type_hash),
+                        structure.keys.column_timeout,
+                        structure.keys.column_hash
+                    ),
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS `{}` (`{}` {}, `{}` {} NOT NULL, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` TINYINT NOT NULL DEFAULT 0, `{}` INT NOT NULL DEFAULT 0, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
+                        structure.users.table_name,
+                        users_id_column,
+                        users_id,
+                        structure.users.column_key,
+                        hash_type(structure.users.bin_type_key),
+                        structure.users.column_uploaded,
+                        structure.users.column_downloaded,
+                        structure.users.column_completed,
+                        structure.users.column_active,
+                        structure.users.column_updated,
+                        users_id_column
+                    ),
+                ],
+            },
+        ]
+    }
 
-            // Create Keys DB
-            info!("[BOOT MySQL] Creating table {}", config.database_structure.clone().keys.table_name);
-            match config.database_structure.clone().keys.bin_type_hash {
-                true => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE `{}` (`{}` BINARY(20) NOT NULL, `{}` INT NOT NULL DEFAULT 0, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
-                            config.database_structure.clone().keys.table_name,
-                            config.database_structure.clone().keys.column_hash,
-                            config.database_structure.clone().keys.column_timeout,
-                            config.database_structure.clone().keys.column_hash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[MySQL] Error: {}", error); }
-                    }
-                }
-                false => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE `{}` (`{}` VARCHAR(40) NOT NULL, `{}` INT NOT NULL DEFAULT 0, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
-                            config.database_structure.clone().keys.table_name,
-                            config.database_structure.clone().keys.column_hash,
-                            config.database_structure.clone().keys.column_timeout,
-                            config.database_structure.clone().keys.column_hash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[MySQL] Error: {}", error); }
-                    }
-                }
-            }
+    /// Returns the latest completely applied migration, 0 when the schema version table does not exist yet.
+    #[tracing::instrument(level = "debug")]
+    pub async fn get_schema_version(&self) -> Result<u64, Error>
+    {
+        let row = sqlx::query("SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = DATABASE() AND table_name = ?").bind(&self.schema_version_table).fetch_one(&self.pool).await?;
+        if row.try_get::<i64, _>(0)? == 0 {
+            return Ok(0);
+        }
+        let row = sqlx::query(&format!("SELECT MAX(`version`) FROM `{}` WHERE `applied` > 0", self.schema_version_table)).fetch_one(&self.pool).await?;
+        Ok(row.try_get::<Option<u64>, _>(0)?.unwrap_or(0))
+    }
 
-            // Create Users DB
-            info!("[BOOT MySQL] Creating table {}", config.database_structure.clone().users.table_name);
-            match config.database_structure.clone().users.id_uuid {
-                true => {
-                    match config.database_structure.clone().users.bin_type_key {
-                        true => {
-                            match sqlx::query(
-                                format!(
-                                    "CREATE TABLE `{}` (`{}` VARCHAR(36) NOT NULL, `{}` BINARY(20) NOT NULL, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` TINYINT NOT NULL DEFAULT 0, `{}` INT NOT NULL DEFAULT 0, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
-                                    config.database_structure.clone().users.table_name,
-                                    config.database_structure.clone().users.column_uuid,
-                                    config.database_structure.clone().users.column_key,
-                                    config.database_structure.clone().users.column_uploaded,
-                                    config.database_structure.clone().users.column_downloaded,
-                                    config.database_structure.clone().users.column_completed,
-                                    config.database_structure.clone().users.column_active,
-                                    config.database_structure.clone().users.column_updated,
-                                    config.database_structure.clone().users.column_uuid
-                                ).as_str()
-                            ).execute(pool).await {
-                                Ok(_) => {}
-                                Err(error) => { panic!("[MySQL] Error: {}", error); }
-                            }
-                        }
-                        false => {
-                            match sqlx::query(
-                                format!(
-                                    "CREATE TABLE `{}` (`{}` VARCHAR(36) NOT NULL, `{}` VARCHAR(40) NOT NULL, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` TINYINT NOT NULL DEFAULT 0, `{}` INT NOT NULL DEFAULT 0, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
-                                    config.database_structure.clone().users.table_name,
-                                    config.database_structure.clone().users.column_uuid,
-                                    config.database_structure.clone().users.column_key,
-                                    config.database_structure.clone().users.column_uploaded,
-                                    config.database_structure.clone().users.column_downloaded,
-                                    config.database_structure.clone().users.column_completed,
-                                    config.database_structure.clone().users.column_active,
-                                    config.database_structure.clone().users.column_updated,
-                                    config.database_structure.clone().users.column_uuid
-                                ).as_str()
-                            ).execute(pool).await {
-                                Ok(_) => {}
-                                Err(error) => { panic!("[MySQL] Error: {}", error); }
-                            }
-                        }
-                    }
-                }
-                false => {
-                    match config.database_structure.clone().users.bin_type_key {
-                        true => {
-                            match sqlx::query(
-                                format!(
-                                    "CREATE TABLE `{}` (`{}` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT, `{}` BINARY(20) NOT NULL, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` TINYINT NOT NULL DEFAULT 0, `{}` INT NOT NULL DEFAULT 0, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
-                                    config.database_structure.clone().users.table_name,
-                                    config.database_structure.clone().users.column_id,
-                                    config.database_structure.clone().users.column_key,
-                                    config.database_structure.clone().users.column_uploaded,
-                                    config.database_structure.clone().users.column_downloaded,
-                                    config.database_structure.clone().users.column_completed,
-                                    config.database_structure.clone().users.column_active,
-                                    config.database_structure.clone().users.column_updated,
-                                    config.database_structure.clone().users.column_id
-                                ).as_str()
-                            ).execute(pool).await {
-                                Ok(_) => {}
-                                Err(error) => { panic!("[MySQL] Error: {}", error); }
-                            }
-                        }
-                        false => {
-                            match sqlx::query(
-                                format!(
-                                    "CREATE TABLE `{}` (`{}` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT, `{}` VARCHAR(40) NOT NULL, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` BIGINT UNSIGNED NOT NULL DEFAULT 0, `{}` TINYINT NOT NULL DEFAULT 0, `{}` INT NOT NULL DEFAULT 0, PRIMARY KEY (`{}`)) COLLATE='utf8mb4_general_ci'",
-                                    config.database_structure.clone().users.table_name,
-                                    config.database_structure.clone().users.column_id,
-                                    config.database_structure.clone().users.column_key,
-                                    config.database_structure.clone().users.column_uploaded,
-                                    config.database_structure.clone().users.column_downloaded,
-                                    config.database_structure.clone().users.column_completed,
-                                    config.database_structure.clone().users.column_active,
-                                    config.database_structure.clone().users.column_updated,
-                                    config.database_structure.clone().users.column_id
-                                ).as_str()
-                            ).execute(pool).await {
-                                Ok(_) => {}
-                                Err(error) => { panic!("[MySQL] Error: {}", error); }
-                            }
-                        }
-                    }
-                }
+    /// Applies the migrations newer than the recorded schema version in order. MySQL commits every DDL statement
+    /// implicitly, so a migration can not be rolled back as a whole: the schema version table records the number of
+    /// statements done in `step` after each of them and a failed migration resumes after its last recorded statement.
+    /// A statement interrupted between its implicit commit and its record runs again, so every statement has to be
+    /// idempotent (`IF NOT EXISTS`). The migration is marked `applied` once all statements ran. With `dry_run` the
+    /// pending migrations are only returned.
+    #[tracing::instrument(level = "debug")]
+    pub async fn apply_migrations(&self, migrations: Vec<DatabaseMigration>, dry_run: bool) -> Result<Vec<DatabaseMigration>, Error>
+    {
+        let version = self.get_schema_version().await?;
+        let pending: Vec<DatabaseMigration> = migrations.into_iter().filter(|migration| migration.version > version).collect();
+        if dry_run || pending.is_empty() {
+            return Ok(pending);
+        }
+        sqlx::query(&format!("CREATE TABLE IF NOT EXISTS `{}` (`version` BIGINT UNSIGNED NOT NULL, `name` VARCHAR(255) NOT NULL, `step` INT UNSIGNED NOT NULL DEFAULT 0, `applied` BIGINT NOT NULL DEFAULT 0, PRIMARY KEY (`version`)) COLLATE='utf8mb4_general_ci'", self.schema_version_table)).execute(&self.pool).await?;
+        for migration in pending.iter() {
+            let step = sqlx::query(&format!("SELECT `step` FROM `{}` WHERE `version` = ?", self.schema_version_table))
+                .bind(migration.version)
+                .fetch_optional(&self.pool).await?
+                .map_or(Ok(0), |row| row.try_get::<u32, _>(0))? as usize;
+            match step {
+                0 => { info!("[MySQL] Applying migration {} {}", migration.version, migration.name); }
+                _ => { info!("[MySQL] Resuming migration {} {} after step {}", migration.version, migration.name, step); }
             }
-            info!("[BOOT] Created the database and tables, restart without the parameter to start the app.");
-            task::sleep(Duration::from_secs(1)).await;
-            exit(0);
+            for (index, statement) in migration.statements.iter().enumerate().skip(step) {
+                sqlx::query(statement).execute(&self.pool).await?;
+                sqlx::query(&format!("INSERT INTO `{}` (`version`, `name`, `step`) VALUES (?, ?, ?) ON DUPLICATE KEY UPDATE `step` = VALUES(`step`)", self.schema_version_table))
+                    .bind(migration.version)
+                    .bind(migration.name.clone())
+                    .bind((index + 1) as u32)
+                    .execute(&self.pool).await?;
+            }
+            sqlx::query(&format!("INSERT INTO `{}` (`version`, `name`, `step`, `applied`) VALUES (?, ?, ?, ?) ON DUPLICATE KEY UPDATE `applied` = VALUES(`applied`)", self.schema_version_table))
+                .bind(migration.version)
+                .bind(migration.name.clone())
+                .bind(migration.statements.len() as u32)
+                .bind(chrono::Utc::now().timestamp())
+                .execute(&self.pool).await?;
         }
-
-        structure
+        Ok(pending)
     }
+}
 
+#[async_trait]
+impl DatabaseBackend for DatabaseConnectorMySQL {
     #[tracing::instrument(level = "debug")]
-    pub async fn load_torrents(&self, tracker: Arc<TorrentTracker>) -> Result<(u64, u64), Error>
+    async fn load_torrents(&self, tracker: Arc<TorrentTracker>) -> Result<(u64, u64), Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -291,27 +197,23 @@ impl DatabaseConnectorMySQL {
             let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
                 true => {
                     format!(
-                        "SELECT HEX(`{}`) AS `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT HEX(`{}`) AS `{}`, `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_infohash,
                         structure.column_infohash,
                         structure.column_completed,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
                 false => {
                     format!(
-                        "SELECT `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT `{}`, `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_infohash,
                         structure.column_completed,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(start as i64).bind(length as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let info_hash_data: &[u8] = result.get(structure.column_infohash.as_str());
                 let info_hash: [u8; 20] = <[u8; 20]>::try_from(hex::decode(info_hash_data).unwrap()[0..20].as_ref()).unwrap();
@@ -322,7 +224,8 @@ impl DatabaseConnectorMySQL {
                         seeds: BTreeMap::new(),
                         peers: BTreeMap::new(),
                         completed: completed_count,
-                        updated: std::time::Instant::now()
+                        updated: std::time::Instant::now(),
+                        peers_cursor: 0
                     }
                 );
                 torrents += 1;
@@ -340,35 +243,68 @@ impl DatabaseConnectorMySQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_torrents(&self, tracker: Arc<TorrentTracker>, torrents: BTreeMap<InfoHash, (TorrentEntry, UpdatesAction)>) -> Result<(), Error>
+    async fn save_torrents(&self, tracker: Arc<TorrentTracker>, torrents: BTreeMap<InfoHash, (TorrentEntry, UpdatesAction)>) -> Result<(), Error>
     {
         let mut torrents_transaction = self.pool.begin().await?;
         let mut torrents_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().torrents;
-        for (info_hash, (torrent_entry, updates_action)) in torrents.iter() {
-            torrents_handled_entries += 1;
-            match updates_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                            true => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`=UNHEX('{}')",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
+        let database = tracker.config.deref().clone().database;
+        let batch_size = database.batch_size as usize;
+        let (removes, saves): (Vec<_>, Vec<_>) = torrents.iter().partition(|(_, (_, updates_action))| *updates_action == UpdatesAction::Remove);
+        if database.remove_action {
+            for chunk in removes.chunks(batch_size) {
+                let mut query_builder: QueryBuilder<MySql> = QueryBuilder::new(format!(
+                    "DELETE FROM `{}` WHERE `{}` IN (",
+                    structure.table_name,
+                    structure.column_infohash
+                ));
+                let mut separated = query_builder.separated(", ");
+                for (info_hash, _) in chunk.iter() {
+                    DatabaseConnectorMySQL::push_hash(&mut separated, info_hash.0, structure.bin_type_infohash);
+                }
+                separated.push_unseparated(")");
+                match query_builder.build().execute(&mut *torrents_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[MySQL] Error: {}", e);
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        torrents_handled_entries += removes.len() as u64;
+        match database.insert_vacant {
+            true => {
+                let mut columns = vec![];
+                if database.update_peers {
+                    columns.push(structure.column_seeds.clone());
+                    columns.push(structure.column_peers.clone());
+                }
+                if database.update_completed {
+                    columns.push(structure.column_completed.clone());
+                }
+                for chunk in saves.chunks(batch_size) {
+                    if !columns.is_empty() {
+                        let mut query_builder: QueryBuilder<MySql> = QueryBuilder::new(format!(
+                            "INSERT INTO `{}` (`{}`, `{}`) ",
+                            structure.table_name,
+                            structure.column_infohash,
+                            columns.join("`, `")
+                        ));
+                        query_builder.push_values(chunk.iter(), |mut row, (info_hash, (torrent_entry, _))| {
+                            DatabaseConnectorMySQL::push_hash(&mut row, info_hash.0, structure.bin_type_infohash);
+                            if database.update_peers {
+                                row.push_bind(torrent_entry.seeds.len() as i64).push_bind(torrent_entry.peers.len() as i64);
                             }
-                            false => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`='{}'",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
+                            if database.update_completed {
+                                row.push_bind(torrent_entry.completed as i64);
                             }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
+                        });
+                        query_builder.push(format!(
+                            " ON DUPLICATE KEY UPDATE {}",
+                            columns.iter().map(|column| format!("`{}`=VALUES(`{}`)", column, column)).collect::<Vec<String>>().join(", ")
+                        ));
+                        match query_builder.build().execute(&mut *torrents_transaction).await {
                             Ok(_) => {}
                             Err(e) => {
                                 error!("[MySQL] Error: {}", e);
@@ -376,170 +312,60 @@ impl DatabaseConnectorMySQL {
                             }
                         }
                     }
+                    torrents_handled_entries += chunk.len() as u64;
+                    info!("[MySQL] Handled {} torrents", torrents_handled_entries);
                 }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    match tracker.config.deref().clone().database.insert_vacant {
-                        true => {
-                            if tracker.config.deref().clone().database.update_peers {
-                                let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                                    true => {
-                                        format!(
-                                            "INSERT INTO `{}` (`{}`, `{}`, `{}`) VALUES (UNHEX('{}'), {}, {}) ON DUPLICATE KEY UPDATE `{}` = VALUES(`{}`), `{}`=VALUES(`{}`)",
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            info_hash,
-                                            torrent_entry.seeds.len(),
-                                            torrent_entry.peers.len(),
-                                            structure.column_seeds,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            structure.column_peers
-                                        )
-                                    }
-                                    false => {
-                                        format!(
-                                            "INSERT INTO `{}` (`{}`, `{}`, `{}`) VALUES ('{}', {}, {}) ON DUPLICATE KEY UPDATE `{}`=VALUES(`{}`), `{}`=VALUES(`{}`)",
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            info_hash,
-                                            torrent_entry.seeds.len(),
-                                            torrent_entry.peers.len(),
-                                            structure.column_seeds,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            structure.column_peers
-                                        )
-                                    }
-                                };
-                                match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        error!("[MySQL] Error: {}", e);
-                                        return Err(e);
-                                    }
-                                }
-                            }
-                            if tracker.config.deref().clone().database.update_completed {
-                                let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                                    true => {
-                                        format!(
-                                            "INSERT INTO `{}` (`{}`, `{}`) VALUES (UNHEX('{}'), {}) ON DUPLICATE KEY UPDATE `{}`=VALUES(`{}`)",
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            structure.column_completed,
-                                            info_hash,
-                                            torrent_entry.completed,
-                                            structure.column_completed,
-                                            structure.column_completed
-                                        )
-                                    }
-                                    false => {
-                                        format!(
-                                            "INSERT INTO `{}` (`{}`, `{}`) VALUES ('{}', {}) ON DUPLICATE KEY UPDATE `{}`=VALUES(`{}`)",
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            structure.column_completed,
-                                            info_hash,
-                                            torrent_entry.completed,
-                                            structure.column_completed,
-                                            structure.column_completed
-                                        )
-                                    }
-                                };
-                                match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        error!("[MySQL] Error: {}", e);
-                                        return Err(e);
-                                    }
-                                }
+            }
+            false => {
+                let string_format_peers = format!(
+                    "UPDATE IGNORE `{}` SET `{}`=?, `{}`=? WHERE `{}`=?",
+                    structure.table_name,
+                    structure.column_seeds,
+                    structure.column_peers,
+                    structure.column_infohash
+                );
+                let string_format_completed = format!(
+                    "UPDATE IGNORE `{}` SET `{}`=? WHERE `{}`=?",
+                    structure.table_name,
+                    structure.column_completed,
+                    structure.column_infohash
+                );
+                for (info_hash, (torrent_entry, _)) in saves.iter() {
+                    torrents_handled_entries += 1;
+                    if database.update_peers {
+                        let query = sqlx::query(string_format_peers.as_str())
+                            .bind(torrent_entry.seeds.len() as i64)
+                            .bind(torrent_entry.peers.len() as i64);
+                        match DatabaseConnectorMySQL::bind_hash(query, info_hash.0, structure.bin_type_infohash).execute(&mut *torrents_transaction).await {
+                            Ok(_) => {}
+                            Err(e) => {
+                                error!("[MySQL] Error: {}", e);
+                                return Err(e);
                             }
                         }
-                        false => {
-                            if tracker.config.deref().clone().database.update_peers {
-                                let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                                    true => {
-                                        format!(
-                                            "UPDATE IGNORE `{}` SET `{}`={}, `{}`={} WHERE `{}`=UNHEX('{}')",
-                                            structure.table_name,
-                                            structure.column_seeds,
-                                            torrent_entry.seeds.len(),
-                                            structure.column_peers,
-                                            torrent_entry.peers.len(),
-                                            structure.column_infohash,
-                                            info_hash
-                                        )
-                                    }
-                                    false => {
-                                        format!(
-                                            "UPDATE IGNORE `{}` SET `{}`={}, `{}`={} WHERE `{}`='{}'",
-                                            structure.table_name,
-                                            structure.column_seeds,
-                                            torrent_entry.seeds.len(),
-                                            structure.column_peers,
-                                            torrent_entry.peers.len(),
-                                            structure.column_infohash,
-                                            info_hash
-                                        )
-                                    }
-                                };
-                                match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        error!("[MySQL] Error: {}", e);
-                                        return Err(e);
-                                    }
-                                }
-                            }
-                            if tracker.config.deref().clone().database.update_completed {
-                                let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                                    true => {
-                                        format!(
-                                            "UPDATE IGNORE `{}` SET `{}`={} WHERE `{}`=UNHEX('{}')",
-                                            structure.table_name,
-                                            structure.column_completed,
-                                            torrent_entry.completed,
-                                            structure.column_infohash,
-                                            info_hash
-                                        )
-                                    }
-                                    false => {
-                                        format!(
-                                            "UPDATE IGNORE `{}` SET `{}`={} WHERE `{}`='{}'",
-                                            structure.table_name,
-                                            structure.column_completed,
-                                            torrent_entry.completed,
-                                            structure.column_infohash,
-                                            info_hash
-                                        )
-                                    }
-                                };
-                                match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        error!("[MySQL] Error: {}", e);
-                                        return Err(e);
-                                    }
-                                }
+                    }
+                    if database.update_completed {
+                        let query = sqlx::query(string_format_completed.as_str()).bind(torrent_entry.completed as i64);
+                        match DatabaseConnectorMySQL::bind_hash(query, info_hash.0, structure.bin_type_infohash).execute(&mut *torrents_transaction).await {
+                            Ok(_) => {}
+                            Err(e) => {
+                                error!("[MySQL] Error: {}", e);
+                                return Err(e);
                             }
                         }
                     }
+                    if (torrents_handled_entries as f64 / 1000f64).fract() == 0.0 {
+                        info!("[MySQL] Handled {} torrents", torrents_handled_entries);
+                    }
                 }
             }
-            if (torrents_handled_entries as f64 / 1000f64).fract() == 0.0 || torrents.len() as u64 == torrents_handled_entries {
-                info!("[MySQL] Handled {} torrents", torrents_handled_entries);
-            }
         }
         info!("[MySQL] Handled {} torrents", torrents_handled_entries);
         self.commit(torrents_transaction).await
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn load_whitelist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
+    async fn load_whitelist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -549,25 +375,21 @@ impl DatabaseConnectorMySQL {
             let string_format = match tracker.config.deref().clone().database_structure.whitelist.bin_type_infohash {
                 true => {
                     format!(
-                        "SELECT HEX(`{}`) AS `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT HEX(`{}`) AS `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_infohash,
                         structure.column_infohash,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
                 false => {
                     format!(
-                        "SELECT `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_infohash,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(start as i64).bind(length as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let info_hash_data: &[u8] = result.get(structure.column_infohash.as_str());
                 let info_hash: [u8; 20] = <[u8; 20]>::try_from(hex::decode(info_hash_data).unwrap()[0..20].as_ref()).unwrap();
@@ -585,82 +407,59 @@ impl DatabaseConnectorMySQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_whitelist(&self, tracker: Arc<TorrentTracker>, whitelists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>
+    async fn save_whitelist(&self, tracker: Arc<TorrentTracker>, whitelists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>
     {
         let mut whitelist_transaction = self.pool.begin().await?;
-        let mut whitelist_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().whitelist;
-        for (info_hash, updates_action) in whitelists.iter() {
-            whitelist_handled_entries += 1;
-            match updates_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.whitelist.bin_type_infohash {
-                            true => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`=UNHEX('{}')",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`='{}'",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *whitelist_transaction).await {
-                            Ok(_) => {}
-                            Err(e) => {
-                                error!("[MySQL] Error: {}", e);
-                                return Err(e);
-                            }
-                        }
-                    }
+        let batch_size = tracker.config.database.batch_size as usize;
+        // The last action queued for a hash wins, a statement can't touch the same row twice
+        let whitelists_latest: BTreeMap<InfoHash, UpdatesAction> = whitelists.iter().copied().collect();
+        let (removes, saves): (Vec<_>, Vec<_>) = whitelists_latest.iter().partition(|(_, updates_action)| **updates_action == UpdatesAction::Remove);
+        if tracker.config.deref().clone().database.remove_action {
+            for chunk in removes.chunks(batch_size) {
+                let mut query_builder: QueryBuilder<MySql> = QueryBuilder::new(format!(
+                    "DELETE FROM `{}` WHERE `{}` IN (",
+                    structure.table_name,
+                    structure.column_infohash
+                ));
+                let mut separated = query_builder.separated(", ");
+                for (info_hash, _) in chunk.iter() {
+                    DatabaseConnectorMySQL::push_hash(&mut separated, info_hash.0, structure.bin_type_infohash);
                 }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    let string_format = match tracker.config.deref().clone().database_structure.whitelist.bin_type_infohash {
-                        true => {
-                            format!(
-                                "INSERT IGNORE INTO `{}` (`{}`) VALUES (UNHEX('{}'))",
-                                structure.table_name,
-                                structure.column_infohash,
-                                info_hash
-                            )
-                        }
-                        false => {
-                            format!(
-                                "INSERT IGNORE INTO `{}` (`{}`) VALUES ('{}')",
-                                structure.table_name,
-                                structure.column_infohash,
-                                info_hash
-                            )
-                        }
-                    };
-                    match sqlx::query(string_format.as_str()).execute(&mut *whitelist_transaction).await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            error!("[MySQL] Error: {}", e);
-                            return Err(e);
-                        }
+                separated.push_unseparated(")");
+                match query_builder.build().execute(&mut *whitelist_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[MySQL] Error: {}", e);
+                        return Err(e);
                     }
                 }
             }
-            if (whitelist_handled_entries as f64 / 1000f64).fract() == 0.0 {
-                info!("[MySQL] Handled {} whitelisted torrents", whitelist_handled_entries);
+        }
+        for chunk in saves.chunks(batch_size) {
+            let mut query_builder: QueryBuilder<MySql> = QueryBuilder::new(format!(
+                "INSERT IGNORE INTO `{}` (`{}`) ",
+                structure.table_name,
+                structure.column_infohash
+            ));
+            query_builder.push_values(chunk.iter(), |mut row, (info_hash, _)| {
+                DatabaseConnectorMySQL::push_hash(&mut row, info_hash.0, structure.bin_type_infohash);
+            });
+            match query_builder.build().execute(&mut *whitelist_transaction).await {
+                Ok(_) => {}
+                Err(e) => {
+                    error!("[MySQL] Error: {}", e);
+                    return Err(e);
+                }
             }
         }
-        info!("[MySQL] Handled {} whitelisted torrents", whitelist_handled_entries);
-        let _ = self.commit(whitelist_transaction).await;
-        Ok(whitelist_handled_entries)
+        info!("[MySQL] Handled {} whitelisted torrents", whitelists.len());
+        self.commit(whitelist_transaction).await?;
+        Ok(whitelists.len() as u64)
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn load_blacklist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
+    async fn load_blacklist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -670,25 +469,21 @@ impl DatabaseConnectorMySQL {
             let string_format = match tracker.config.deref().clone().database_structure.blacklist.bin_type_infohash {
                 true => {
                     format!(
-                        "SELECT HEX(`{}`) AS `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT HEX(`{}`) AS `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_infohash,
                         structure.column_infohash,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
                 false => {
                     format!(
-                        "SELECT `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_infohash,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(start as i64).bind(length as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let info_hash_data: &[u8] = result.get(structure.column_infohash.as_str());
                 let info_hash: [u8; 20] = <[u8; 20]>::try_from(hex::decode(info_hash_data).unwrap()[0..20].as_ref()).unwrap();
@@ -706,82 +501,59 @@ impl DatabaseConnectorMySQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_blacklist(&self, tracker: Arc<TorrentTracker>, blacklists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>
+    async fn save_blacklist(&self, tracker: Arc<TorrentTracker>, blacklists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>
     {
         let mut blacklist_transaction = self.pool.begin().await?;
-        let mut blacklist_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().blacklist;
-        for (info_hash, updates_action) in blacklists.iter() {
-            blacklist_handled_entries += 1;
-            match updates_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.blacklist.bin_type_infohash {
-                            true => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`=UNHEX('{}')",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`='{}'",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *blacklist_transaction).await {
-                            Ok(_) => {}
-                            Err(e) => {
-                                error!("[MySQL] Error: {}", e);
-                                return Err(e);
-                            }
-                        }
-                    }
+        let batch_size = tracker.config.database.batch_size as usize;
+        // The last action queued for a hash wins, a statement can't touch the same row twice
+        let blacklists_latest: BTreeMap<InfoHash, UpdatesAction> = blacklists.iter().copied().collect();
+        let (removes, saves): (Vec<_>, Vec<_>) = blacklists_latest.iter().partition(|(_, updates_action)| **updates_action == UpdatesAction::Remove);
+        if tracker.config.deref().clone().database.remove_action {
+            for chunk in removes.chunks(batch_size) {
+                let mut query_builder: QueryBuilder<MySql> = QueryBuilder::new(format!(
+                    "DELETE FROM `{}` WHERE `{}` IN (",
+                    structure.table_name,
+                    structure.column_infohash
+                ));
+                let mut separated = query_builder.separated(", ");
+                for (info_hash, _) in chunk.iter() {
+                    DatabaseConnectorMySQL::push_hash(&mut separated, info_hash.0, structure.bin_type_infohash);
                 }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    let string_format = match tracker.config.deref().clone().database_structure.blacklist.bin_type_infohash {
-                        true => {
-                            format!(
-                                "INSERT IGNORE INTO `{}` (`{}`) VALUES (UNHEX('{}'))",
-                                structure.table_name,
-                                structure.column_infohash,
-                                info_hash
-                            )
-                        }
-                        false => {
-                            format!(
-                                "INSERT IGNORE INTO `{}` (`{}`) VALUES ('{}')",
-                                structure.table_name,
-                                structure.column_infohash,
-                                info_hash
-                            )
-                        }
-                    };
-                    match sqlx::query(string_format.as_str()).execute(&mut *blacklist_transaction).await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            error!("[MySQL] Error: {}", e);
-                            return Err(e);
-                        }
+                separated.push_unseparated(")");
+                match query_builder.build().execute(&mut *blacklist_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[MySQL] Error: {}", e);
+                        return Err(e);
                     }
                 }
             }
-            if (blacklist_handled_entries as f64 / 1000f64).fract() == 0.0 {
-                info!("[MySQL] Handled {} blacklisted torrents", blacklist_handled_entries);
+        }
+        for chunk in saves.chunks(batch_size) {
+            let mut query_builder: QueryBuilder<MySql> = QueryBuilder::new(format!(
+                "INSERT IGNORE INTO `{}` (`{}`) ",
+                structure.table_name,
+                structure.column_infohash
+            ));
+            query_builder.push_values(chunk.iter(), |mut row, (info_hash, _)| {
+                DatabaseConnectorMySQL::push_hash(&mut row, info_hash.0, structure.bin_type_infohash);
+            });
+            match query_builder.build().execute(&mut *blacklist_transaction).await {
+                Ok(_) => {}
+                Err(e) => {
+                    error!("[MySQL] Error: {}", e);
+                    return Err(e);
+                }
             }
         }
-        info!("[MySQL] Handled {} blacklisted torrents", blacklist_handled_entries);
-        let _ = self.commit(blacklist_transaction).await;
-        Ok(blacklist_handled_entries)
+        info!("[MySQL] Handled {} blacklisted torrents", blacklists.len());
+        self.commit(blacklist_transaction).await?;
+        Ok(blacklists.len() as u64)
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn load_keys(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
+    async fn load_keys(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -791,27 +563,23 @@ impl DatabaseConnectorMySQL {
             let string_format = match tracker.config.deref().clone().database_structure.keys.bin_type_hash {
                 true => {
                     format!(
-                        "SELECT HEX(`{}`) AS `{}`,`{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT HEX(`{}`) AS `{}`,`{}` FROM `{}` LIMIT ?, ?",
                         structure.column_hash,
                         structure.column_hash,
                         structure.column_timeout,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
                 false => {
                     format!(
-                        "SELECT `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT `{}`, `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_hash,
                         structure.column_timeout,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(start as i64).bind(length as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let hash_data: &[u8] = result.get(structure.column_hash.as_str());
                 let hash: [u8; 20] = <[u8; 20]>::try_from(hex::decode(hash_data).unwrap()[0..20].as_ref()).unwrap();
@@ -830,94 +598,68 @@ impl DatabaseConnectorMySQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_keys(&self, tracker: Arc<TorrentTracker>, keys: BTreeMap<InfoHash, (i64, UpdatesAction)>) -> Result<u64, Error>
+    async fn save_keys(&self, tracker: Arc<TorrentTracker>, keys: BTreeMap<InfoHash, (i64, UpdatesAction)>) -> Result<u64, Error>
     {
         let mut keys_transaction = self.pool.begin().await?;
         let mut keys_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().keys;
-        for (hash, (timeout, update_action)) in keys.iter() {
-            keys_handled_entries += 1;
-            match update_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.keys.bin_type_hash {
-                            true => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`=UNHEX('{}')",
-                                    structure.table_name,
-                                    structure.column_hash,
-                                    hash
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`='{}'",
-                                    structure.table_name,
-                                    structure.column_hash,
-                                    hash
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *keys_transaction).await {
-                            Ok(_) => {}
-                            Err(e) => {
-                                error!("[MySQL] Error: {}", e);
-                                return Err(e);
-                            }
-                        }
-                    }
+        let batch_size = tracker.config.database.batch_size as usize;
+        let (removes, saves): (Vec<_>, Vec<_>) = keys.iter().partition(|(_, (_, updates_action))| *updates_action == UpdatesAction::Remove);
+        if tracker.config.deref().clone().database.remove_action {
+            for chunk in removes.chunks(batch_size) {
+                let mut query_builder: QueryBuilder<MySql> = QueryBuilder::new(format!(
+                    "DELETE FROM `{}` WHERE `{}` IN (",
+                    structure.table_name,
+                    structure.column_hash
+                ));
+                let mut separated = query_builder.separated(", ");
+                for (hash, _) in chunk.iter() {
+                    DatabaseConnectorMySQL::push_hash(&mut separated, hash.0, structure.bin_type_hash);
                 }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    let string_format = match tracker.config.deref().clone().database_structure.keys.bin_type_hash {
-                        true => {
-                            format!(
-                                "INSERT INTO `{}` (`{}`, `{}`) VALUES (UNHEX('{}'), {}) ON DUPLICATE KEY UPDATE `{}`=VALUES(`{}`), `{}`=VALUES(`{}`)",
-                                structure.table_name,
-                                structure.column_hash,
-                                structure.column_timeout,
-                                hash,
-                                timeout,
-                                structure.column_hash,
-                                structure.column_hash,
-                                structure.column_timeout,
-                                structure.column_timeout
-                            )
-                        }
-                        false => {
-                            format!(
-                                "INSERT INTO `{}` (`{}`, `{}`) VALUES ('{}', {}) ON DUPLICATE KEY UPDATE `{}`=VALUES(`{}`), `{}`=VALUES(`{}`)",
-                                structure.table_name,
-                                structure.column_hash,
-                                structure.column_timeout,
-                                hash,
-                                timeout,
-                                structure.column_hash,
-                                structure.column_hash,
-                                structure.column_timeout,
-                                structure.column_timeout
-                            )
-                        }
-                    };
-                    match sqlx::query(string_format.as_str()).execute(&mut *keys_transaction).await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            error!("[MySQL] Error: {}", e);
-                            return Err(e);
-                        }
+                separated.push_unseparated(")");
+                match query_builder.build().execute(&mut *keys_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[MySQL] Error: {}", e);
+                        return Err(e);
                     }
                 }
             }
-            if (keys_handled_entries as f64 / 1000f64).fract() == 0.0 {
-                info!("[MySQL] Handled {} keys", keys_handled_entries);
+        }
+        keys_handled_entries += removes.len() as u64;
+        for chunk in saves.chunks(batch_size) {
+            let mut query_builder: QueryBuilder<MySql> = QueryBuilder::new(format!(
+                "INSERT INTO `{}` (`{}`, `{}`) ",
+                structure.table_name,
+                structure.column_hash,
+                structure.column_timeout
+            ));
+            query_builder.push_values(chunk.iter(), |mut row, (hash, (timeout, _))| {
+                DatabaseConnectorMySQL::push_hash(&mut row, hash.0, structure.bin_type_hash);
+                row.push_bind(*timeout);
+            });
+            query_builder.push(format!(
+                " ON DUPLICATE KEY UPDATE `{}`=VALUES(`{}`)",
+                structure.column_timeout,
+                structure.column_timeout
+            ));
+            match query_builder.build().execute(&mut *keys_transaction).await {
+                Ok(_) => {}
+                Err(e) => {
+                    error!("[MySQL] Error: {}", e);
+                    return Err(e);
+                }
             }
+            keys_handled_entries += chunk.len() as u64;
+            info!("[MySQL] Handled {} keys", keys_handled_entries);
         }
         info!("[MySQL] Handled {} keys", keys_handled_entries);
-        let _ = self.commit(keys_transaction).await;
+        self.commit(keys_transaction).await?;
         Ok(keys_handled_entries)
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn load_users(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
+    async fn load_users(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -929,7 +671,7 @@ impl DatabaseConnectorMySQL {
                     match tracker.config.deref().clone().database_structure.users.bin_type_key {
                         true => {
                             format!(
-                                "SELECT `{}`, HEX(`{}`) AS `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                                "SELECT `{}`, HEX(`{}`) AS `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT ?, ?",
                                 structure.column_uuid,
                                 structure.column_key,
                                 structure.column_key,
@@ -938,14 +680,12 @@ impl DatabaseConnectorMySQL {
                                 structure.column_completed,
                                 structure.column_updated,
                                 structure.column_active,
-                                structure.table_name,
-                                start,
-                                length
+                                structure.table_name
                             )
                         }
                         false => {
                             format!(
-                                "SELECT `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                                "SELECT `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT ?, ?",
                                 structure.column_uuid,
                                 structure.column_key,
                                 structure.column_uploaded,
@@ -953,9 +693,7 @@ impl DatabaseConnectorMySQL {
                                 structure.column_completed,
                                 structure.column_updated,
                                 structure.column_active,
-                                structure.table_name,
-                                start,
-                                length
+                                structure.table_name
                             )
                         }
                     }
@@ -964,7 +702,7 @@ impl DatabaseConnectorMySQL {
                     match tracker.config.deref().clone().database_structure.users.bin_type_key {
                         true => {
                             format!(
-                                "SELECT `{}`, HEX(`{}`) AS `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                                "SELECT `{}`, HEX(`{}`) AS `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT ?, ?",
                                 structure.column_id,
                                 structure.column_key,
                                 structure.column_key,
@@ -973,14 +711,12 @@ impl DatabaseConnectorMySQL {
                                 structure.column_completed,
                                 structure.column_updated,
                                 structure.column_active,
-                                structure.table_name,
-                                start,
-                                length
+                                structure.table_name
                             )
                         }
                         false => {
                             format!(
-                                "SELECT `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                                "SELECT `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT ?, ?",
                                 structure.column_id,
                                 structure.column_key,
                                 structure.column_uploaded,
@@ -988,30 +724,26 @@ impl DatabaseConnectorMySQL {
                                 structure.column_completed,
                                 structure.column_updated,
                                 structure.column_active,
-                                structure.table_name,
-                                start,
-                                length
+                                structure.table_name
                             )
                         }
                     }
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(start as i64).bind(length as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let hash = match tracker.config.deref().clone().database_structure.users.id_uuid {
                     true => {
                         let uuid_data: &[u8] = result.get(structure.column_uuid.as_str());
                         let mut hasher = Sha1::new();
                         hasher.update(uuid_data);
-                        let hashed = <[u8; 20]>::try_from(hasher.finalize().as_slice()).unwrap();
-                        hashed
+                        <[u8; 20]>::try_from(hasher.finalize().as_slice()).unwrap()
                     }
                     false => {
                         let id_data: &[u8] = result.get(structure.column_id.as_str());
                         let mut hasher = Sha1::new();
                         hasher.update(id_data);
-                        let hashed = <[u8; 20]>::try_from(hasher.finalize().as_slice()).unwrap();
-                        hashed
+                        <[u8; 20]>::try_from(hasher.finalize().as_slice()).unwrap()
                     }
                 };
                 tracker.add_user(UserId(hash), UserEntryItem {
@@ -1044,289 +776,120 @@ impl DatabaseConnectorMySQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_users(&self, tracker: Arc<TorrentTracker>, users: BTreeMap<UserId, (UserEntryItem, UpdatesAction)>) -> Result<(), Error>
+    async fn save_users(&self, tracker: Arc<TorrentTracker>, users: BTreeMap<UserId, (UserEntryItem, UpdatesAction)>) -> Result<(), Error>
     {
         let mut users_transaction = self.pool.begin().await?;
         let mut users_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().users;
-        for (_, (user_entry_item, updates_action)) in users.iter() {
-            users_handled_entries += 1;
-            match updates_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.users.id_uuid {
-                            true => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`='{}'",
-                                    structure.table_name,
-                                    structure.column_uuid,
-                                    user_entry_item.user_uuid.clone().unwrap()
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`='{}'",
-                                    structure.table_name,
-                                    structure.column_id,
-                                    user_entry_item.user_id.unwrap()
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *users_transaction).await {
-                            Ok(_) => {}
-                            Err(e) => {
-                                error!("[MySQL] Error: {}", e);
-                                return Err(e);
-                            }
-                        }
+        let batch_size = tracker.config.database.batch_size as usize;
+        let column_identity = match structure.id_uuid {
+            true => { structure.column_uuid.clone() }
+            false => { structure.column_id.clone() }
+        };
+        let (removes, saves): (Vec<_>, Vec<_>) = users.values().partition(|(_, updates_action)| *updates_action == UpdatesAction::Remove);
+        if tracker.config.deref().clone().database.remove_action {
+            for chunk in removes.chunks(batch_size) {
+                let mut query_builder: QueryBuilder<MySql> = QueryBuilder::new(format!(
+                    "DELETE FROM `{}` WHERE `{}` IN (",
+                    structure.table_name,
+                    column_identity
+                ));
+                let mut separated = query_builder.separated(", ");
+                for (user_entry_item, _) in chunk.iter() {
+                    DatabaseConnectorMySQL::push_user(&mut separated, user_entry_item, structure.id_uuid);
+                }
+                separated.push_unseparated(")");
+                match query_builder.build().execute(&mut *users_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[MySQL] Error: {}", e);
+                        return Err(e);
                     }
                 }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    let string_format = match  tracker.config.deref().clone().database.insert_vacant {
-                        true => {
-                            match tracker.config.deref().clone().database_structure.users.id_uuid {
-                                true => {
-                                    match tracker.config.deref().clone().database_structure.users.bin_type_key {
-                                        true => {
-                                            format!(
-                                                "INSERT INTO `{}` (`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`) VALUES ('{}', UNHEX('{}'), {}, {}, {}, {}, {}) ON DUPLICATE KEY UPDATE `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`)",
-                                                structure.table_name,
-                                                structure.column_uuid,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_downloaded,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_updated,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                                user_entry_item.key,
-                                                user_entry_item.uploaded,
-                                                user_entry_item.downloaded,
-                                                user_entry_item.completed,
-                                                user_entry_item.active,
-                                                user_entry_item.updated,
-                                                structure.column_key,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_uploaded,
-                                                structure.column_downloaded,
-                                                structure.column_downloaded,
-                                                structure.column_completed,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_active,
-                                                structure.column_updated,
-                                                structure.column_updated
-                                            )
-                                        }
-                                        false => {
-                                            format!(
-                                                "INSERT INTO `{}` (`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`) VALUES ('{}', '{}', {}, {}, {}, {}, {}) ON DUPLICATE KEY UPDATE `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`)",
-                                                structure.table_name,
-                                                structure.column_uuid,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_downloaded,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_updated,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                                user_entry_item.key,
-                                                user_entry_item.uploaded,
-                                                user_entry_item.downloaded,
-                                                user_entry_item.completed,
-                                                user_entry_item.active,
-                                                user_entry_item.updated,
-                                                structure.column_key,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_uploaded,
-                                                structure.column_downloaded,
-                                                structure.column_downloaded,
-                                                structure.column_completed,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_active,
-                                                structure.column_updated,
-                                                structure.column_updated
-                                            )
-                                        }
-                                    }
-                                }
-                                false => {
-                                    match tracker.config.deref().clone().database_structure.users.bin_type_key {
-                                        true => {
-                                            format!(
-                                                "INSERT INTO `{}` (`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`) VALUES ('{}', UNHEX('{}'), {}, {}, {}, {}, {}) ON DUPLICATE KEY UPDATE `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`)",
-                                                structure.table_name,
-                                                structure.column_id,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_downloaded,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_updated,
-                                                user_entry_item.user_id.unwrap(),
-                                                user_entry_item.key,
-                                                user_entry_item.uploaded,
-                                                user_entry_item.downloaded,
-                                                user_entry_item.completed,
-                                                user_entry_item.active,
-                                                user_entry_item.updated,
-                                                structure.column_key,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_uploaded,
-                                                structure.column_downloaded,
-                                                structure.column_downloaded,
-                                                structure.column_completed,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_active,
-                                                structure.column_updated,
-                                                structure.column_updated
-                                            )
-                                        }
-                                        false => {
-                                            format!(
-                                                "INSERT INTO `{}` (`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`) VALUES ('{}', '{}', {}, {}, {}, {}, {}) ON DUPLICATE KEY UPDATE `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`)",
-                                                structure.table_name,
-                                                structure.column_id,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.user_id.unwrap(),
-                                                user_entry_item.completed,
-                                                user_entry_item.active,
-                                                user_entry_item.downloaded,
-                                                user_entry_item.key,
-                                                user_entry_item.uploaded,
-                                                user_entry_item.updated,
-                                                structure.column_completed,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                structure.column_updated
-                                            )
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        false => {
-                            match tracker.config.deref().clone().database_structure.users.id_uuid {
-                                true => {
-                                    match tracker.config.deref().clone().database_structure.users.bin_type_key {
-                                        true => {
-                                            format!(
-                                                "UPDATE IGNORE `{}` SET `{}`={}, `{}`={}, `{}`={}, `{}`=UNHEX('{}'), `{}`={}, `{}`={} WHERE `{}`=UNHEX('{}')",
-                                                structure.table_name,
-                                                structure.column_completed,
-                                                user_entry_item.completed,
-                                                structure.column_active,
-                                                user_entry_item.active,
-                                                structure.column_downloaded,
-                                                user_entry_item.downloaded,
-                                                structure.column_key,
-                                                user_entry_item.key,
-                                                structure.column_uploaded,
-                                                user_entry_item.uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.updated,
-                                                structure.column_uuid,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                            )
-                                        }
-                                        false => {
-                                            format!(
-                                                "UPDATE IGNORE `{}` SET `{}`={}, `{}`={}, `{}`={}, `{}`='{}', `{}`={}, `{}`={} WHERE `{}`='{}'",
-                                                structure.table_name,
-                                                structure.column_completed,
-                                                user_entry_item.completed,
-                                                structure.column_active,
-                                                user_entry_item.active,
-                                                structure.column_downloaded,
-                                                user_entry_item.downloaded,
-                                                structure.column_key,
-                                                user_entry_item.key,
-                                                structure.column_uploaded,
-                                                user_entry_item.uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.updated,
-                                                structure.column_uuid,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                            )
-                                        }
-                                    }
-                                }
-                                false => {
-                                    match tracker.config.deref().clone().database_structure.users.bin_type_key {
-                                        true => {
-                                            format!(
-                                                "UPDATE IGNORE `{}` SET `{}`={}, `{}`={}, `{}`={}, `{}`=UNHEX('{}'), `{}`={}, `{}`={} WHERE `{}`=UNHEX('{}')",
-                                                structure.table_name,
-                                                structure.column_completed,
-                                                user_entry_item.completed,
-                                                structure.column_active,
-                                                user_entry_item.active,
-                                                structure.column_downloaded,
-                                                user_entry_item.downloaded,
-                                                structure.column_key,
-                                                user_entry_item.key,
-                                                structure.column_uploaded,
-                                                user_entry_item.uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.updated,
-                                                structure.column_id,
-                                                user_entry_item.user_id.unwrap(),
-                                            )
-                                        }
-                                        false => {
-                                            format!(
-                                                "UPDATE IGNORE `{}` SET `{}`={}, `{}`={}, `{}`={}, `{}`='{}', `{}`={}, `{}`={} WHERE `{}`='{}'",
-                                                structure.table_name,
-                                                structure.column_completed,
-                                                user_entry_item.completed,
-                                                structure.column_active,
-                                                user_entry_item.active,
-                                                structure.column_downloaded,
-                                                user_entry_item.downloaded,
-                                                structure.column_key,
-                                                user_entry_item.key,
-                                                structure.column_uploaded,
-                                                user_entry_item.uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.updated,
-                                                structure.column_id,
-                                                user_entry_item.user_id.unwrap(),
-                                            )
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    };
-                    match sqlx::query(string_format.as_str()).execute(&mut *users_transaction).await {
+            }
+        }
+        users_handled_entries += removes.len() as u64;
+        match tracker.config.deref().clone().database.insert_vacant {
+            true => {
+                for chunk in saves.chunks(batch_size) {
+                    let mut query_builder: QueryBuilder<MySql> = QueryBuilder::new(format!(
+                        "INSERT INTO `{}` (`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`) ",
+                        structure.table_name,
+                        column_identity,
+                        structure.column_completed,
+                        structure.column_active,
+                        structure.column_downloaded,
+                        structure.column_key,
+                        structure.column_uploaded,
+                        structure.column_updated
+                    ));
+                    query_builder.push_values(chunk.iter(), |mut row, (user_entry_item, _)| {
+                        DatabaseConnectorMySQL::push_user(&mut row, user_entry_item, structure.id_uuid);
+                        row.push_bind(user_entry_item.completed as i64)
+                            .push_bind(user_entry_item.active as i64)
+                            .push_bind(user_entry_item.downloaded as i64);
+                        DatabaseConnectorMySQL::push_hash(&mut row, user_entry_item.key.0, structure.bin_type_key);
+                        row.push_bind(user_entry_item.uploaded as i64)
+                            .push_bind(user_entry_item.updated as i64);
+                    });
+                    query_builder.push(format!(
+                        " ON DUPLICATE KEY UPDATE `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`), `{}`=VALUES(`{}`)",
+                        structure.column_completed,
+                        structure.column_completed,
+                        structure.column_active,
+                        structure.column_active,
+                        structure.column_downloaded,
+                        structure.column_downloaded,
+                        structure.column_key,
+                        structure.column_key,
+                        structure.column_uploaded,
+                        structure.column_uploaded,
+                        structure.column_updated,
+                        structure.column_updated
+                    ));
+                    match query_builder.build().execute(&mut *users_transaction).await {
                         Ok(_) => {}
                         Err(e) => {
                             error!("[MySQL] Error: {}", e);
                             return Err(e);
                         }
                     }
+                    users_handled_entries += chunk.len() as u64;
+                    info!("[MySQL] Handled {} users", users_handled_entries);
                 }
             }
-            if (users_handled_entries as f64 / 1000f64).fract() == 0.0 || users.len() as u64 == users_handled_entries {
-                info!("[MySQL] Handled {} users", users_handled_entries);
+            false => {
+                let string_format = format!(
+                    "UPDATE IGNORE `{}` SET `{}`=?, `{}`=?, `{}`=?, `{}`=?, `{}`=?, `{}`=? WHERE `{}`=?",
+                    structure.table_name,
+                    structure.column_completed,
+                    structure.column_active,
+                    structure.column_downloaded,
+                    structure.column_key,
+                    structure.column_uploaded,
+                    structure.column_updated,
+                    column_identity
+                );
+                for (user_entry_item, _) in saves.iter() {
+                    users_handled_entries += 1;
+                    let query = sqlx::query(string_format.as_str())
+                        .bind(user_entry_item.completed as i64)
+                        .bind(user_entry_item.active as i64)
+                        .bind(user_entry_item.downloaded as i64);
+                    let query = DatabaseConnectorMySQL::bind_hash(query, user_entry_item.key.0, structure.bin_type_key)
+                        .bind(user_entry_item.uploaded as i64)
+                        .bind(user_entry_item.updated as i64);
+                    match DatabaseConnectorMySQL::bind_user(query, user_entry_item, structure.id_uuid).execute(&mut *users_transaction).await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            error!("[MySQL] Error: {}", e);
+                            return Err(e);
+                        }
+                    }
+                    if (users_handled_entries as f64 / 1000f64).fract() == 0.0 {
+                        info!("[MySQL] Handled {} users", users_handled_entries);
+                    }
+                }
             }
         }
         info!("[MySQL] Handled {} users", users_handled_entries);
@@ -1334,7 +897,7 @@ impl DatabaseConnectorMySQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn reset_seeds_peers(&self, tracker: Arc<TorrentTracker>) -> Result<(), Error>
+    async fn reset_seeds_peers(&self, tracker: Arc<TorrentTracker>) -> Result<(), Error>
     {
         let mut reset_seeds_peers_transaction = self.pool.begin().await?;
         let structure = tracker.config.deref().clone().database_structure.clone().torrents;
@@ -1351,10 +914,60 @@ impl DatabaseConnectorMySQL {
                 return Err(e);
             }
         }
-        let _ = self.commit(reset_seeds_peers_transaction).await;
+        self.commit(reset_seeds_peers_transaction).await?;
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug")]
+    async fn schema_version(&self) -> Result<u64, Error>
+    {
+        self.get_schema_version().await
+    }
+
+    #[tracing::instrument(level = "debug")]
+    async fn migrate(&self, tracker: Arc<TorrentTracker>, dry_run: bool) -> Result<Vec<DatabaseMigration>, Error>
+    {
+        self.apply_migrations(DatabaseConnectorMySQL::migrations(&tracker.config), dry_run).await
+    }
+}
+
+impl DatabaseConnectorMySQL {
+    /// Binds a 20 bytes hash as binary, or as its hex string when the column is not binary.
+    pub fn bind_hash<'q>(query: Query<'q, MySql, MySqlArguments>, hash: [u8; 20], bin_type: bool) -> Query<'q, MySql, MySqlArguments>
+    {
+        match bin_type {
+            true => { query.bind(hash.to_vec()) }
+            false => { query.bind(hex::encode(hash)) }
+        }
+    }
+
+    /// Binds the identity of a user, its UUID or its numeric ID depending on the configured structure.
+    pub fn bind_user<'q>(query: Query<'q, MySql, MySqlArguments>, user_entry_item: &UserEntryItem, id_uuid: bool) -> Query<'q, MySql, MySqlArguments>
+    {
+        match id_uuid {
+            true => { query.bind(user_entry_item.user_uuid.clone().unwrap()) }
+            false => { query.bind(user_entry_item.user_id.unwrap()) }
+        }
+    }
+
+    /// Pushes a 20 bytes hash into a multi-row statement, the same way `bind_hash` binds it.
+    pub fn push_hash(separated: &mut Separated<'_, '_, MySql, &'static str>, hash: [u8; 20], bin_type: bool)
+    {
+        match bin_type {
+            true => { separated.push_bind(hash.to_vec()); }
+            false => { separated.push_bind(hex::encode(hash)); }
+        }
+    }
+
+    /// Pushes the identity of a user into a multi-row statement, the same way `bind_user` binds it.
+    pub fn push_user(separated: &mut Separated<'_, '_, MySql, &'static str>, user_entry_item: &UserEntryItem, id_uuid: bool)
+    {
+        match id_uuid {
+            true => { separated.push_bind(user_entry_item.user_uuid.clone().unwrap()); }
+            false => { separated.push_bind(user_entry_item.user_id.unwrap()); }
+        }
+    }
+
     pub async fn commit(&self, transaction: Transaction<'_, MySql>) -> Result<(), Error>
     {
         match transaction.commit().await {
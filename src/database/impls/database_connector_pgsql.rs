@@ -5,15 +5,17 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use async_std::task;
+use async_trait::async_trait;
 use futures_util::TryStreamExt;
 use log::{error, info};
 use sha1::{Digest, Sha1};
 use sqlx::{ConnectOptions, Error, Pool, Postgres, Row, Transaction};
-use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::postgres::{PgArguments, PgConnectOptions, PgPoolOptions};
+use sqlx::query::Query;
 use crate::config::structs::configuration::Configuration;
-use crate::database::enums::database_drivers::DatabaseDrivers;
-use crate::database::structs::database_connector::DatabaseConnector;
+use crate::database::structs::database_migration::DatabaseMigration;
 use crate::database::structs::database_connector_pgsql::DatabaseConnectorPgSQL;
+use crate::database::traits::DatabaseBackend;
 use crate::stats::enums::stats_event::StatsEvent;
 use crate::tracker::enums::updates_action::UpdatesAction;
 use crate::tracker::structs::info_hash::InfoHash;
@@ -33,7 +35,7 @@ impl DatabaseConnectorPgSQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn database_connector(config: Arc<Configuration>, create_database: bool) -> DatabaseConnector
+    pub async fn database_connector(config: Arc<Configuration>, create_database: bool) -> Box<dyn DatabaseBackend>
     {
         let pgsql_connect = DatabaseConnectorPgSQL::create(config.database.clone().path.as_str()).await;
         if pgsql_connect.is_err() {
@@ -42,244 +44,133 @@ impl DatabaseConnectorPgSQL {
             exit(1);
         }
 
-        let mut structure = DatabaseConnector { mysql: None, sqlite: None, pgsql: None, engine: None };
-        structure.pgsql = Some(DatabaseConnectorPgSQL { pool: pgsql_connect.unwrap() });
-        structure.engine = Some(DatabaseDrivers::pgsql);
+        let structure = DatabaseConnectorPgSQL { pool: pgsql_connect.unwrap(), schema_version_table: config.database_structure.schema_version.table_name.clone() };
 
         if create_database {
-            let pool = &structure.pgsql.clone().unwrap().pool;
             info!("[BOOT] Database creation triggered for PgSQL.");
 
-            // Create Torrent DB
-            info!("[BOOT PgSQL] Creating table {}", config.database_structure.clone().torrents.table_name);
-            match config.database_structure.clone().torrents.bin_type_infohash {
-                true => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS public.{} ({} bytea NOT NULL, {} integer NOT NULL DEFAULT 0, {} integer NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, CONSTRAINT torrents_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
-                            config.database_structure.clone().torrents.table_name,
-                            config.database_structure.clone().torrents.column_infohash,
-                            config.database_structure.clone().torrents.column_seeds,
-                            config.database_structure.clone().torrents.column_peers,
-                            config.database_structure.clone().torrents.column_completed,
-                            config.database_structure.clone().torrents.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[PgSQL] Error: {}", error); }
-                    }
-                }
-                false => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS public.{} ({} character(40) NOT NULL, {} integer NOT NULL DEFAULT 0, {} integer NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, CONSTRAINT torrents_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
-                            config.database_structure.clone().torrents.table_name,
-                            config.database_structure.clone().torrents.column_infohash,
-                            config.database_structure.clone().torrents.column_seeds,
-                            config.database_structure.clone().torrents.column_peers,
-                            config.database_structure.clone().torrents.column_completed,
-                            config.database_structure.clone().torrents.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[PgSQL] Error: {}", error); }
-                    }
-                }
+            if let Err(error) = structure.apply_migrations(DatabaseConnectorPgSQL::migrations(&config), false).await {
+                panic!("[PgSQL] Error: {}", error);
             }
+            info!("[BOOT] Created the database and tables, restart without the parameter to start the app.");
+            task::sleep(Duration::from_secs(1)).await;
+            exit(0);
+        }
 
-            // Create Whitelist DB
-            info!("[BOOT PgSQL] Creating table {}", config.database_structure.clone().whitelist.table_name);
-            match config.database_structure.clone().whitelist.bin_type_infohash {
-                true => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS public.{} ({} bytea NOT NULL, CONSTRAINT whitelist_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
-                            config.database_structure.clone().whitelist.table_name,
-                            config.database_structure.clone().whitelist.column_infohash,
-                            config.database_structure.clone().whitelist.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[PgSQL] Error: {}", error); }
-                    }
-                }
-                false => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS public.{} ({} character(40) NOT NULL, CONSTRAINT whitelist_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
-                            config.database_structure.clone().whitelist.table_name,
-                            config.database_structure.clone().whitelist.column_infohash,
-                            config.database_structure.clone().whitelist.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[PgSQL] Error: {}", error); }
-                    }
-                }
-            }
+        Box::new(structure)
+    }
 
-            // Create Blacklist DB
-            info!("[BOOT PgSQL] Creating table {}", config.database_structure.clone().blacklist.table_name);
-            match config.database_structure.clone().blacklist.bin_type_infohash {
-                true => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS public.{} ({} bytea NOT NULL, CONSTRAINT blacklist_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
-                            config.database_structure.clone().blacklist.table_name,
-                            config.database_structure.clone().blacklist.column_infohash,
-                            config.database_structure.clone().blacklist.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[PgSQL] Error: {}", error); }
-                    }
-                }
-                false => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS public.{} ({} character(40) NOT NULL, CONSTRAINT blacklist_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
-                            config.database_structure.clone().blacklist.table_name,
-                            config.database_structure.clone().blacklist.column_infohash,
-                            config.database_structure.clone().blacklist.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[PgSQL] Error: {}", error); }
-                    }
-                }
-            }
+    /// The ordered schema migrations for PostgreSQL, rendered with the configured table and column names.
+    #[tracing::instrument(level = "debug")]
+    pub fn migrations(config: &Configuration) -> Vec<DatabaseMigration>
+    {
+        let structure = &config.database_structure;
+        let hash_type = |bin_type: bool| match bin_type { true => "bytea", false => "character(40)" };
+        let (users_id, users_id_column, users_pkey) = match structure.users.id_uuid {
+            true => ("character(36)", &structure.users.column_uuid, "uuid_pkey"),
+            false => ("bigserial", &structure.users.column_id, "id_pkey")
+        };
+        vec![
+            DatabaseMigration {
+                version: 1,
+                name: String::from("initial_schema"),
+                statements: vec![
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS public.{} ({} {} NOT NULL, {} integer NOT NULL DEFAULT 0, {} integer NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, CONSTRAINT torrents_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
+                        structure.torrents.table_name,
+                        structure.torrents.column_infohash,
+                        hash_type(structure.torrents.bin_type_infohash),
+                        structure.torrents.column_seeds,
+                        structure.torrents.column_peers,
+                        structure.torrents.column_completed,
+                        structure.torrents.column_infohash
+                    ),
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS public.{} ({} {} NOT NULL, CONSTRAINT whitelist_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
+                        structure.whitelist.table_name,
+                        structure.whitelist.column_infohash,
+                        hash_type(structure.whitelist.bin_type_infohash),
+                        structure.whitelist.column_infohash
+                    ),
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS public.{} ({} {} NOT NULL, CONSTRAINT blacklist_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
+                        structure.blacklist.table_name,
+                        structure.blacklist.column_infohash,
+                        hash_type(structure.blacklist.bin_type_infohash),
+                        structure.blacklist.column_infohash
+                    ),
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS public.{} ({} {} NOT NULL, {} integer NOT NULL DEFAULT 0, CONSTRAINT keys_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
+                        structure.keys.table_name,
+                        structure.keys.column_hash,
+                        hash_type(structure.keys.bin_type_hash),
+                        structure.keys.column_timeout,
+                        structure.keys.column_hash
+                    ),
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS public.{} ({} {} NOT NULL, {} {} NOT NULL, {} bigint NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, {} smallint NOT NULL DEFAULT 0, {} integer NOT NULL DEFAULT 0, CONSTRAINT {} PRIMARY KEY ({})) TABLESPACE pg_default",
+                        structure.users.table_name,
+                        users_id_column,
+                        users_id,
+                        structure.users.column_key,
+                        hash_type(structure.users.bin_type_key),
+                        structure.users.column_uploaded,
+                        structure.users.column_downloaded,
+                        structure.users.column_completed,
+                        structure.users.column_active,
+                        structure.users.column_updated,
+                        users_pkey,
+                        users_id_column
+                    ),
+                ],
+            },
+        ]
+    }
 
-            // Create Keys DB
-            info!("[BOOT PgSQL] Creating table {}", config.database_structure.clone().keys.table_name);
-            match config.database_structure.clone().keys.bin_type_hash {
-                true => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS public.{} ({} bytea NOT NULL, {} integer NOT NULL DEFAULT 0, CONSTRAINT keys_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
-                            config.database_structure.clone().keys.table_name,
-                            config.database_structure.clone().keys.column_hash,
-                            config.database_structure.clone().keys.column_timeout,
-                            config.database_structure.clone().keys.column_hash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[PgSQL] Error: {}", error); }
-                    }
-                }
-                false => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS public.{} ({} character(40) NOT NULL, {} integer NOT NULL DEFAULT 0, CONSTRAINT keys_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
-                            config.database_structure.clone().keys.table_name,
-                            config.database_structure.clone().keys.column_hash,
-                            config.database_structure.clone().keys.column_timeout,
-                            config.database_structure.clone().keys.column_hash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[PgSQL] Error: {}", error); }
-                    }
-                }
-            }
+    /// Returns the latest applied migration, 0 when the schema version table does not exist yet.
+    #[tracing::instrument(level = "debug")]
+    pub async fn get_schema_version(&self) -> Result<u64, Error>
+    {
+        let row = sqlx::query("SELECT to_regclass($1) IS NOT NULL").bind(format!("public.{}", self.schema_version_table)).fetch_one(&self.pool).await?;
+        if !row.try_get::<bool, _>(0)? {
+            return Ok(0);
+        }
+        let row = sqlx::query(&format!("SELECT MAX(version) FROM public.{}", self.schema_version_table)).fetch_one(&self.pool).await?;
+        Ok(row.try_get::<Option<i64>, _>(0)?.unwrap_or(0) as u64)
+    }
 
-            // Create Users DB
-            info!("[BOOT PgSQL] Creating table {}", config.database_structure.clone().users.table_name);
-            match config.database_structure.clone().users.id_uuid {
-                true => {
-                    match config.database_structure.clone().users.bin_type_key {
-                        true => {
-                            match sqlx::query(
-                                format!(
-                                    "CREATE TABLE IF NOT EXISTS public.{} ({} character(36) NOT NULL, {} bytea NOT NULL, {} bigint NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, {} smallint NOT NULL DEFAULT 0, {} integer NOT NULL DEFAULT 0, CONSTRAINT uuid_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
-                                    config.database_structure.clone().users.table_name,
-                                    config.database_structure.clone().users.column_uuid,
-                                    config.database_structure.clone().users.column_key,
-                                    config.database_structure.clone().users.column_uploaded,
-                                    config.database_structure.clone().users.column_downloaded,
-                                    config.database_structure.clone().users.column_completed,
-                                    config.database_structure.clone().users.column_active,
-                                    config.database_structure.clone().users.column_updated,
-                                    config.database_structure.clone().users.column_uuid
-                                ).as_str()
-                            ).execute(pool).await {
-                                Ok(_) => {}
-                                Err(error) => { panic!("[PgSQL] Error: {}", error); }
-                            }
-                        }
-                        false => {
-                            match sqlx::query(
-                                format!(
-                                    "CREATE TABLE IF NOT EXISTS public.{} ({} character(36) NOT NULL, {} character(40) NOT NULL, {} bigint NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, {} smallint NOT NULL DEFAULT 0, {} integer NOT NULL DEFAULT 0, CONSTRAINT uuid_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
-                                    config.database_structure.clone().users.table_name,
-                                    config.database_structure.clone().users.column_uuid,
-                                    config.database_structure.clone().users.column_key,
-                                    config.database_structure.clone().users.column_uploaded,
-                                    config.database_structure.clone().users.column_downloaded,
-                                    config.database_structure.clone().users.column_completed,
-                                    config.database_structure.clone().users.column_active,
-                                    config.database_structure.clone().users.column_updated,
-                                    config.database_structure.clone().users.column_uuid
-                                ).as_str()
-                            ).execute(pool).await {
-                                Ok(_) => {}
-                                Err(error) => { panic!("[PgSQL] Error: {}", error); }
-                            }
-                        }
-                    }
-                }
-                false => {
-                    match config.database_structure.clone().users.bin_type_key {
-                        true => {
-                            match sqlx::query(
-                                format!(
-                                    "CREATE TABLE IF NOT EXISTS public.{} ({} bigserial NOT NULL, {} bytea NOT NULL, {} bigint NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, {} smallint NOT NULL DEFAULT 0, {} integer NOT NULL DEFAULT 0, CONSTRAINT id_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
-                                    config.database_structure.clone().users.table_name,
-                                    config.database_structure.clone().users.column_id,
-                                    config.database_structure.clone().users.column_key,
-                                    config.database_structure.clone().users.column_uploaded,
-                                    config.database_structure.clone().users.column_downloaded,
-                                    config.database_structure.clone().users.column_completed,
-                                    config.database_structure.clone().users.column_active,
-                                    config.database_structure.clone().users.column_updated,
-                                    config.database_structure.clone().users.column_id
-                                ).as_str()
-                            ).execute(pool).await {
-                                Ok(_) => {}
-                                Err(error) => { panic!("[PgSQL] Error: {}", error); }
-                            }
-                        }
-                        false => {
-                            match sqlx::query(
-                                format!(
-                                    "CREATE TABLE IF NOT EXISTS public.{} ({} bigserial NOT NULL, {} character(40) NOT NULL, {} bigint NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, {} bigint NOT NULL DEFAULT 0, {} smallint NOT NULL DEFAULT 0, {} integer NOT NULL DEFAULT 0, CONSTRAINT id_pkey PRIMARY KEY ({})) TABLESPACE pg_default",
-                                    config.database_structure.clone().users.table_name,
-                                    config.database_structure.clone().users.column_id,
-                                    config.database_structure.clone().users.column_key,
-                                    config.database_structure.clone().users.column_uploaded,
-                                    config.database_structure.clone().users.column_downloaded,
-                                    config.database_structure.clone().users.column_completed,
-                                    config.database_structure.clone().users.column_active,
-                                    config.database_structure.clone().users.column_updated,
-                                    config.database_structure.clone().users.column_id
-                                ).as_str()
-                            ).execute(pool).await {
-                                Ok(_) => {}
-                                Err(error) => { panic!("[PgSQL] Error: {}", error); }
-                            }
-                        }
-                    }
-                }
+    /// Applies the migrations newer than the recorded schema version in order, every migration together with its
+    /// record in the schema version table in one transaction. With `dry_run` the pending migrations are only returned.
+    #[tracing::instrument(level = "debug")]
+    pub async fn apply_migrations(&self, migrations: Vec<DatabaseMigration>, dry_run: bool) -> Result<Vec<DatabaseMigration>, Error>
+    {
+        let version = self.get_schema_version().await?;
+        let pending: Vec<DatabaseMigration> = migrations.into_iter().filter(|migration| migration.version > version).collect();
+        if dry_run || pending.is_empty() {
+            return Ok(pending);
+        }
+        sqlx::query(&format!("CREATE TABLE IF NOT EXISTS public.{0} (version bigint NOT NULL, name character varying(255) NOT NULL, applied bigint NOT NULL, CONSTRAINT {0}_pkey PRIMARY KEY (version)) TABLESPACE pg_default", self.schema_version_table)).execute(&self.pool).await?;
+        for migration in pending.iter() {
+            info!("[PgSQL] Applying migration {} {}", migration.version, migration.name);
+            let mut transaction = self.pool.begin().await?;
+            for statement in migration.statements.iter() {
+                sqlx::query(statement).execute(&mut *transaction).await?;
             }
-            info!("[BOOT] Created the database and tables, restart without the parameter to start the app.");
-            task::sleep(Duration::from_secs(1)).await;
-            exit(0);
+            sqlx::query(&format!("INSERT INTO public.{} (version, name, applied) VALUES ($1, $2, $3)", self.schema_version_table))
+                .bind(migration.version as i64)
+                .bind(migration.name.clone())
+                .bind(chrono::Utc::now().timestamp())
+                .execute(&mut *transaction).await?;
+            self.commit(transaction).await?;
         }
-
-        structure
+        Ok(pending)
     }
+}
 
+#[async_trait]
+impl DatabaseBackend for DatabaseConnectorPgSQL {
     #[tracing::instrument(level = "debug")]
-    pub async fn load_torrents(&self, tracker: Arc<TorrentTracker>) -> Result<(u64, u64), Error>
+    async fn load_torrents(&self, tracker: Arc<TorrentTracker>) -> Result<(u64, u64), Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -290,26 +181,22 @@ impl DatabaseConnectorPgSQL {
             let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
                 true => {
                     format!(
-                        "SELECT encode({}::bytea, 'hex'), {} FROM {} LIMIT {}, {}",
+                        "SELECT encode({}::bytea, 'hex'), {} FROM {} LIMIT $1 OFFSET $2",
                         structure.column_infohash,
                         structure.column_completed,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
                 false => {
                     format!(
-                        "SELECT {}, {} FROM {} LIMIT {}, {}",
+                        "SELECT {}, {} FROM {} LIMIT $1 OFFSET $2",
                         structure.column_infohash,
                         structure.column_completed,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(length as i64).bind(start as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let info_hash_data: &[u8] = result.get(structure.column_infohash.as_str());
                 let info_hash: [u8; 20] = <[u8; 20]>::try_from(hex::decode(info_hash_data).unwrap()[0..20].as_ref()).unwrap();
@@ -320,7 +207,8 @@ impl DatabaseConnectorPgSQL {
                         seeds: BTreeMap::new(),
                         peers: BTreeMap::new(),
                         completed: completed_count as u64,
-                        updated: std::time::Instant::now()
+                        updated: std::time::Instant::now(),
+                        peers_cursor: 0
                     }
                 );
                 torrents += 1;
@@ -338,35 +226,65 @@ impl DatabaseConnectorPgSQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_torrents(&self, tracker: Arc<TorrentTracker>, torrents: BTreeMap<InfoHash, (TorrentEntry, UpdatesAction)>) -> Result<(), Error>
+    async fn save_torrents(&self, tracker: Arc<TorrentTracker>, torrents: BTreeMap<InfoHash, (TorrentEntry, UpdatesAction)>) -> Result<(), Error>
     {
         let mut torrents_transaction = self.pool.begin().await?;
         let mut torrents_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().torrents;
-        for (info_hash, (torrent_entry, updates_action)) in torrents.iter() {
-            torrents_handled_entries += 1;
-            match updates_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                            true => {
-                                format!(
-                                    "DELETE FROM {} WHERE {}=decode('{}', 'hex')",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM {} WHERE {}='{}'",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
+        let database = tracker.config.deref().clone().database;
+        let batch_size = database.batch_size as usize;
+        let (removes, saves): (Vec<_>, Vec<_>) = torrents.iter().partition(|(_, (_, updates_action))| *updates_action == UpdatesAction::Remove);
+        if database.remove_action {
+            let string_format = format!(
+                "DELETE FROM {} WHERE {} = ANY($1::{})",
+                structure.table_name,
+                structure.column_infohash,
+                DatabaseConnectorPgSQL::hash_array_type(structure.bin_type_infohash)
+            );
+            for chunk in removes.chunks(batch_size) {
+                let query = DatabaseConnectorPgSQL::bind_hashes(sqlx::query(string_format.as_str()), chunk.iter().map(|(info_hash, _)| info_hash.0).collect(), structure.bin_type_infohash);
+                match query.execute(&mut *torrents_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[PgSQL] Error: {}", e);
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        torrents_handled_entries += removes.len() as u64;
+        match database.insert_vacant {
+            true => {
+                let mut columns = vec![];
+                if database.update_peers {
+                    columns.push(structure.column_seeds.clone());
+                    columns.push(structure.column_peers.clone());
+                }
+                if database.update_completed {
+                    columns.push(structure.column_completed.clone());
+                }
+                let string_format = format!(
+                    "INSERT INTO {} ({}, {}) SELECT * FROM UNNEST($1::{}, {}) ON CONFLICT ({}) DO UPDATE SET {}",
+                    structure.table_name,
+                    structure.column_infohash,
+                    columns.join(", "),
+                    DatabaseConnectorPgSQL::hash_array_type(structure.bin_type_infohash),
+                    (0..columns.len()).map(|index| format!("${}::bigint[]", index + 2)).collect::<Vec<String>>().join(", "),
+                    structure.column_infohash,
+                    columns.iter().map(|column| format!("{}=excluded.{}", column, column)).collect::<Vec<String>>().join(", ")
+                );
+                for chunk in saves.chunks(batch_size) {
+                    if !columns.is_empty() {
+                        let mut query = DatabaseConnectorPgSQL::bind_hashes(sqlx::query(string_format.as_str()), chunk.iter().map(|(info_hash, _)| info_hash.0).collect(), structure.bin_type_infohash);
+                        if database.update_peers {
+                            query = query
+                                .bind(chunk.iter().map(|(_, (torrent_entry, _))| torrent_entry.seeds.len() as i64).collect::<Vec<i64>>())
+                                .bind(chunk.iter().map(|(_, (torrent_entry, _))| torrent_entry.peers.len() as i64).collect::<Vec<i64>>());
+                        }
+                        if database.update_completed {
+                            query = query.bind(chunk.iter().map(|(_, (torrent_entry, _))| torrent_entry.completed as i64).collect::<Vec<i64>>());
+                        }
+                        match query.execute(&mut *torrents_transaction).await {
                             Ok(_) => {}
                             Err(e) => {
                                 error!("[PgSQL] Error: {}", e);
@@ -374,186 +292,60 @@ impl DatabaseConnectorPgSQL {
                             }
                         }
                     }
+                    torrents_handled_entries += chunk.len() as u64;
+                    info!("[PgSQL] Handled {} torrents", torrents_handled_entries);
                 }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    match tracker.config.deref().clone().database.insert_vacant {
-                        true => {
-                            if tracker.config.deref().clone().database.update_peers {
-                                let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                                    true => {
-                                        format!(
-                                            "INSERT INTO {} ({}, {}, {}) VALUES (decode('{}', 'hex'), {}, {}) ON CONFLICT ({}) DO UPDATE SET {}=excluded.{}, {}=excluded.{}",
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            info_hash,
-                                            torrent_entry.seeds.len(),
-                                            torrent_entry.peers.len(),
-                                            structure.column_infohash,
-                                            structure.column_seeds,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            structure.column_peers
-                                        )
-                                    }
-                                    false => {
-                                        format!(
-                                            "INSERT INTO {} ({}, {}, {}) VALUES ('{}', {}, {}) ON CONFLICT ({}) DO UPDATE SET {}=excluded.{}, {}=excluded.{}",
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            info_hash,
-                                            torrent_entry.seeds.len(),
-                                            torrent_entry.peers.len(),
-                                            structure.column_infohash,
-                                            structure.column_seeds,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            structure.column_peers
-                                        )
-                                    }
-                                };
-                                match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        error!("[PgSQL] Error: {}", e);
-                                        return Err(e);
-                                    }
-                                }
-                            }
-                            if tracker.config.deref().clone().database.update_completed {
-                                let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                                    true => {
-                                        format!(
-                                            "INSERT INTO {} ({}, {}) VALUES (decode('{}', 'hex'), {}) ON CONFLICT ({}) DO UPDATE SET {}=excluded.{}",
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            structure.column_completed,
-                                            info_hash,
-                                            torrent_entry.completed,
-                                            structure.column_infohash,
-                                            structure.column_completed,
-                                            structure.column_completed
-                                        )
-                                    }
-                                    false => {
-                                        format!(
-                                            "INSERT INTO {} ({}, {}) VALUES ('{}', {}) ON CONFLICT ({}) DO UPDATE SET {}=excluded.{}",
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            structure.column_completed,
-                                            info_hash,
-                                            torrent_entry.completed,
-                                            structure.column_infohash,
-                                            structure.column_completed,
-                                            structure.column_completed
-                                        )
-                                    }
-                                };
-                                match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        error!("[PgSQL] Error: {}", e);
-                                        return Err(e);
-                                    }
-                                }
+            }
+            false => {
+                let string_format_peers = format!(
+                    "UPDATE {} SET {}=$1, {}=$2 WHERE {}=$3",
+                    structure.table_name,
+                    structure.column_seeds,
+                    structure.column_peers,
+                    structure.column_infohash
+                );
+                let string_format_completed = format!(
+                    "UPDATE {} SET {}=$1 WHERE {}=$2",
+                    structure.table_name,
+                    structure.column_completed,
+                    structure.column_infohash
+                );
+                for (info_hash, (torrent_entry, _)) in saves.iter() {
+                    torrents_handled_entries += 1;
+                    if database.update_peers {
+                        let query = sqlx::query(string_format_peers.as_str())
+                            .bind(torrent_entry.seeds.len() as i64)
+                            .bind(torrent_entry.peers.len() as i64);
+                        match DatabaseConnectorPgSQL::bind_hash(query, info_hash.0, structure.bin_type_infohash).execute(&mut *torrents_transaction).await {
+                            Ok(_) => {}
+                            Err(e) => {
+                                error!("[PgSQL] Error: {}", e);
+                                return Err(e);
                             }
                         }
-                        false => {
-                            if tracker.config.deref().clone().database.update_peers {
-                                let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                                    true => {
-                                        format!(
-                                            "UPDATE {} SET ({}, {}) = ({}, {}) WHERE {}=decode('{}', 'hex') AND NOT EXISTS (SELECT 1 FROM {} WHERE {}=decode('{}', 'hex'))",
-                                            structure.table_name,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            torrent_entry.seeds.len(),
-                                            torrent_entry.peers.len(),
-                                            structure.column_infohash,
-                                            info_hash,
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            info_hash
-                                        )
-                                    }
-                                    false => {
-                                        format!(
-                                            "UPDATE {} SET ({}, {}) = ({}, {}) WHERE {}='{}' AND NOT EXISTS (SELECT 1 FROM {} WHERE {}='{}')",
-                                            structure.table_name,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            torrent_entry.seeds.len(),
-                                            torrent_entry.peers.len(),
-                                            structure.column_infohash,
-                                            info_hash,
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            info_hash
-                                        )
-                                    }
-                                };
-                                match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        error!("[PgSQL] Error: {}", e);
-                                        return Err(e);
-                                    }
-                                }
-                            }
-                            if tracker.config.deref().clone().database.update_completed {
-                                let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                                    true => {
-                                        format!(
-                                            "UPDATE {} SET {}={} WHERE {}=decode('{}', 'hex') AND EXISTS (SELECT 1 FROM {} WHERE {}=decode('{}', 'hex'))",
-                                            structure.table_name,
-                                            structure.column_completed,
-                                            torrent_entry.completed,
-                                            structure.column_infohash,
-                                            info_hash,
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            info_hash
-                                        )
-                                    }
-                                    false => {
-                                        format!(
-                                            "UPDATE {} SET {}={} WHERE {}='{}' AND EXISTS (SELECT 1 FROM {} WHERE {}='{}')",
-                                            structure.table_name,
-                                            structure.column_completed,
-                                            torrent_entry.completed,
-                                            structure.column_infohash,
-                                            info_hash,
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            info_hash
-                                        )
-                                    }
-                                };
-                                match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        error!("[PgSQL] Error: {}", e);
-                                        return Err(e);
-                                    }
-                                }
+                    }
+                    if database.update_completed {
+                        let query = sqlx::query(string_format_completed.as_str()).bind(torrent_entry.completed as i64);
+                        match DatabaseConnectorPgSQL::bind_hash(query, info_hash.0, structure.bin_type_infohash).execute(&mut *torrents_transaction).await {
+                            Ok(_) => {}
+                            Err(e) => {
+                                error!("[PgSQL] Error: {}", e);
+                                return Err(e);
                             }
                         }
                     }
+                    if (torrents_handled_entries as f64 / 1000f64).fract() == 0.0 {
+                        info!("[PgSQL] Handled {} torrents", torrents_handled_entries);
+                    }
                 }
             }
-            if (torrents_handled_entries as f64 / 1000f64).fract() == 0.0 || torrents.len() as u64 == torrents_handled_entries {
-                info!("[PgSQL] Handled {} torrents", torrents_handled_entries);
-            }
         }
         info!("[PgSQL] Handled {} torrents", torrents_handled_entries);
         self.commit(torrents_transaction).await
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn load_whitelist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
+    async fn load_whitelist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -563,24 +355,20 @@ impl DatabaseConnectorPgSQL {
             let string_format = match tracker.config.deref().clone().database_structure.whitelist.bin_type_infohash {
                 true => {
                     format!(
-                        "SELECT encode({}::bytea, 'hex') FROM {} LIMIT {}, {}",
+                        "SELECT encode({}::bytea, 'hex') FROM {} LIMIT $1 OFFSET $2",
                         structure.column_infohash,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
                 false => {
                     format!(
-                        "SELECT {} FROM {} LIMIT {}, {}",
+                        "SELECT {} FROM {} LIMIT $1 OFFSET $2",
                         structure.column_infohash,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(length as i64).bind(start as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let info_hash_data: &[u8] = result.get(structure.column_infohash.as_str());
                 let info_hash: [u8; 20] = <[u8; 20]>::try_from(hex::decode(info_hash_data).unwrap()[0..20].as_ref()).unwrap();
@@ -598,82 +386,55 @@ impl DatabaseConnectorPgSQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_whitelist(&self, tracker: Arc<TorrentTracker>, whitelists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>
+    async fn save_whitelist(&self, tracker: Arc<TorrentTracker>, whitelists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>
     {
         let mut whitelist_transaction = self.pool.begin().await?;
-        let mut whitelist_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().whitelist;
-        for (info_hash, updates_action) in whitelists.iter() {
-            whitelist_handled_entries += 1;
-            match updates_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.whitelist.bin_type_infohash {
-                            true => {
-                                format!(
-                                    "DELETE FROM {} WHERE {}=decode('{}', 'hex')",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM {} WHERE {}='{}'",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *whitelist_transaction).await {
-                            Ok(_) => {}
-                            Err(e) => {
-                                error!("[PgSQL] Error: {}", e);
-                                return Err(e);
-                            }
-                        }
-                    }
-                }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    let string_format = match tracker.config.deref().clone().database_structure.whitelist.bin_type_infohash {
-                        true => {
-                            format!(
-                                "INSERT INTO {} ({}) VALUES (decode('{}', 'hex')) ON CONFLICT DO NOTHING",
-                                structure.table_name,
-                                structure.column_infohash,
-                                info_hash
-                            )
-                        }
-                        false => {
-                            format!(
-                                "INSERT INTO {} ({}) VALUES ('{}') ON CONFLICT DO NOTHING",
-                                structure.table_name,
-                                structure.column_infohash,
-                                info_hash
-                            )
-                        }
-                    };
-                    match sqlx::query(string_format.as_str()).execute(&mut *whitelist_transaction).await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            error!("[PgSQL] Error: {}", e);
-                            return Err(e);
-                        }
+        let batch_size = tracker.config.database.batch_size as usize;
+        // The last action queued for a hash wins, a statement can't touch the same row twice
+        let whitelists_latest: BTreeMap<InfoHash, UpdatesAction> = whitelists.iter().copied().collect();
+        let (removes, saves): (Vec<_>, Vec<_>) = whitelists_latest.iter().partition(|(_, updates_action)| **updates_action == UpdatesAction::Remove);
+        if tracker.config.deref().clone().database.remove_action {
+            let string_format = format!(
+                "DELETE FROM {} WHERE {} = ANY($1::{})",
+                structure.table_name,
+                structure.column_infohash,
+                DatabaseConnectorPgSQL::hash_array_type(structure.bin_type_infohash)
+            );
+            for chunk in removes.chunks(batch_size) {
+                let query = DatabaseConnectorPgSQL::bind_hashes(sqlx::query(string_format.as_str()), chunk.iter().map(|(info_hash, _)| info_hash.0).collect(), structure.bin_type_infohash);
+                match query.execute(&mut *whitelist_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[PgSQL] Error: {}", e);
+                        return Err(e);
                     }
                 }
             }
-            if (whitelist_handled_entries as f64 / 1000f64).fract() == 0.0 {
-                info!("[PgSQL] Handled {} torrents", whitelist_handled_entries);
+        }
+        let string_format = format!(
+            "INSERT INTO {} ({}) SELECT * FROM UNNEST($1::{}) ON CONFLICT DO NOTHING",
+            structure.table_name,
+            structure.column_infohash,
+            DatabaseConnectorPgSQL::hash_array_type(structure.bin_type_infohash)
+        );
+        for chunk in saves.chunks(batch_size) {
+            let query = DatabaseConnectorPgSQL::bind_hashes(sqlx::query(string_format.as_str()), chunk.iter().map(|(info_hash, _)| info_hash.0).collect(), structure.bin_type_infohash);
+            match query.execute(&mut *whitelist_transaction).await {
+                Ok(_) => {}
+                Err(e) => {
+                    error!("[PgSQL] Error: {}", e);
+                    return Err(e);
+                }
             }
         }
-        info!("[PgSQL] Handled {} whitelisted torrents", whitelist_handled_entries);
-        let _ = self.commit(whitelist_transaction).await;
-        Ok(whitelist_handled_entries)
+        info!("[PgSQL] Handled {} whitelisted torrents", whitelists.len());
+        self.commit(whitelist_transaction).await?;
+        Ok(whitelists.len() as u64)
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn load_blacklist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
+    async fn load_blacklist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -683,24 +444,20 @@ impl DatabaseConnectorPgSQL {
             let string_format = match tracker.config.deref().clone().database_structure.blacklist.bin_type_infohash {
                 true => {
                     format!(
-                        "SELECT encode({}::bytea, 'hex') FROM {} LIMIT {}, {}",
+                        "SELECT encode({}::bytea, 'hex') FROM {} LIMIT $1 OFFSET $2",
                         structure.column_infohash,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
                 false => {
                     format!(
-                        "SELECT {} FROM {} LIMIT {}, {}",
+                        "SELECT {} FROM {} LIMIT $1 OFFSET $2",
                         structure.column_infohash,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(length as i64).bind(start as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let info_hash_data: &[u8] = result.get(structure.column_infohash.as_str());
                 let info_hash: [u8; 20] = <[u8; 20]>::try_from(hex::decode(info_hash_data).unwrap()[0..20].as_ref()).unwrap();
@@ -718,82 +475,55 @@ impl DatabaseConnectorPgSQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_blacklist(&self, tracker: Arc<TorrentTracker>, blacklists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>
+    async fn save_blacklist(&self, tracker: Arc<TorrentTracker>, blacklists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>
     {
         let mut blacklist_transaction = self.pool.begin().await?;
-        let mut blacklist_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().blacklist;
-        for (info_hash, updates_action) in blacklists.iter() {
-            blacklist_handled_entries += 1;
-            match updates_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.blacklist.bin_type_infohash {
-                            true => {
-                                format!(
-                                    "DELETE FROM {} WHERE {}=decode('{}', 'hex')",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM {} WHERE {}='{}'",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *blacklist_transaction).await {
-                            Ok(_) => {}
-                            Err(e) => {
-                                error!("[PgSQL] Error: {}", e);
-                                return Err(e);
-                            }
-                        }
-                    }
-                }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    let string_format = match tracker.config.deref().clone().database_structure.blacklist.bin_type_infohash {
-                        true => {
-                            format!(
-                                "INSERT INTO {} ({}) VALUES (decode('{}', 'hex')) ON CONFLICT DO NOTHING",
-                                structure.table_name,
-                                structure.column_infohash,
-                                info_hash
-                            )
-                        }
-                        false => {
-                            format!(
-                                "INSERT INTO {} ({}) VALUES ('{}') ON CONFLICT DO NOTHING",
-                                structure.table_name,
-                                structure.column_infohash,
-                                info_hash
-                            )
-                        }
-                    };
-                    match sqlx::query(string_format.as_str()).execute(&mut *blacklist_transaction).await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            error!("[PgSQL] Error: {}", e);
-                            return Err(e);
-                        }
+        let batch_size = tracker.config.database.batch_size as usize;
+        // The last action queued for a hash wins, a statement can't touch the same row twice
+        let blacklists_latest: BTreeMap<InfoHash, UpdatesAction> = blacklists.iter().copied().collect();
+        let (removes, saves): (Vec<_>, Vec<_>) = blacklists_latest.iter().partition(|(_, updates_action)| **updates_action == UpdatesAction::Remove);
+        if tracker.config.deref().clone().database.remove_action {
+            let string_format = format!(
+                "DELETE FROM {} WHERE {} = ANY($1::{})",
+                structure.table_name,
+                structure.column_infohash,
+                DatabaseConnectorPgSQL::hash_array_type(structure.bin_type_infohash)
+            );
+            for chunk in removes.chunks(batch_size) {
+                let query = DatabaseConnectorPgSQL::bind_hashes(sqlx::query(string_format.as_str()), chunk.iter().map(|(info_hash, _)| info_hash.0).collect(), structure.bin_type_infohash);
+                match query.execute(&mut *blacklist_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[PgSQL] Error: {}", e);
+                        return Err(e);
                     }
                 }
             }
-            if (blacklist_handled_entries as f64 / 1000f64).fract() == 0.0 {
-                info!("[PgSQL] Handled {} blacklisted torrents", blacklist_handled_entries);
+        }
+        let string_format = format!(
+            "INSERT INTO {} ({}) SELECT * FROM UNNEST($1::{}) ON CONFLICT DO NOTHING",
+            structure.table_name,
+            structure.column_infohash,
+            DatabaseConnectorPgSQL::hash_array_type(structure.bin_type_infohash)
+        );
+        for chunk in saves.chunks(batch_size) {
+            let query = DatabaseConnectorPgSQL::bind_hashes(sqlx::query(string_format.as_str()), chunk.iter().map(|(info_hash, _)| info_hash.0).collect(), structure.bin_type_infohash);
+            match query.execute(&mut *blacklist_transaction).await {
+                Ok(_) => {}
+                Err(e) => {
+                    error!("[PgSQL] Error: {}", e);
+                    return Err(e);
+                }
             }
         }
-        info!("[PgSQL] Handled {} blacklisted torrents", blacklist_handled_entries);
-        let _ = self.commit(blacklist_transaction).await;
-        Ok(blacklist_handled_entries)
+        info!("[PgSQL] Handled {} blacklisted torrents", blacklists.len());
+        self.commit(blacklist_transaction).await?;
+        Ok(blacklists.len() as u64)
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn load_keys(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
+    async fn load_keys(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -803,26 +533,22 @@ impl DatabaseConnectorPgSQL {
             let string_format = match tracker.config.deref().clone().database_structure.keys.bin_type_hash {
                 true => {
                     format!(
-                        "SELECT encode({}::bytea, 'hex'), {} FROM {} LIMIT {}, {}",
+                        "SELECT encode({}::bytea, 'hex'), {} FROM {} LIMIT $1 OFFSET $2",
                         structure.column_hash,
                         structure.column_timeout,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
                 false => {
                     format!(
-                        "SELECT {}, {} FROM {} LIMIT {}, {}",
+                        "SELECT {}, {} FROM {} LIMIT $1 OFFSET $2",
                         structure.column_hash,
                         structure.column_timeout,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(length as i64).bind(start as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let hash_data: &[u8] = result.get(structure.column_hash.as_str());
                 let hash: [u8; 20] = <[u8; 20]>::try_from(hex::decode(hash_data).unwrap()[0..20].as_ref()).unwrap();
@@ -841,92 +567,62 @@ impl DatabaseConnectorPgSQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_keys(&self, tracker: Arc<TorrentTracker>, keys: BTreeMap<InfoHash, (i64, UpdatesAction)>) -> Result<u64, Error>
+    async fn save_keys(&self, tracker: Arc<TorrentTracker>, keys: BTreeMap<InfoHash, (i64, UpdatesAction)>) -> Result<u64, Error>
     {
         let mut keys_transaction = self.pool.begin().await?;
         let mut keys_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().keys;
-        for (hash, (timeout, update_action)) in keys.iter() {
-            keys_handled_entries += 1;
-            match update_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.keys.bin_type_hash {
-                            true => {
-                                format!(
-                                    "DELETE FROM {} WHERE {}=decode('{}', 'hex')",
-                                    structure.table_name,
-                                    structure.column_hash,
-                                    hash
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM {} WHERE {}='{}'",
-                                    structure.table_name,
-                                    structure.column_hash,
-                                    hash
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *keys_transaction).await {
-                            Ok(_) => {}
-                            Err(e) => {
-                                error!("[PgSQL] Error: {}", e);
-                                return Err(e);
-                            }
-                        }
-                    }
-                }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    let string_format = match tracker.config.deref().clone().database_structure.keys.bin_type_hash {
-                        true => {
-                            format!(
-                                "INSERT INTO {} ({}, {}) VALUES (decode('{}', 'hex'), {}) ON CONFLICT ({}) DO UPDATE SET {}=excluded.{}",
-                                structure.table_name,
-                                structure.column_hash,
-                                structure.column_timeout,
-                                hash,
-                                timeout,
-                                structure.column_hash,
-                                structure.column_timeout,
-                                structure.column_timeout
-                            )
-                        }
-                        false => {
-                            format!(
-                                "INSERT INTO {} ({}, {}) VALUES ('{}', {}) ON CONFLICT ({}) DO UPDATE SET {}=excluded.{}",
-                                structure.table_name,
-                                structure.column_hash,
-                                structure.column_timeout,
-                                hash,
-                                timeout,
-                                structure.column_hash,
-                                structure.column_timeout,
-                                structure.column_timeout
-                            )
-                        }
-                    };
-                    match sqlx::query(string_format.as_str()).execute(&mut *keys_transaction).await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            error!("[PgSQL] Error: {}", e);
-                            return Err(e);
-                        }
+        let batch_size = tracker.config.database.batch_size as usize;
+        let (removes, saves): (Vec<_>, Vec<_>) = keys.iter().partition(|(_, (_, updates_action))| *updates_action == UpdatesAction::Remove);
+        if tracker.config.deref().clone().database.remove_action {
+            let string_format = format!(
+                "DELETE FROM {} WHERE {} = ANY($1::{})",
+                structure.table_name,
+                structure.column_hash,
+                DatabaseConnectorPgSQL::hash_array_type(structure.bin_type_hash)
+            );
+            for chunk in removes.chunks(batch_size) {
+                let query = DatabaseConnectorPgSQL::bind_hashes(sqlx::query(string_format.as_str()), chunk.iter().map(|(hash, _)| hash.0).collect(), structure.bin_type_hash);
+                match query.execute(&mut *keys_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[PgSQL] Error: {}", e);
+                        return Err(e);
                     }
                 }
             }
-            if (keys_handled_entries as f64 / 1000f64).fract() == 0.0 {
-                info!("[PgSQL] Handled {} keys", keys_handled_entries);
+        }
+        keys_handled_entries += removes.len() as u64;
+        let string_format = format!(
+            "INSERT INTO {} ({}, {}) SELECT * FROM UNNEST($1::{}, $2::bigint[]) ON CONFLICT ({}) DO UPDATE SET {}=excluded.{}",
+            structure.table_name,
+            structure.column_hash,
+            structure.column_timeout,
+            DatabaseConnectorPgSQL::hash_array_type(structure.bin_type_hash),
+            structure.column_hash,
+            structure.column_timeout,
+            structure.column_timeout
+        );
+        for chunk in saves.chunks(batch_size) {
+            let query = DatabaseConnectorPgSQL::bind_hashes(sqlx::query(string_format.as_str()), chunk.iter().map(|(hash, _)| hash.0).collect(), structure.bin_type_hash)
+                .bind(chunk.iter().map(|(_, (timeout, _))| *timeout).collect::<Vec<i64>>());
+            match query.execute(&mut *keys_transaction).await {
+                Ok(_) => {}
+                Err(e) => {
+                    error!("[PgSQL] Error: {}", e);
+                    return Err(e);
+                }
             }
+            keys_handled_entries += chunk.len() as u64;
+            info!("[PgSQL] Handled {} keys", keys_handled_entries);
         }
         info!("[PgSQL] Handled {} keys", keys_handled_entries);
-        let _ = self.commit(keys_transaction).await;
+        self.commit(keys_transaction).await?;
         Ok(keys_handled_entries)
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn load_users(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
+    async fn load_users(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -938,7 +634,7 @@ impl DatabaseConnectorPgSQL {
                     match tracker.config.deref().clone().database_structure.users.bin_type_key {
                         true => {
                             format!(
-                                "SELECT {}, encode({}::bytea, 'hex'), {}, {}, {}, {}, {} FROM {} LIMIT {}, {}",
+                                "SELECT {}, encode({}::bytea, 'hex'), {}, {}, {}, {}, {} FROM {} LIMIT $1 OFFSET $2",
                                 structure.column_uuid,
                                 structure.column_key,
                                 structure.column_uploaded,
@@ -946,14 +642,12 @@ impl DatabaseConnectorPgSQL {
                                 structure.column_completed,
                                 structure.column_updated,
                                 structure.column_active,
-                                structure.table_name,
-                                start,
-                                length
+                                structure.table_name
                             )
                         }
                         false => {
                             format!(
-                                "SELECT {}, {}, {}, {}, {}, {}, {} FROM {} LIMIT {}, {}",
+                                "SELECT {}, {}, {}, {}, {}, {}, {} FROM {} LIMIT $1 OFFSET $2",
                                 structure.column_uuid,
                                 structure.column_key,
                                 structure.column_uploaded,
@@ -961,9 +655,7 @@ impl DatabaseConnectorPgSQL {
                                 structure.column_completed,
                                 structure.column_updated,
                                 structure.column_active,
-                                structure.table_name,
-                                start,
-                                length
+                                structure.table_name
                             )
                         }
                     }
@@ -972,7 +664,7 @@ impl DatabaseConnectorPgSQL {
                     match tracker.config.deref().clone().database_structure.users.bin_type_key {
                         true => {
                             format!(
-                                "SELECT {}, encode({}::bytea, 'hex'), {}, {}, {}, {}, {} FROM {} LIMIT {}, {}",
+                                "SELECT {}, encode({}::bytea, 'hex'), {}, {}, {}, {}, {} FROM {} LIMIT $1 OFFSET $2",
                                 structure.column_id,
                                 structure.column_key,
                                 structure.column_uploaded,
@@ -980,14 +672,12 @@ impl DatabaseConnectorPgSQL {
                                 structure.column_completed,
                                 structure.column_updated,
                                 structure.column_active,
-                                structure.table_name,
-                                start,
-                                length
+                                structure.table_name
                             )
                         }
                         false => {
                             format!(
-                                "SELECT {}, {}, {}, {}, {}, {}, {} FROM {} LIMIT {}, {}",
+                                "SELECT {}, {}, {}, {}, {}, {}, {} FROM {} LIMIT $1 OFFSET $2",
                                 structure.column_id,
                                 structure.column_key,
                                 structure.column_uploaded,
@@ -995,30 +685,26 @@ impl DatabaseConnectorPgSQL {
                                 structure.column_completed,
                                 structure.column_updated,
                                 structure.column_active,
-                                structure.table_name,
-                                start,
-                                length
+                                structure.table_name
                             )
                         }
                     }
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(length as i64).bind(start as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let hash = match tracker.config.deref().clone().database_structure.users.id_uuid {
                     true => {
                         let uuid_data: &[u8] = result.get(structure.column_uuid.as_str());
                         let mut hasher = Sha1::new();
                         hasher.update(uuid_data);
-                        let hashed = <[u8; 20]>::try_from(hasher.finalize().as_slice()).unwrap();
-                        hashed
+                        <[u8; 20]>::try_from(hasher.finalize().as_slice()).unwrap()
                     }
                     false => {
                         let id_data: &[u8] = result.get(structure.column_id.as_str());
                         let mut hasher = Sha1::new();
                         hasher.update(id_data);
-                        let hashed = <[u8; 20]>::try_from(hasher.finalize().as_slice()).unwrap();
-                        hashed
+                        <[u8; 20]>::try_from(hasher.finalize().as_slice()).unwrap()
                     }
                 };
                 tracker.add_user(UserId(hash), UserEntryItem {
@@ -1051,305 +737,115 @@ impl DatabaseConnectorPgSQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_users(&self, tracker: Arc<TorrentTracker>, users: BTreeMap<UserId, (UserEntryItem, UpdatesAction)>) -> Result<(), Error>
+    async fn save_users(&self, tracker: Arc<TorrentTracker>, users: BTreeMap<UserId, (UserEntryItem, UpdatesAction)>) -> Result<(), Error>
     {
         let mut users_transaction = self.pool.begin().await?;
         let mut users_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().users;
-        for (_, (user_entry_item, updates_action)) in users.iter() {
-            users_handled_entries += 1;
-            match updates_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.users.id_uuid {
-                            true => {
-                                format!(
-                                    "DELETE FROM {} WHERE {}='{}'",
-                                    structure.table_name,
-                                    structure.column_uuid,
-                                    user_entry_item.user_uuid.clone().unwrap()
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM {} WHERE {}='{}'",
-                                    structure.table_name,
-                                    structure.column_id,
-                                    user_entry_item.user_id.unwrap()
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *users_transaction).await {
-                            Ok(_) => {}
-                            Err(e) => {
-                                error!("[PgSQL] Error: {}", e);
-                                return Err(e);
-                            }
-                        }
+        let batch_size = tracker.config.database.batch_size as usize;
+        let column_identity = match structure.id_uuid {
+            true => { structure.column_uuid.clone() }
+            false => { structure.column_id.clone() }
+        };
+        let (removes, saves): (Vec<_>, Vec<_>) = users.values().partition(|(_, updates_action)| *updates_action == UpdatesAction::Remove);
+        if tracker.config.deref().clone().database.remove_action {
+            let string_format = format!(
+                "DELETE FROM {} WHERE {} = ANY($1::{})",
+                structure.table_name,
+                column_identity,
+                DatabaseConnectorPgSQL::user_array_type(structure.id_uuid)
+            );
+            for chunk in removes.chunks(batch_size) {
+                let query = DatabaseConnectorPgSQL::bind_users(sqlx::query(string_format.as_str()), chunk.iter().map(|(user_entry_item, _)| user_entry_item).collect(), structure.id_uuid);
+                match query.execute(&mut *users_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[PgSQL] Error: {}", e);
+                        return Err(e);
                     }
                 }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    let string_format = match  tracker.config.deref().clone().database.insert_vacant {
-                        true => {
-                            match tracker.config.deref().clone().database_structure.users.id_uuid {
-                                true => {
-                                    match tracker.config.deref().clone().database_structure.users.bin_type_key {
-                                        true => {
-                                            format!(
-                                                "INSERT INTO {} ({}, {}, {}, {}, {}, {}, {}) VALUES ('{}', {}, {}, {}, decode('{}', 'hex'), {}, {}) ON CONFLICT ({}) DO UPDATE SET {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}",
-                                                structure.table_name,
-                                                structure.column_uuid,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                                user_entry_item.completed,
-                                                user_entry_item.active,
-                                                user_entry_item.downloaded,
-                                                user_entry_item.key,
-                                                user_entry_item.uploaded,
-                                                user_entry_item.updated,
-                                                structure.column_uuid,
-                                                structure.column_completed,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                structure.column_updated
-                                            )
-                                        }
-                                        false => {
-                                            format!(
-                                                "INSERT INTO {} ({}, {}, {}, {}, {}, {}, {}) VALUES ('{}', {}, {}, {}, '{}', {}, {}) ON CONFLICT ({}) DO UPDATE SET {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}",
-                                                structure.table_name,
-                                                structure.column_uuid,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                                user_entry_item.completed,
-                                                user_entry_item.active,
-                                                user_entry_item.downloaded,
-                                                user_entry_item.key,
-                                                user_entry_item.uploaded,
-                                                user_entry_item.updated,
-                                                structure.column_uuid,
-                                                structure.column_completed,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                structure.column_updated
-                                            )
-                                        }
-                                    }
-                                }
-                                false => {
-                                    match tracker.config.deref().clone().database_structure.users.bin_type_key {
-                                        true => {
-                                            format!(
-                                                "INSERT INTO {} ({}, {}, {}, {}, {}, {}, {}) VALUES ('{}', {}, {}, {}, decode('{}', 'hex'), {}, {}) ON CONFLICT ({}) DO UPDATE SET {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}",
-                                                structure.table_name,
-                                                structure.column_id,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.user_id.unwrap(),
-                                                user_entry_item.completed,
-                                                user_entry_item.active,
-                                                user_entry_item.downloaded,
-                                                user_entry_item.key,
-                                                user_entry_item.uploaded,
-                                                user_entry_item.updated,
-                                                structure.column_id,
-                                                structure.column_completed,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                structure.column_updated
-                                            )
-                                        }
-                                        false => {
-                                            format!(
-                                                "INSERT INTO {} ({}, {}, {}, {}, {}, {}, {}) VALUES ('{}', {}, {}, {}, '{}', {}, {}) ON CONFLICT ({}) DO UPDATE SET {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}",
-                                                structure.table_name,
-                                                structure.column_id,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.user_id.unwrap(),
-                                                user_entry_item.completed,
-                                                user_entry_item.active,
-                                                user_entry_item.downloaded,
-                                                user_entry_item.key,
-                                                user_entry_item.uploaded,
-                                                user_entry_item.updated,
-                                                structure.column_id,
-                                                structure.column_completed,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                structure.column_updated
-                                            )
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        false => {
-                            match tracker.config.deref().clone().database_structure.users.id_uuid {
-                                true => {
-                                    match tracker.config.deref().clone().database_structure.users.bin_type_key {
-                                        true => {
-                                            format!(
-                                                "UPDATE {} SET {}={}, {}={}, {}={}, {}=decode('{}', 'hex'), {}={}, {}={} WHERE {}='{}' AND EXISTS (SELECT 1 FROM {} WHERE {}='{}')",
-                                                structure.table_name,
-                                                structure.column_completed,
-                                                user_entry_item.completed,
-                                                structure.column_active,
-                                                user_entry_item.active,
-                                                structure.column_downloaded,
-                                                user_entry_item.downloaded,
-                                                structure.column_key,
-                                                user_entry_item.key,
-                                                structure.column_uploaded,
-                                                user_entry_item.uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.updated,
-                                                structure.column_uuid,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                                structure.table_name,
-                                                structure.column_uuid,
-                                                user_entry_item.user_uuid.clone().unwrap()
-                                            )
-                                        }
-                                        false => {
-                                            format!(
-                                                "UPDATE {} SET {}={}, {}={}, {}={}, {}='{}', {}={}, {}={} WHERE {}='{}' AND EXISTS (SELECT 1 FROM {} WHERE {}='{}')",
-                                                structure.table_name,
-                                                structure.column_completed,
-                                                user_entry_item.completed,
-                                                structure.column_active,
-                                                user_entry_item.active,
-                                                structure.column_downloaded,
-                                                user_entry_item.downloaded,
-                                                structure.column_key,
-                                                user_entry_item.key,
-                                                structure.column_uploaded,
-                                                user_entry_item.uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.updated,
-                                                structure.column_uuid,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                                structure.table_name,
-                                                structure.column_uuid,
-                                                user_entry_item.user_uuid.clone().unwrap()
-                                            )
-                                        }
-                                    }
-                                }
-                                false => {
-                                    match tracker.config.deref().clone().database_structure.users.bin_type_key {
-                                        true => {
-                                            format!(
-                                                "UPDATE {} SET {}={}, {}={}, {}={}, {}=decode('{}', 'hex'), {}={}, {}={} WHERE {}='{}' AND EXISTS (SELECT 1 FROM {} WHERE {}='{}')",
-                                                structure.table_name,
-                                                structure.column_completed,
-                                                user_entry_item.completed,
-                                                structure.column_active,
-                                                user_entry_item.active,
-                                                structure.column_downloaded,
-                                                user_entry_item.downloaded,
-                                                structure.column_key,
-                                                user_entry_item.key,
-                                                structure.column_uploaded,
-                                                user_entry_item.uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.updated,
-                                                structure.column_id,
-                                                user_entry_item.user_id.unwrap(),
-                                                structure.table_name,
-                                                structure.column_id,
-                                                user_entry_item.user_id.unwrap()
-                                            )
-                                        }
-                                        false => {
-                                            format!(
-                                                "UPDATE {} SET {}={}, {}={}, {}={}, {}='{}', {}={}, {}={} WHERE {}='{}' AND EXISTS (SELECT 1 FROM {} WHERE {}='{}')",
-                                                structure.table_name,
-                                                structure.column_completed,
-                                                user_entry_item.completed,
-                                                structure.column_active,
-                                                user_entry_item.active,
-                                                structure.column_downloaded,
-                                                user_entry_item.downloaded,
-                                                structure.column_key,
-                                                user_entry_item.key,
-                                                structure.column_uploaded,
-                                                user_entry_item.uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.updated,
-                                                structure.column_id,
-                                                user_entry_item.user_id.unwrap(),
-                                                structure.table_name,
-                                                structure.column_id,
-                                                user_entry_item.user_id.unwrap()
-                                            )
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    };
-                    match sqlx::query(string_format.as_str()).execute(&mut *users_transaction).await {
+            }
+        }
+        users_handled_entries += removes.len() as u64;
+        match tracker.config.deref().clone().database.insert_vacant {
+            true => {
+                let string_format = format!(
+                    "INSERT INTO {} ({}, {}, {}, {}, {}, {}, {}) SELECT * FROM UNNEST($1::{}, $2::bigint[], $3::bigint[], $4::bigint[], $5::{}, $6::bigint[], $7::bigint[]) ON CONFLICT ({}) DO UPDATE SET {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}, {}=excluded.{}",
+                    structure.table_name,
+                    column_identity,
+                    structure.column_completed,
+                    structure.column_active,
+                    structure.column_downloaded,
+                    structure.column_key,
+                    structure.column_uploaded,
+                    structure.column_updated,
+                    DatabaseConnectorPgSQL::user_array_type(structure.id_uuid),
+                    DatabaseConnectorPgSQL::hash_array_type(structure.bin_type_key),
+                    column_identity,
+                    structure.column_completed,
+                    structure.column_completed,
+                    structure.column_active,
+                    structure.column_active,
+                    structure.column_downloaded,
+                    structure.column_downloaded,
+                    structure.column_key,
+                    structure.column_key,
+                    structure.column_uploaded,
+                    structure.column_uploaded,
+                    structure.column_updated,
+                    structure.column_updated
+                );
+                for chunk in saves.chunks(batch_size) {
+                    let query = DatabaseConnectorPgSQL::bind_users(sqlx::query(string_format.as_str()), chunk.iter().map(|(user_entry_item, _)| user_entry_item).collect(), structure.id_uuid)
+                        .bind(chunk.iter().map(|(user_entry_item, _)| user_entry_item.completed as i64).collect::<Vec<i64>>())
+                        .bind(chunk.iter().map(|(user_entry_item, _)| user_entry_item.active as i64).collect::<Vec<i64>>())
+                        .bind(chunk.iter().map(|(user_entry_item, _)| user_entry_item.downloaded as i64).collect::<Vec<i64>>());
+                    let query = DatabaseConnectorPgSQL::bind_hashes(query, chunk.iter().map(|(user_entry_item, _)| user_entry_item.key.0).collect(), structure.bin_type_key)
+                        .bind(chunk.iter().map(|(user_entry_item, _)| user_entry_item.uploaded as i64).collect::<Vec<i64>>())
+                        .bind(chunk.iter().map(|(user_entry_item, _)| user_entry_item.updated as i64).collect::<Vec<i64>>());
+                    match query.execute(&mut *users_transaction).await {
                         Ok(_) => {}
                         Err(e) => {
                             error!("[PgSQL] Error: {}", e);
                             return Err(e);
                         }
                     }
+                    users_handled_entries += chunk.len() as u64;
+                    info!("[PgSQL] Handled {} users", users_handled_entries);
                 }
             }
-            if (users_handled_entries as f64 / 1000f64).fract() == 0.0 || users.len() as u64 == users_handled_entries {
-                info!("[PgSQL] Handled {} users", users_handled_entries);
+            false => {
+                let string_format = format!(
+                    "UPDATE {} SET {}=$1, {}=$2, {}=$3, {}=$4, {}=$5, {}=$6 WHERE {}=$7",
+                    structure.table_name,
+                    structure.column_completed,
+                    structure.column_active,
+                    structure.column_downloaded,
+                    structure.column_key,
+                    structure.column_uploaded,
+                    structure.column_updated,
+                    column_identity
+                );
+                for (user_entry_item, _) in saves.iter() {
+                    users_handled_entries += 1;
+                    let query = sqlx::query(string_format.as_str())
+                        .bind(user_entry_item.completed as i64)
+                        .bind(user_entry_item.active as i64)
+                        .bind(user_entry_item.downloaded as i64);
+                    let query = DatabaseConnectorPgSQL::bind_hash(query, user_entry_item.key.0, structure.bin_type_key)
+                        .bind(user_entry_item.uploaded as i64)
+                        .bind(user_entry_item.updated as i64);
+                    match DatabaseConnectorPgSQL::bind_user(query, user_entry_item, structure.id_uuid).execute(&mut *users_transaction).await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            error!("[PgSQL] Error: {}", e);
+                            return Err(e);
+                        }
+                    }
+                    if (users_handled_entries as f64 / 1000f64).fract() == 0.0 {
+                        info!("[PgSQL] Handled {} users", users_handled_entries);
+                    }
+                }
             }
         }
         info!("[PgSQL] Handled {} users", users_handled_entries);
@@ -1357,7 +853,7 @@ impl DatabaseConnectorPgSQL {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn reset_seeds_peers(&self, tracker: Arc<TorrentTracker>) -> Result<(), Error>
+    async fn reset_seeds_peers(&self, tracker: Arc<TorrentTracker>) -> Result<(), Error>
     {
         let mut reset_seeds_peers_transaction = self.pool.begin().await?;
         let structure = tracker.config.deref().clone().database_structure.clone().torrents;
@@ -1374,10 +870,78 @@ impl DatabaseConnectorPgSQL {
                 return Err(e);
             }
         }
-        let _ = self.commit(reset_seeds_peers_transaction).await;
+        self.commit(reset_seeds_peers_transaction).await?;
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug")]
+    async fn schema_version(&self) -> Result<u64, Error>
+    {
+        self.get_schema_version().await
+    }
+
+    #[tracing::instrument(level = "debug")]
+    async fn migrate(&self, tracker: Arc<TorrentTracker>, dry_run: bool) -> Result<Vec<DatabaseMigration>, Error>
+    {
+        self.apply_migrations(DatabaseConnectorPgSQL::migrations(&tracker.config), dry_run).await
+    }
+}
+
+impl DatabaseConnectorPgSQL {
+    /// Binds a 20 bytes hash as bytea, or as its hex string when the column is not binary.
+    pub fn bind_hash<'q>(query: Query<'q, Postgres, PgArguments>, hash: [u8; 20], bin_type: bool) -> Query<'q, Postgres, PgArguments>
+    {
+        match bin_type {
+            true => { query.bind(hash.to_vec()) }
+            false => { query.bind(hex::encode(hash)) }
+        }
+    }
+
+    /// Binds the identity of a user, its UUID or its numeric ID depending on the configured structure.
+    pub fn bind_user<'q>(query: Query<'q, Postgres, PgArguments>, user_entry_item: &UserEntryItem, id_uuid: bool) -> Query<'q, Postgres, PgArguments>
+    {
+        match id_uuid {
+            true => { query.bind(user_entry_item.user_uuid.clone().unwrap()) }
+            false => { query.bind(user_entry_item.user_id.unwrap() as i64) }
+        }
+    }
+
+    /// The array type a batch of hashes is cast to, matching how `bind_hashes` binds it.
+    pub fn hash_array_type(bin_type: bool) -> &'static str
+    {
+        match bin_type {
+            true => { "bytea[]" }
+            false => { "text[]" }
+        }
+    }
+
+    /// Binds a batch of 20 bytes hashes as a single array, for `UNNEST` and `ANY`.
+    pub fn bind_hashes<'q>(query: Query<'q, Postgres, PgArguments>, hashes: Vec<[u8; 20]>, bin_type: bool) -> Query<'q, Postgres, PgArguments>
+    {
+        match bin_type {
+            true => { query.bind(hashes.iter().map(|hash| hash.to_vec()).collect::<Vec<Vec<u8>>>()) }
+            false => { query.bind(hashes.iter().map(hex::encode).collect::<Vec<String>>()) }
+        }
+    }
+
+    /// The array type a batch of user identities is cast to, matching how `bind_users` binds it.
+    pub fn user_array_type(id_uuid: bool) -> &'static str
+    {
+        match id_uuid {
+            true => { "text[]" }
+            false => { "bigint[]" }
+        }
+    }
+
+    /// Binds the identities of a batch of users as a single array, for `UNNEST` and `ANY`.
+    pub fn bind_users<'q>(query: Query<'q, Postgres, PgArguments>, user_entry_items: Vec<&UserEntryItem>, id_uuid: bool) -> Query<'q, Postgres, PgArguments>
+    {
+        match id_uuid {
+            true => { query.bind(user_entry_items.iter().map(|user_entry_item| user_entry_item.user_uuid.clone().unwrap()).collect::<Vec<String>>()) }
+            false => { query.bind(user_entry_items.iter().map(|user_entry_item| user_entry_item.user_id.unwrap() as i64).collect::<Vec<i64>>()) }
+        }
+    }
+
     #[tracing::instrument(level = "debug")]
     pub async fn commit(&self, transaction: Transaction<'_, Postgres>) -> Result<(), Error>
     {
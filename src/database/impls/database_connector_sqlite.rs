@@ -5,15 +5,18 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use async_std::task;
+use async_trait::async_trait;
 use futures_util::TryStreamExt;
 use log::{error, info};
 use sha1::{Digest, Sha1};
-use sqlx::{ConnectOptions, Error, Sqlite, Pool, Row, Transaction};
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{ConnectOptions, Error, QueryBuilder, Sqlite, Pool, Row, Transaction};
+use sqlx::sqlite::{SqliteArguments, SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::query::Query;
+use sqlx::query_builder::Separated;
 use crate::config::structs::configuration::Configuration;
-use crate::database::enums::database_drivers::DatabaseDrivers;
-use crate::database::structs::database_connector::DatabaseConnector;
+use crate::database::structs::database_migration::DatabaseMigration;
 use crate::database::structs::database_connector_sqlite::DatabaseConnectorSQLite;
+use crate::database::traits::DatabaseBackend;
 use crate::stats::enums::stats_event::StatsEvent;
 use crate::tracker::enums::updates_action::UpdatesAction;
 use crate::tracker::structs::info_hash::InfoHash;
@@ -33,7 +36,7 @@ impl DatabaseConnectorSQLite {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn database_connector(config: Arc<Configuration>, create_database: bool) -> DatabaseConnector
+    pub async fn database_connector(config: Arc<Configuration>, create_database: bool) -> Box<dyn DatabaseBackend>
     {
         let sqlite_connect = DatabaseConnectorSQLite::create(config.database.clone().path.as_str()).await;
         if sqlite_connect.is_err() {
@@ -42,12 +45,10 @@ impl DatabaseConnectorSQLite {
             exit(1);
         }
 
-        let mut structure = DatabaseConnector { mysql: None, sqlite: None, pgsql: None, engine: None };
-        structure.sqlite = Some(DatabaseConnectorSQLite { pool: sqlite_connect.unwrap() });
-        structure.engine = Some(DatabaseDrivers::sqlite3);
+        let structure = DatabaseConnectorSQLite { pool: sqlite_connect.unwrap(), schema_version_table: config.database_structure.schema_version.table_name.clone() };
 
         if create_database {
-            let pool = &structure.sqlite.clone().unwrap().pool;
+            let pool = &structure.pool;
             info!("[BOOT] Database creation triggered for SQLite.");
 
             info!("[BOOT SQLite] Setting the PRAGMA config...");
@@ -56,224 +57,120 @@ impl DatabaseConnectorSQLite {
             let _ = sqlx::query("PRAGMA page_size = 32768;").execute(pool).await;
             let _ = sqlx::query("PRAGMA synchronous = full;").execute(pool).await;
 
-            // Create Torrent DB
-            info!("[BOOT SQLite] Creating table {}", config.database_structure.clone().torrents.table_name);
-            match config.database_structure.clone().torrents.bin_type_infohash {
-                true => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS `{}` (`{}` BLOB PRIMARY KEY NOT NULL, `{}` INTEGER DEFAULT 0, `{}` INTEGER DEFAULT 0, `{}` INTEGER DEFAULT 0)",
-                            config.database_structure.clone().torrents.table_name,
-                            config.database_structure.clone().torrents.column_infohash,
-                            config.database_structure.clone().torrents.column_seeds,
-                            config.database_structure.clone().torrents.column_peers,
-                            config.database_structure.clone().torrents.column_completed
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[SQLite] Error: {}", error); }
-                    }
-                }
-                false => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS `{}` (`{}` TEXT PRIMARY KEY NOT NULL, `{}` INTEGER DEFAULT 0, `{}` INTEGER DEFAULT 0, `{}` INTEGER DEFAULT 0)",
-                            config.database_structure.clone().torrents.table_name,
-                            config.database_structure.clone().torrents.column_infohash,
-                            config.database_structure.clone().torrents.column_seeds,
-                            config.database_structure.clone().torrents.column_peers,
-                            config.database_structure.clone().torrents.column_completed
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[SQLite] Error: {}", error); }
-                    }
-                }
+            if let Err(error) = structure.apply_migrations(DatabaseConnectorSQLite::migrations(&config), false).await {
+                panic!("[SQLite] Error: {}", error);
             }
+            info!("[BOOT] Created the database and tables, restart without the parameter to start the app.");
+            task::sleep(Duration::from_secs(1)).await;
+            exit(0);
+        }
 
-            // Create Whitelist DB
-            info!("[BOOT SQLite] Creating table {}", config.database_structure.clone().whitelist.table_name);
-            match config.database_structure.clone().whitelist.bin_type_infohash {
-                true => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS `{}` (`{}` BLOB PRIMARY KEY NOT NULL)",
-                            config.database_structure.clone().whitelist.table_name,
-                            config.database_structure.clone().whitelist.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[SQLite] Error: {}", error); }
-                    }
-                }
-                false => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS `{}` (`{}` TEXT PRIMARY KEY NOT NULL)",
-                            config.database_structure.clone().whitelist.table_name,
-                            config.database_structure.clone().whitelist.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[SQLite] Error: {}", error); }
-                    }
-                }
-            }
+        Box::new(structure)
+    }
 
-            // Create Blacklist DB
-            info!("[BOOT SQLite] Creating table {}", config.database_structure.clone().blacklist.table_name);
-            match config.database_structure.clone().blacklist.bin_type_infohash {
-                true => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS `{}` (`{}` BLOB PRIMARY KEY NOT NULL)",
-                            config.database_structure.clone().blacklist.table_name,
-                            config.database_structure.clone().blacklist.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[SQLite] Error: {}", error); }
-                    }
-                }
-                false => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS `{}` (`{}` TEXT PRIMARY KEY NOT NULL)",
-                            config.database_structure.clone().blacklist.table_name,
-                            config.database_structure.clone().blacklist.column_infohash
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[SQLite] Error: {}", error); }
-                    }
-                }
-            }
+    /// The ordered schema migrations for SQLite, rendered with the configured table and column names.
+    #[tracing::instrument(level = "debug")]
+    pub fn migrations(config: &Configuration) -> Vec<DatabaseMigration>
+    {
+        let structure = &config.database_structure;
+        let hash_type = |bin_type: bool| match bin_type { true => "BLOB", false => "TEXT" };
+        let users_id = match structure.users.id_uuid {
+            true => format!("`{}` TEXT PRIMARY KEY NOT NULL", structure.users.column_uuid),
+            false => format!("`{}` INTEGER PRIMARY KEY AUTOINCREMENT", structure.users.column_id)
+        };
+        vec![
+            DatabaseMigration {
+                version: 1,
+                name: String::from("initial_schema"),
+                statements: vec![
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS `{}` (`{}` {} PRIMARY KEY NOT NULL, `{}` INTEGER DEFAULT 0, `{}` INTEGER DEFAULT 0, `{}` INTEGER DEFAULT 0)",
+                        structure.torrents.table_name,
+                        structure.torrents.column_infohash,
+                        hash_type(structure.torrents.bin_type_infohash),
+                        structure.torrents.column_seeds,
+                        structure.torrents.column_peers,
+                        structure.torrents.column_completed
+                    ),
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS `{}` (`{}` {} PRIMARY KEY NOT NULL)",
+                        structure.whitelist.table_name,
+                        structure.whitelist.column_infohash,
+                        hash_type(structure.whitelist.bin_type_infohash)
+                    ),
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS `{}` (`{}` {} PRIMARY KEY NOT NULL)",
+                        structure.blacklist.table_name,
+                        structure.blacklist.column_infohash,
+                        hash_type(structure.blacklist.bin_type_infohash)
+                    ),
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS `{}` (`{}` {} PRIMARY KEY NOT NULL, `{}` INTEGER DEFAULT 0)",
+                        structure.keys.table_name,
+                        structure.keys.column_hash,
+                        hash_type(structure.keys.bin_type_hash),
+                        structure.keys.column_timeout
+                    ),
+                    format!(
+                        "CREATE TABLE IF NOT EXISTS `{}` ({}, `{}` {} NOT NULL, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0)",
+                        structure.users.table_name,
+                        users_id,
+                        structure.users.column_key,
+                        hash_type(structure.users.bin_type_key),
+                        structure.users.column_uploaded,
+                        structure.users.column_downloaded,
+                        structure.users.column_completed,
+                        structure.users.column_active,
+                        structure.users.column_updated
+                    ),
+                ],
+            },
+        ]
+    }
 
-            // Create Keys DB
-            info!("[BOOT SQLite] Creating table {}", config.database_structure.clone().keys.table_name);
-            match config.database_structure.clone().keys.bin_type_hash {
-                true => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS `{}` (`{}` BLOB PRIMARY KEY NOT NULL, `{}` INTEGER DEFAULT 0)",
-                            config.database_structure.clone().keys.table_name,
-                            config.database_structure.clone().keys.column_hash,
-                            config.database_structure.clone().keys.column_timeout
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[SQLite] Error: {}", error); }
-                    }
-                }
-                false => {
-                    match sqlx::query(
-                        format!(
-                            "CREATE TABLE IF NOT EXISTS `{}` (`{}` TEXT PRIMARY KEY NOT NULL, `{}` INTEGER DEFAULT 0)",
-                            config.database_structure.clone().keys.table_name,
-                            config.database_structure.clone().keys.column_hash,
-                            config.database_structure.clone().keys.column_timeout
-                        ).as_str()
-                    ).execute(pool).await {
-                        Ok(_) => {}
-                        Err(error) => { panic!("[SQLite] Error: {}", error); }
-                    }
-                }
-            }
+    /// Returns the latest applied migration, 0 when the schema version table does not exist yet.
+    #[tracing::instrument(level = "debug")]
+    pub async fn get_schema_version(&self) -> Result<u64, Error>
+    {
+        if sqlx::query("SELECT `name` FROM `sqlite_master` WHERE `type` = 'table' AND `name` = ?").bind(&self.schema_version_table).fetch_optional(&self.pool).await?.is_none() {
+            return Ok(0);
+        }
+        let row = sqlx::query(&format!("SELECT MAX(`version`) FROM `{}`", self.schema_version_table)).fetch_one(&self.pool).await?;
+        Ok(row.try_get::<Option<i64>, _>(0)?.unwrap_or(0) as u64)
+    }
 
-            // Create Users DB
-            info!("[BOOT SQLite] Creating table {}", config.database_structure.clone().users.table_name);
-            match config.database_structure.clone().users.id_uuid {
-                true => {
-                    match config.database_structure.clone().users.bin_type_key {
-                        true => {
-                            match sqlx::query(
-                                format!(
-                                    "CREATE TABLE IF NOT EXISTS `{}` (`{}` TEXT PRIMARY KEY NOT NULL, `{}` BLOB NOT NULL, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0)",
-                                    config.database_structure.clone().users.table_name,
-                                    config.database_structure.clone().users.column_uuid,
-                                    config.database_structure.clone().users.column_key,
-                                    config.database_structure.clone().users.column_uploaded,
-                                    config.database_structure.clone().users.column_downloaded,
-                                    config.database_structure.clone().users.column_completed,
-                                    config.database_structure.clone().users.column_active,
-                                    config.database_structure.clone().users.column_updated
-                                ).as_str()
-                            ).execute(pool).await {
-                                Ok(_) => {}
-                                Err(error) => { panic!("[SQLite] Error: {}", error); }
-                            }
-                        }
-                        false => {
-                            match sqlx::query(
-                                format!(
-                                    "CREATE TABLE IF NOT EXISTS `{}` (`{}` TEXT PRIMARY KEY NOT NULL, `{}` TEXT NOT NULL, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0)",
-                                    config.database_structure.clone().users.table_name,
-                                    config.database_structure.clone().users.column_uuid,
-                                    config.database_structure.clone().users.column_key,
-                                    config.database_structure.clone().users.column_uploaded,
-                                    config.database_structure.clone().users.column_downloaded,
-                                    config.database_structure.clone().users.column_completed,
-                                    config.database_structure.clone().users.column_active,
-                                    config.database_structure.clone().users.column_updated
-                                ).as_str()
-                            ).execute(pool).await {
-                                Ok(_) => {}
-                                Err(error) => { panic!("[SQLite] Error: {}", error); }
-                            }
-                        }
-                    }
-                }
-                false => {
-                    match config.database_structure.clone().users.bin_type_key {
-                        true => {
-                            match sqlx::query(
-                                format!(
-                                    "CREATE TABLE IF NOT EXISTS `{}` (`{}` INTEGER PRIMARY KEY AUTOINCREMENT, `{}` BLOB NOT NULL, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0)",
-                                    config.database_structure.clone().users.table_name,
-                                    config.database_structure.clone().users.column_id,
-                                    config.database_structure.clone().users.column_key,
-                                    config.database_structure.clone().users.column_uploaded,
-                                    config.database_structure.clone().users.column_downloaded,
-                                    config.database_structure.clone().users.column_completed,
-                                    config.database_structure.clone().users.column_active,
-                                    config.database_structure.clone().users.column_updated
-                                ).as_str()
-                            ).execute(pool).await {
-                                Ok(_) => {}
-                                Err(error) => { panic!("[SQLite] Error: {}", error); }
-                            }
-                        }
-                        false => {
-                            match sqlx::query(
-                                format!(
-                                    "CREATE TABLE IF NOT EXISTS `{}` (`{}` INTEGER PRIMARY KEY AUTOINCREMENT, `{}` TEXT NOT NULL, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0, `{}` INTEGER NOT NULL DEFAULT 0)",
-                                    config.database_structure.clone().users.table_name,
-                                    config.database_structure.clone().users.column_id,
-                                    config.database_structure.clone().users.column_key,
-                                    config.database_structure.clone().users.column_uploaded,
-                                    config.database_structure.clone().users.column_downloaded,
-                                    config.database_structure.clone().users.column_completed,
-                                    config.database_structure.clone().users.column_active,
-                                    config.database_structure.clone().users.column_updated
-                                ).as_str()
-                            ).execute(pool).await {
-                                Ok(_) => {}
-                                Err(error) => { panic!("[SQLite] Error: {}", error); }
-                            }
-                        }
-                    }
-                }
+    /// Applies the migrations newer than the recorded schema version in order, every migration together with its
+    /// record in the schema version table in one transaction. With `dry_run` the pending migrations are only returned.
+    #[tracing::instrument(level = "debug")]
+    pub async fn apply_migrations(&self, migrations: Vec<DatabaseMigration>, dry_run: bool) -> Result<Vec<DatabaseMigration>, Error>
+    {
+        let version = self.get_schema_version().await?;
+        let pending: Vec<DatabaseMigration> = migrations.into_iter().filter(|migration| migration.version > version).collect();
+        if dry_run || pending.is_empty() {
+            return Ok(pending);
+        }
+        sqlx::query(&format!("CREATE TABLE IF NOT EXISTS `{}` (`version` INTEGER PRIMARY KEY NOT NULL, `name` TEXT NOT NULL, `applied` INTEGER NOT NULL)", self.schema_version_table)).execute(&self.pool).await?;
+        for migration in pending.iter() {
+            info!("[SQLite] Applying migration {} {}", migration.version, migration.name);
+            let mut transaction = self.pool.begin().await?;
+            for statement in migration.statements.iter() {
+                sqlx::query(statement).execute(&mut *transaction).await?;
             }
-            info!("[BOOT] Created the database and tables, restart without the parameter to start the app.");
-            task::sleep(Duration::from_secs(1)).await;
-            exit(0);
+            sqlx::query(&format!("INSERT INTO `{}` (`version`, `name`, `applied`) VALUES (?, ?, ?)", self.schema_version_table))
+                .bind(migration.version as i64)
+                .bind(migration.name.clone())
+                .bind(chrono::Utc::now().timestamp())
+                .execute(&mut *transaction).await?;
+            self.commit(transaction).await?;
         }
-
-        structure
+        Ok(pending)
     }
+}
 
+#[async_trait]
+impl DatabaseBackend for DatabaseConnectorSQLite {
     #[tracing::instrument(level = "debug")]
-    pub async fn load_torrents(&self, tracker: Arc<TorrentTracker>) -> Result<(u64, u64), Error>
+    async fn load_torrents(&self, tracker: Arc<TorrentTracker>) -> Result<(u64, u64), Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -284,27 +181,23 @@ impl DatabaseConnectorSQLite {
             let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
                 true => {
                     format!(
-                        "SELECT hex(`{}`) AS `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT hex(`{}`) AS `{}`, `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_infohash,
                         structure.column_infohash,
                         structure.column_completed,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
                 false => {
                     format!(
-                        "SELECT `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT `{}`, `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_infohash,
                         structure.column_completed,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(start as i64).bind(length as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let info_hash_data: &[u8] = result.get(structure.column_infohash.as_str());
                 let info_hash: [u8; 20] = <[u8; 20]>::try_from(hex::decode(info_hash_data).unwrap()[0..20].as_ref()).unwrap();
@@ -315,7 +208,8 @@ impl DatabaseConnectorSQLite {
                         seeds: BTreeMap::new(),
                         peers: BTreeMap::new(),
                         completed: completed_count as u64,
-                        updated: std::time::Instant::now()
+                        updated: std::time::Instant::now(),
+                        peers_cursor: 0
                     }
                 );
                 torrents += 1;
@@ -333,35 +227,69 @@ impl DatabaseConnectorSQLite {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_torrents(&self, tracker: Arc<TorrentTracker>, torrents: BTreeMap<InfoHash, (TorrentEntry, UpdatesAction)>) -> Result<(), Error>
+    async fn save_torrents(&self, tracker: Arc<TorrentTracker>, torrents: BTreeMap<InfoHash, (TorrentEntry, UpdatesAction)>) -> Result<(), Error>
     {
         let mut torrents_transaction = self.pool.begin().await?;
         let mut torrents_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().torrents;
-        for (info_hash, (torrent_entry, updates_action)) in torrents.iter() {
-            torrents_handled_entries += 1;
-            match updates_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                            true => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`=X'{}'",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
+        let database = tracker.config.deref().clone().database;
+        let batch_size = database.batch_size as usize;
+        let (removes, saves): (Vec<_>, Vec<_>) = torrents.iter().partition(|(_, (_, updates_action))| *updates_action == UpdatesAction::Remove);
+        if database.remove_action {
+            for chunk in removes.chunks(batch_size) {
+                let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+                    "DELETE FROM `{}` WHERE `{}` IN (",
+                    structure.table_name,
+                    structure.column_infohash
+                ));
+                let mut separated = query_builder.separated(", ");
+                for (info_hash, _) in chunk.iter() {
+                    DatabaseConnectorSQLite::push_hash(&mut separated, info_hash.0, structure.bin_type_infohash);
+                }
+                separated.push_unseparated(")");
+                match query_builder.build().execute(&mut *torrents_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[SQLite] Error: {}", e);
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        torrents_handled_entries += removes.len() as u64;
+        match database.insert_vacant {
+            true => {
+                let mut columns = vec![];
+                if database.update_peers {
+                    columns.push(structure.column_seeds.clone());
+                    columns.push(structure.column_peers.clone());
+                }
+                if database.update_completed {
+                    columns.push(structure.column_completed.clone());
+                }
+                for chunk in saves.chunks(batch_size) {
+                    if !columns.is_empty() {
+                        let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+                            "INSERT INTO `{}` (`{}`, `{}`) ",
+                            structure.table_name,
+                            structure.column_infohash,
+                            columns.join("`, `")
+                        ));
+                        query_builder.push_values(chunk.iter(), |mut row, (info_hash, (torrent_entry, _))| {
+                            DatabaseConnectorSQLite::push_hash(&mut row, info_hash.0, structure.bin_type_infohash);
+                            if database.update_peers {
+                                row.push_bind(torrent_entry.seeds.len() as i64).push_bind(torrent_entry.peers.len() as i64);
                             }
-                            false => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`='{}'",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
+                            if database.update_completed {
+                                row.push_bind(torrent_entry.completed as i64);
                             }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
+                        });
+                        query_builder.push(format!(
+                            " ON CONFLICT (`{}`) DO UPDATE SET {}",
+                            structure.column_infohash,
+                            columns.iter().map(|column| format!("`{}`=excluded.`{}`", column, column)).collect::<Vec<String>>().join(", ")
+                        ));
+                        match query_builder.build().execute(&mut *torrents_transaction).await {
                             Ok(_) => {}
                             Err(e) => {
                                 error!("[SQLite] Error: {}", e);
@@ -369,174 +297,60 @@ impl DatabaseConnectorSQLite {
                             }
                         }
                     }
+                    torrents_handled_entries += chunk.len() as u64;
+                    info!("[SQLite] Handled {} torrents", torrents_handled_entries);
                 }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    match tracker.config.deref().clone().database.insert_vacant {
-                        true => {
-                            if tracker.config.deref().clone().database.update_peers {
-                                let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                                    true => {
-                                        format!(
-                                            "INSERT INTO `{}` (`{}`, `{}`, `{}`) VALUES (X'{}', {}, {}) ON CONFLICT (`{}`) DO UPDATE SET `{}`=excluded.`{}`, `{}`=excluded.`{}`",
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            info_hash,
-                                            torrent_entry.seeds.len(),
-                                            torrent_entry.peers.len(),
-                                            structure.column_infohash,
-                                            structure.column_seeds,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            structure.column_peers
-                                        )
-                                    }
-                                    false => {
-                                        format!(
-                                            "INSERT INTO `{}` (`{}`, `{}`, `{}`) VALUES ('{}', {}, {}) ON CONFLICT (`{}`) DO UPDATE SET `{}`=excluded.`{}`, `{}`=excluded.`{}`",
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            info_hash,
-                                            torrent_entry.seeds.len(),
-                                            torrent_entry.peers.len(),
-                                            structure.column_infohash,
-                                            structure.column_seeds,
-                                            structure.column_seeds,
-                                            structure.column_peers,
-                                            structure.column_peers
-                                        )
-                                    }
-                                };
-                                match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        error!("[SQLite] Error: {}", e);
-                                        return Err(e);
-                                    }
-                                }
-                            }
-                            if tracker.config.deref().clone().database.update_completed {
-                                let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                                    true => {
-                                        format!(
-                                            "INSERT INTO `{}` (`{}`, `{}`) VALUES (X'{}', {}) ON CONFLICT (`{}`) DO UPDATE SET `{}`=excluded.`{}`",
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            structure.column_completed,
-                                            info_hash,
-                                            torrent_entry.completed,
-                                            structure.column_infohash,
-                                            structure.column_completed,
-                                            structure.column_completed
-                                        )
-                                    }
-                                    false => {
-                                        format!(
-                                            "INSERT INTO `{}` (`{}`, `{}`) VALUES ('{}', {}) ON CONFLICT (`{}`) DO UPDATE SET `{}`=excluded.`{}`",
-                                            structure.table_name,
-                                            structure.column_infohash,
-                                            structure.column_completed,
-                                            info_hash,
-                                            torrent_entry.completed,
-                                            structure.column_infohash,
-                                            structure.column_completed,
-                                            structure.column_completed
-                                        )
-                                    }
-                                };
-                                match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        error!("[SQLite] Error: {}", e);
-                                        return Err(e);
-                                    }
-                                }
+            }
+            false => {
+                let string_format_peers = format!(
+                    "UPDATE OR IGNORE `{}` SET `{}`=?, `{}`=? WHERE `{}`=?",
+                    structure.table_name,
+                    structure.column_seeds,
+                    structure.column_peers,
+                    structure.column_infohash
+                );
+                let string_format_completed = format!(
+                    "UPDATE OR IGNORE `{}` SET `{}`=? WHERE `{}`=?",
+                    structure.table_name,
+                    structure.column_completed,
+                    structure.column_infohash
+                );
+                for (info_hash, (torrent_entry, _)) in saves.iter() {
+                    torrents_handled_entries += 1;
+                    if database.update_peers {
+                        let query = sqlx::query(string_format_peers.as_str())
+                            .bind(torrent_entry.seeds.len() as i64)
+                            .bind(torrent_entry.peers.len() as i64);
+                        match DatabaseConnectorSQLite::bind_hash(query, info_hash.0, structure.bin_type_infohash).execute(&mut *torrents_transaction).await {
+                            Ok(_) => {}
+                            Err(e) => {
+                                error!("[SQLite] Error: {}", e);
+                                return Err(e);
                             }
                         }
-                        false => {
-                            if tracker.config.deref().clone().database.update_peers {
-                                let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                                    true => {
-                                        format!(
-                                            "UPDATE OR IGNORE `{}` SET `{}`={}, `{}`={} WHERE `{}`=X'{}'",
-                                            structure.table_name,
-                                            structure.column_seeds,
-                                            torrent_entry.seeds.len(),
-                                            structure.column_peers,
-                                            torrent_entry.peers.len(),
-                                            structure.column_infohash,
-                                            info_hash
-                                        )
-                                    }
-                                    false => {
-                                        format!(
-                                            "UPDATE OR IGNORE `{}` SET `{}`={}, `{}`={} WHERE `{}`='{}'",
-                                            structure.table_name,
-                                            structure.column_seeds,
-                                            torrent_entry.seeds.len(),
-                                            structure.column_peers,
-                                            torrent_entry.peers.len(),
-                                            structure.column_infohash,
-                                            info_hash
-                                        )
-                                    }
-                                };
-                                match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        error!("[SQLite] Error: {}", e);
-                                        return Err(e);
-                                    }
-                                }
-                            }
-                            if tracker.config.deref().clone().database.update_completed {
-                                let string_format = match tracker.config.deref().clone().database_structure.torrents.bin_type_infohash {
-                                    true => {
-                                        format!(
-                                            "UPDATE IGNORE `{}` SET `{}`={} WHERE `{}`=X'{}'",
-                                            structure.table_name,
-                                            structure.column_completed,
-                                            torrent_entry.completed,
-                                            structure.column_infohash,
-                                            info_hash
-                                        )
-                                    }
-                                    false => {
-                                        format!(
-                                            "UPDATE IGNORE `{}` SET `{}`={} WHERE `{}`='{}'",
-                                            structure.table_name,
-                                            structure.column_completed,
-                                            torrent_entry.completed,
-                                            structure.column_infohash,
-                                            info_hash
-                                        )
-                                    }
-                                };
-                                match sqlx::query(string_format.as_str()).execute(&mut *torrents_transaction).await {
-                                    Ok(_) => {}
-                                    Err(e) => {
-                                        error!("[SQLite] Error: {}", e);
-                                        return Err(e);
-                                    }
-                                }
+                    }
+                    if database.update_completed {
+                        let query = sqlx::query(string_format_completed.as_str()).bind(torrent_entry.completed as i64);
+                        match DatabaseConnectorSQLite::bind_hash(query, info_hash.0, structure.bin_type_infohash).execute(&mut *torrents_transaction).await {
+                            Ok(_) => {}
+                            Err(e) => {
+                                error!("[SQLite] Error: {}", e);
+                                return Err(e);
                             }
                         }
                     }
+                    if (torrents_handled_entries as f64 / 1000f64).fract() == 0.0 {
+                        info!("[SQLite] Handled {} torrents", torrents_handled_entries);
+                    }
                 }
             }
-            if (torrents_handled_entries as f64 / 1000f64).fract() == 0.0 || torrents.len() as u64 == torrents_handled_entries {
-                info!("[SQLite] Handled {} torrents", torrents_handled_entries);
-            }
         }
         info!("[SQLite] Handled {} torrents", torrents_handled_entries);
         self.commit(torrents_transaction).await
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn load_whitelist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
+    async fn load_whitelist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -546,25 +360,21 @@ impl DatabaseConnectorSQLite {
             let string_format = match tracker.config.deref().clone().database_structure.whitelist.bin_type_infohash {
                 true => {
                     format!(
-                        "SELECT HEX(`{}`) AS `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT HEX(`{}`) AS `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_infohash,
                         structure.column_infohash,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
                 false => {
                     format!(
-                        "SELECT `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_infohash,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(start as i64).bind(length as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let info_hash_data: &[u8] = result.get(structure.column_infohash.as_str());
                 let info_hash: [u8; 20] = <[u8; 20]>::try_from(hex::decode(info_hash_data).unwrap()[0..20].as_ref()).unwrap();
@@ -582,82 +392,59 @@ impl DatabaseConnectorSQLite {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_whitelist(&self, tracker: Arc<TorrentTracker>, whitelists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>
+    async fn save_whitelist(&self, tracker: Arc<TorrentTracker>, whitelists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>
     {
         let mut whitelist_transaction = self.pool.begin().await?;
-        let mut whitelist_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().whitelist;
-        for (info_hash, updates_action) in whitelists.iter() {
-            whitelist_handled_entries += 1;
-            match updates_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.whitelist.bin_type_infohash {
-                            true => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`=UNHEX('{}')",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`='{}'",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *whitelist_transaction).await {
-                            Ok(_) => {}
-                            Err(e) => {
-                                error!("[SQLite] Error: {}", e);
-                                return Err(e);
-                            }
-                        }
-                    }
+        let batch_size = tracker.config.database.batch_size as usize;
+        // The last action queued for a hash wins, a statement can't touch the same row twice
+        let whitelists_latest: BTreeMap<InfoHash, UpdatesAction> = whitelists.iter().copied().collect();
+        let (removes, saves): (Vec<_>, Vec<_>) = whitelists_latest.iter().partition(|(_, updates_action)| **updates_action == UpdatesAction::Remove);
+        if tracker.config.deref().clone().database.remove_action {
+            for chunk in removes.chunks(batch_size) {
+                let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+                    "DELETE FROM `{}` WHERE `{}` IN (",
+                    structure.table_name,
+                    structure.column_infohash
+                ));
+                let mut separated = query_builder.separated(", ");
+                for (info_hash, _) in chunk.iter() {
+                    DatabaseConnectorSQLite::push_hash(&mut separated, info_hash.0, structure.bin_type_infohash);
                 }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    let string_format = match tracker.config.deref().clone().database_structure.whitelist.bin_type_infohash {
-                        true => {
-                            format!(
-                                "INSERT OR IGNORE INTO `{}` (`{}`) VALUES (X'{}')",
-                                structure.table_name,
-                                structure.column_infohash,
-                                info_hash
-                            )
-                        }
-                        false => {
-                            format!(
-                                "INSERT OR IGNORE INTO `{}` (`{}`) VALUES ('{}')",
-                                structure.table_name,
-                                structure.column_infohash,
-                                info_hash
-                            )
-                        }
-                    };
-                    match sqlx::query(string_format.as_str()).execute(&mut *whitelist_transaction).await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            error!("[SQLite] Error: {}", e);
-                            return Err(e);
-                        }
+                separated.push_unseparated(")");
+                match query_builder.build().execute(&mut *whitelist_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[SQLite] Error: {}", e);
+                        return Err(e);
                     }
                 }
             }
-            if (whitelist_handled_entries as f64 / 1000f64).fract() == 0.0 {
-                info!("[SQLite] Handled {} whitelisted torrents", whitelist_handled_entries);
+        }
+        for chunk in saves.chunks(batch_size) {
+            let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+                "INSERT OR IGNORE INTO `{}` (`{}`) ",
+                structure.table_name,
+                structure.column_infohash
+            ));
+            query_builder.push_values(chunk.iter(), |mut row, (info_hash, _)| {
+                DatabaseConnectorSQLite::push_hash(&mut row, info_hash.0, structure.bin_type_infohash);
+            });
+            match query_builder.build().execute(&mut *whitelist_transaction).await {
+                Ok(_) => {}
+                Err(e) => {
+                    error!("[SQLite] Error: {}", e);
+                    return Err(e);
+                }
             }
         }
-        info!("[SQLite] Handled {} whitelisted torrents", whitelist_handled_entries);
-        let _ = self.commit(whitelist_transaction).await;
-        Ok(whitelist_handled_entries)
+        info!("[SQLite] Handled {} whitelisted torrents", whitelists.len());
+        self.commit(whitelist_transaction).await?;
+        Ok(whitelists.len() as u64)
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn load_blacklist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
+    async fn load_blacklist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -667,25 +454,21 @@ impl DatabaseConnectorSQLite {
             let string_format = match tracker.config.deref().clone().database_structure.blacklist.bin_type_infohash {
                 true => {
                     format!(
-                        "SELECT HEX(`{}`) AS `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT HEX(`{}`) AS `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_infohash,
                         structure.column_infohash,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
                 false => {
                     format!(
-                        "SELECT `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_infohash,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(start as i64).bind(length as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let info_hash_data: &[u8] = result.get(structure.column_infohash.as_str());
                 let info_hash: [u8; 20] = <[u8; 20]>::try_from(hex::decode(info_hash_data).unwrap()[0..20].as_ref()).unwrap();
@@ -703,82 +486,59 @@ impl DatabaseConnectorSQLite {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_blacklist(&self, tracker: Arc<TorrentTracker>, blacklists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>
+    async fn save_blacklist(&self, tracker: Arc<TorrentTracker>, blacklists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>
     {
         let mut blacklist_transaction = self.pool.begin().await?;
-        let mut blacklist_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().blacklist;
-        for (info_hash, updates_action) in blacklists.iter() {
-            blacklist_handled_entries += 1;
-            match updates_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.blacklist.bin_type_infohash {
-                            true => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`=X'{}'",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`='{}'",
-                                    structure.table_name,
-                                    structure.column_infohash,
-                                    info_hash
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *blacklist_transaction).await {
-                            Ok(_) => {}
-                            Err(e) => {
-                                error!("[SQLite] Error: {}", e);
-                                return Err(e);
-                            }
-                        }
-                    }
+        let batch_size = tracker.config.database.batch_size as usize;
+        // The last action queued for a hash wins, a statement can't touch the same row twice
+        let blacklists_latest: BTreeMap<InfoHash, UpdatesAction> = blacklists.iter().copied().collect();
+        let (removes, saves): (Vec<_>, Vec<_>) = blacklists_latest.iter().partition(|(_, updates_action)| **updates_action == UpdatesAction::Remove);
+        if tracker.config.deref().clone().database.remove_action {
+            for chunk in removes.chunks(batch_size) {
+                let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+                    "DELETE FROM `{}` WHERE `{}` IN (",
+                    structure.table_name,
+                    structure.column_infohash
+                ));
+                let mut separated = query_builder.separated(", ");
+                for (info_hash, _) in chunk.iter() {
+                    DatabaseConnectorSQLite::push_hash(&mut separated, info_hash.0, structure.bin_type_infohash);
                 }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    let string_format = match tracker.config.deref().clone().database_structure.blacklist.bin_type_infohash {
-                        true => {
-                            format!(
-                                "INSERT OR IGNORE INTO `{}` (`{}`) VALUES (X'{}')",
-                                structure.table_name,
-                                structure.column_infohash,
-                                info_hash
-                            )
-                        }
-                        false => {
-                            format!(
-                                "INSERT OR IGNORE INTO `{}` (`{}`) VALUES ('{}')",
-                                structure.table_name,
-                                structure.column_infohash,
-                                info_hash
-                            )
-                        }
-                    };
-                    match sqlx::query(string_format.as_str()).execute(&mut *blacklist_transaction).await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            error!("[SQLite] Error: {}", e);
-                            return Err(e);
-                        }
+                separated.push_unseparated(")");
+                match query_builder.build().execute(&mut *blacklist_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[SQLite] Error: {}", e);
+                        return Err(e);
                     }
                 }
             }
-            if (blacklist_handled_entries as f64 / 1000f64).fract() == 0.0 {
-                info!("[SQLite] Handled {} blacklisted torrents", blacklist_handled_entries);
+        }
+        for chunk in saves.chunks(batch_size) {
+            let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+                "INSERT OR IGNORE INTO `{}` (`{}`) ",
+                structure.table_name,
+                structure.column_infohash
+            ));
+            query_builder.push_values(chunk.iter(), |mut row, (info_hash, _)| {
+                DatabaseConnectorSQLite::push_hash(&mut row, info_hash.0, structure.bin_type_infohash);
+            });
+            match query_builder.build().execute(&mut *blacklist_transaction).await {
+                Ok(_) => {}
+                Err(e) => {
+                    error!("[SQLite] Error: {}", e);
+                    return Err(e);
+                }
             }
         }
-        info!("[SQLite] Handled {} blacklisted torrents", blacklist_handled_entries);
-        let _ = self.commit(blacklist_transaction).await;
-        Ok(blacklist_handled_entries)
+        info!("[SQLite] Handled {} blacklisted torrents", blacklists.len());
+        self.commit(blacklist_transaction).await?;
+        Ok(blacklists.len() as u64)
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn load_keys(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
+    async fn load_keys(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -788,27 +548,23 @@ impl DatabaseConnectorSQLite {
             let string_format = match tracker.config.deref().clone().database_structure.keys.bin_type_hash {
                 true => {
                     format!(
-                        "SELECT HEX(`{}`) AS `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT HEX(`{}`) AS `{}`, `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_hash,
                         structure.column_hash,
                         structure.column_timeout,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
                 false => {
                     format!(
-                        "SELECT `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                        "SELECT `{}`, `{}` FROM `{}` LIMIT ?, ?",
                         structure.column_hash,
                         structure.column_timeout,
-                        structure.table_name,
-                        start,
-                        length
+                        structure.table_name
                     )
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(start as i64).bind(length as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let hash_data: &[u8] = result.get(structure.column_hash.as_str());
                 let hash: [u8; 20] = <[u8; 20]>::try_from(hex::decode(hash_data).unwrap()[0..20].as_ref()).unwrap();
@@ -827,92 +583,69 @@ impl DatabaseConnectorSQLite {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_keys(&self, tracker: Arc<TorrentTracker>, keys: BTreeMap<InfoHash, (i64, UpdatesAction)>) -> Result<u64, Error>
+    async fn save_keys(&self, tracker: Arc<TorrentTracker>, keys: BTreeMap<InfoHash, (i64, UpdatesAction)>) -> Result<u64, Error>
     {
         let mut keys_transaction = self.pool.begin().await?;
         let mut keys_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().keys;
-        for (hash, (timeout, update_action)) in keys.iter() {
-            keys_handled_entries += 1;
-            match update_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.keys.bin_type_hash {
-                            true => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`=X'{}'",
-                                    structure.table_name,
-                                    structure.column_hash,
-                                    hash
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`='{}'",
-                                    structure.table_name,
-                                    structure.column_hash,
-                                    hash
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *keys_transaction).await {
-                            Ok(_) => {}
-                            Err(e) => {
-                                error!("[SQLite] Error: {}", e);
-                                return Err(e);
-                            }
-                        }
-                    }
+        let batch_size = tracker.config.database.batch_size as usize;
+        let (removes, saves): (Vec<_>, Vec<_>) = keys.iter().partition(|(_, (_, updates_action))| *updates_action == UpdatesAction::Remove);
+        if tracker.config.deref().clone().database.remove_action {
+            for chunk in removes.chunks(batch_size) {
+                let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+                    "DELETE FROM `{}` WHERE `{}` IN (",
+                    structure.table_name,
+                    structure.column_hash
+                ));
+                let mut separated = query_builder.separated(", ");
+                for (hash, _) in chunk.iter() {
+                    DatabaseConnectorSQLite::push_hash(&mut separated, hash.0, structure.bin_type_hash);
                 }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    let string_format = match tracker.config.deref().clone().database_structure.keys.bin_type_hash {
-                        true => {
-                            format!(
-                                "INSERT INTO `{}` (`{}`, `{}`) VALUES (X'{}', {}) ON CONFLICT (`{}`) DO UPDATE SET `{}`=excluded.`{}`",
-                                structure.table_name,
-                                structure.column_hash,
-                                structure.column_timeout,
-                                hash,
-                                timeout,
-                                structure.column_hash,
-                                structure.column_timeout,
-                                structure.column_timeout
-                            )
-                        }
-                        false => {
-                            format!(
-                                "INSERT INTO `{}` (`{}`, `{}`) VALUES ('{}', {}) ON CONFLICT (`{}`) DO UPDATE SET `{}`=excluded.`{}`",
-                                structure.table_name,
-                                structure.column_hash,
-                                structure.column_timeout,
-                                hash,
-                                timeout,
-                                structure.column_hash,
-                                structure.column_timeout,
-                                structure.column_timeout
-                            )
-                        }
-                    };
-                    match sqlx::query(string_format.as_str()).execute(&mut *keys_transaction).await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            error!("[SQLite] Error: {}", e);
-                            return Err(e);
-                        }
+                separated.push_unseparated(")");
+                match query_builder.build().execute(&mut *keys_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[SQLite] Error: {}", e);
+                        return Err(e);
                     }
                 }
             }
-            if (keys_handled_entries as f64 / 1000f64).fract() == 0.0 {
-                info!("[SQLite] Handled {} keys", keys_handled_entries);
+        }
+        keys_handled_entries += removes.len() as u64;
+        for chunk in saves.chunks(batch_size) {
+            let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+                "INSERT INTO `{}` (`{}`, `{}`) ",
+                structure.table_name,
+                structure.column_hash,
+                structure.column_timeout
+            ));
+            query_builder.push_values(chunk.iter(), |mut row, (hash, (timeout, _))| {
+                DatabaseConnectorSQLite::push_hash(&mut row, hash.0, structure.bin_type_hash);
+                row.push_bind(*timeout);
+            });
+            query_builder.push(format!(
+                " ON CONFLICT (`{}`) DO UPDATE SET `{}`=excluded.`{}`",
+                structure.column_hash,
+                structure.column_timeout,
+                structure.column_timeout
+            ));
+            match query_builder.build().execute(&mut *keys_transaction).await {
+                Ok(_) => {}
+                Err(e) => {
+                    error!("[SQLite] Error: {}", e);
+                    return Err(e);
+                }
             }
+            keys_handled_entries += chunk.len() as u64;
+            info!("[SQLite] Handled {} keys", keys_handled_entries);
         }
         info!("[SQLite] Handled {} keys", keys_handled_entries);
-        let _ = self.commit(keys_transaction).await;
+        self.commit(keys_transaction).await?;
         Ok(keys_handled_entries)
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn load_users(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
+    async fn load_users(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>
     {
         let mut start = 0u64;
         let length = 100000u64;
@@ -924,7 +657,7 @@ impl DatabaseConnectorSQLite {
                     match tracker.config.deref().clone().database_structure.users.bin_type_key {
                         true => {
                             format!(
-                                "SELECT `{}`, HEX(`{}`) AS `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                                "SELECT `{}`, HEX(`{}`) AS `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT ?, ?",
                                 structure.column_uuid,
                                 structure.column_key,
                                 structure.column_key,
@@ -933,14 +666,12 @@ impl DatabaseConnectorSQLite {
                                 structure.column_completed,
                                 structure.column_updated,
                                 structure.column_active,
-                                structure.table_name,
-                                start,
-                                length
+                                structure.table_name
                             )
                         }
                         false => {
                             format!(
-                                "SELECT `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                                "SELECT `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT ?, ?",
                                 structure.column_uuid,
                                 structure.column_key,
                                 structure.column_uploaded,
@@ -948,9 +679,7 @@ impl DatabaseConnectorSQLite {
                                 structure.column_completed,
                                 structure.column_updated,
                                 structure.column_active,
-                                structure.table_name,
-                                start,
-                                length
+                                structure.table_name
                             )
                         }
                     }
@@ -959,7 +688,7 @@ impl DatabaseConnectorSQLite {
                     match tracker.config.deref().clone().database_structure.users.bin_type_key {
                         true => {
                             format!(
-                                "SELECT `{}`, HEX(`{}`) AS `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                                "SELECT `{}`, HEX(`{}`) AS `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT ?, ?",
                                 structure.column_id,
                                 structure.column_key,
                                 structure.column_key,
@@ -968,14 +697,12 @@ impl DatabaseConnectorSQLite {
                                 structure.column_completed,
                                 structure.column_updated,
                                 structure.column_active,
-                                structure.table_name,
-                                start,
-                                length
+                                structure.table_name
                             )
                         }
                         false => {
                             format!(
-                                "SELECT `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT {}, {}",
+                                "SELECT `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}` FROM `{}` LIMIT ?, ?",
                                 structure.column_id,
                                 structure.column_key,
                                 structure.column_uploaded,
@@ -983,30 +710,26 @@ impl DatabaseConnectorSQLite {
                                 structure.column_completed,
                                 structure.column_updated,
                                 structure.column_active,
-                                structure.table_name,
-                                start,
-                                length
+                                structure.table_name
                             )
                         }
                     }
                 }
             };
-            let mut rows = sqlx::query(string_format.as_str()).fetch(&self.pool);
+            let mut rows = sqlx::query(string_format.as_str()).bind(start as i64).bind(length as i64).fetch(&self.pool);
             while let Some(result) = rows.try_next().await? {
                 let hash = match tracker.config.deref().clone().database_structure.users.id_uuid {
                     true => {
                         let uuid_data: &[u8] = result.get(structure.column_uuid.as_str());
                         let mut hasher = Sha1::new();
                         hasher.update(uuid_data);
-                        let hashed = <[u8; 20]>::try_from(hasher.finalize().as_slice()).unwrap();
-                        hashed
+                        <[u8; 20]>::try_from(hasher.finalize().as_slice()).unwrap()
                     }
                     false => {
                         let id_data: &[u8] = result.get(structure.column_id.as_str());
                         let mut hasher = Sha1::new();
                         hasher.update(id_data);
-                        let hashed = <[u8; 20]>::try_from(hasher.finalize().as_slice()).unwrap();
-                        hashed
+                        <[u8; 20]>::try_from(hasher.finalize().as_slice()).unwrap()
                     }
                 };
                 tracker.add_user(UserId(hash), UserEntryItem {
@@ -1039,293 +762,121 @@ impl DatabaseConnectorSQLite {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn save_users(&self, tracker: Arc<TorrentTracker>, users: BTreeMap<UserId, (UserEntryItem, UpdatesAction)>) -> Result<(), Error>
+    async fn save_users(&self, tracker: Arc<TorrentTracker>, users: BTreeMap<UserId, (UserEntryItem, UpdatesAction)>) -> Result<(), Error>
     {
         let mut users_transaction = self.pool.begin().await?;
         let mut users_handled_entries = 0u64;
         let structure = tracker.config.deref().clone().database_structure.clone().users;
-        for (_, (user_entry_item, updates_action)) in users.iter() {
-            users_handled_entries += 1;
-            match updates_action {
-                UpdatesAction::Remove => {
-                    if tracker.config.deref().clone().database.remove_action {
-                        let string_format = match tracker.config.deref().clone().database_structure.users.id_uuid {
-                            true => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`='{}'",
-                                    structure.table_name,
-                                    structure.column_uuid,
-                                    user_entry_item.user_uuid.clone().unwrap()
-                                )
-                            }
-                            false => {
-                                format!(
-                                    "DELETE FROM `{}` WHERE `{}`='{}'",
-                                    structure.table_name,
-                                    structure.column_id,
-                                    user_entry_item.user_id.unwrap()
-                                )
-                            }
-                        };
-                        match sqlx::query(string_format.as_str()).execute(&mut *users_transaction).await {
-                            Ok(_) => {}
-                            Err(e) => {
-                                error!("[SQLite] Error: {}", e);
-                                return Err(e);
-                            }
-                        }
+        let batch_size = tracker.config.database.batch_size as usize;
+        let column_identity = match structure.id_uuid {
+            true => { structure.column_uuid.clone() }
+            false => { structure.column_id.clone() }
+        };
+        let (removes, saves): (Vec<_>, Vec<_>) = users.values().partition(|(_, updates_action)| *updates_action == UpdatesAction::Remove);
+        if tracker.config.deref().clone().database.remove_action {
+            for chunk in removes.chunks(batch_size) {
+                let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+                    "DELETE FROM `{}` WHERE `{}` IN (",
+                    structure.table_name,
+                    column_identity
+                ));
+                let mut separated = query_builder.separated(", ");
+                for (user_entry_item, _) in chunk.iter() {
+                    DatabaseConnectorSQLite::push_user(&mut separated, user_entry_item, structure.id_uuid);
+                }
+                separated.push_unseparated(")");
+                match query_builder.build().execute(&mut *users_transaction).await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        error!("[SQLite] Error: {}", e);
+                        return Err(e);
                     }
                 }
-                UpdatesAction::Add | UpdatesAction::Update => {
-                    let string_format = match  tracker.config.deref().clone().database.insert_vacant {
-                        true => {
-                            match tracker.config.deref().clone().database_structure.users.id_uuid {
-                                true => {
-                                    match tracker.config.deref().clone().database_structure.users.bin_type_key {
-                                        true => {
-                                            format!(
-                                                "INSERT INTO `{}` (`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`) VALUES ('{}', {}, {}, {}, X'{}', {}, {}) ON CONFLICT (`{}`) DO UPDATE SET `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`",
-                                                structure.table_name,
-                                                structure.column_uuid,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.user_uuid.clone().unwrap() ,
-                                                user_entry_item.completed,
-                                                user_entry_item.active,
-                                                user_entry_item.downloaded,
-                                                user_entry_item.key,
-                                                user_entry_item.uploaded,
-                                                user_entry_item.updated,
-                                                structure.column_uuid,
-                                                structure.column_completed,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                structure.column_updated
-                                            )
-                                        }
-                                        false => {
-                                            format!(
-                                                "INSERT INTO `{}` (`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`) VALUES ('{}', {}, {}, {}, '{}', {}, {}) ON CONFLICT (`{}`) DO UPDATE SET `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`",
-                                                structure.table_name,
-                                                structure.column_uuid,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                                user_entry_item.completed,
-                                                user_entry_item.active,
-                                                user_entry_item.downloaded,
-                                                user_entry_item.key,
-                                                user_entry_item.uploaded,
-                                                user_entry_item.updated,
-                                                structure.column_uuid,
-                                                structure.column_completed,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                structure.column_updated
-                                            )
-                                        }
-                                    }
-                                }
-                                false => {
-                                    match tracker.config.deref().clone().database_structure.users.bin_type_key {
-                                        true => {
-                                            format!(
-                                                "INSERT INTO `{}` (`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`) VALUES (X'{}', {}, {}, {}, X'{}', {}, {}) ON CONFLICT (`{}`) DO UPDATE SET `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`",
-                                                structure.table_name,
-                                                structure.column_id,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                                user_entry_item.completed,
-                                                user_entry_item.active,
-                                                user_entry_item.downloaded,
-                                                user_entry_item.key,
-                                                user_entry_item.uploaded,
-                                                user_entry_item.updated,
-                                                structure.column_id,
-                                                structure.column_completed,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                structure.column_updated
-                                            )
-                                        }
-                                        false => {
-                                            format!(
-                                                "INSERT INTO `{}` (`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`) VALUES ('{}', {}, {}, {}, '{}', {}, {}) ON CONFLICT (`{}`) DO UPDATE SET `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`",
-                                                structure.table_name,
-                                                structure.column_id,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                                user_entry_item.completed,
-                                                user_entry_item.active,
-                                                user_entry_item.downloaded,
-                                                user_entry_item.key,
-                                                user_entry_item.uploaded,
-                                                user_entry_item.updated,
-                                                structure.column_id,
-                                                structure.column_completed,
-                                                structure.column_completed,
-                                                structure.column_active,
-                                                structure.column_active,
-                                                structure.column_downloaded,
-                                                structure.column_downloaded,
-                                                structure.column_key,
-                                                structure.column_key,
-                                                structure.column_uploaded,
-                                                structure.column_uploaded,
-                                                structure.column_updated,
-                                                structure.column_updated
-                                            )
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        false => {
-                            match tracker.config.deref().clone().database_structure.users.id_uuid {
-                                true => {
-                                    match tracker.config.deref().clone().database_structure.users.bin_type_key {
-                                        true => {
-                                            format!(
-                                                "UPDATE OR IGNORE `{}` SET `{}`={}, `{}`={}, `{}`={}, `{}`=X'{}', `{}`={}, `{}`={} WHERE `{}`=X'{}'",
-                                                structure.table_name,
-                                                structure.column_completed,
-                                                user_entry_item.completed,
-                                                structure.column_active,
-                                                user_entry_item.active,
-                                                structure.column_downloaded,
-                                                user_entry_item.downloaded,
-                                                structure.column_key,
-                                                user_entry_item.key,
-                                                structure.column_uploaded,
-                                                user_entry_item.uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.updated,
-                                                structure.column_uuid,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                            )
-                                        }
-                                        false => {
-                                            format!(
-                                                "UPDATE OR IGNORE `{}` SET `{}`={}, `{}`={}, `{}`={}, `{}`='{}', `{}`={}, `{}`={} WHERE `{}`='{}'",
-                                                structure.table_name,
-                                                structure.column_completed,
-                                                user_entry_item.completed,
-                                                structure.column_active,
-                                                user_entry_item.active,
-                                                structure.column_downloaded,
-                                                user_entry_item.downloaded,
-                                                structure.column_key,
-                                                user_entry_item.key,
-                                                structure.column_uploaded,
-                                                user_entry_item.uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.updated,
-                                                structure.column_uuid,
-                                                user_entry_item.user_uuid.clone().unwrap(),
-                                            )
-                                        }
-                                    }
-                                }
-                                false => {
-                                    match tracker.config.deref().clone().database_structure.users.bin_type_key {
-                                        true => {
-                                            format!(
-                                                "UPDATE OR IGNORE `{}` SET `{}`={}, `{}`={}, `{}`={}, `{}`=X'{}', `{}`={}, `{}`={} WHERE `{}`=X'{}'",
-                                                structure.table_name,
-                                                structure.column_completed,
-                                                user_entry_item.completed,
-                                                structure.column_active,
-                                                user_entry_item.active,
-                                                structure.column_downloaded,
-                                                user_entry_item.downloaded,
-                                                structure.column_key,
-                                                user_entry_item.key,
-                                                structure.column_uploaded,
-                                                user_entry_item.uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.updated,
-                                                structure.column_uuid,
-                                                user_entry_item.user_id.unwrap(),
-                                            )
-                                        }
-                                        false => {
-                                            format!(
-                                                "UPDATE OR IGNORE `{}` SET `{}`={}, `{}`={}, `{}`={}, `{}`='{}', `{}`={}, `{}`={} WHERE `{}`='{}'",
-                                                structure.table_name,
-                                                structure.column_completed,
-                                                user_entry_item.completed,
-                                                structure.column_active,
-                                                user_entry_item.active,
-                                                structure.column_downloaded,
-                                                user_entry_item.downloaded,
-                                                structure.column_key,
-                                                user_entry_item.key,
-                                                structure.column_uploaded,
-                                                user_entry_item.uploaded,
-                                                structure.column_updated,
-                                                user_entry_item.updated,
-                                                structure.column_uuid,
-                                                user_entry_item.user_id.unwrap(),
-                                            )
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    };
-                    match sqlx::query(string_format.as_str()).execute(&mut *users_transaction).await {
+            }
+        }
+        users_handled_entries += removes.len() as u64;
+        match tracker.config.deref().clone().database.insert_vacant {
+            true => {
+                for chunk in saves.chunks(batch_size) {
+                    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+                        "INSERT INTO `{}` (`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`) ",
+                        structure.table_name,
+                        column_identity,
+                        structure.column_completed,
+                        structure.column_active,
+                        structure.column_downloaded,
+                        structure.column_key,
+                        structure.column_uploaded,
+                        structure.column_updated
+                    ));
+                    query_builder.push_values(chunk.iter(), |mut row, (user_entry_item, _)| {
+                        DatabaseConnectorSQLite::push_user(&mut row, user_entry_item, structure.id_uuid);
+                        row.push_bind(user_entry_item.completed as i64)
+                            .push_bind(user_entry_item.active as i64)
+                            .push_bind(user_entry_item.downloaded as i64);
+                        DatabaseConnectorSQLite::push_hash(&mut row, user_entry_item.key.0, structure.bin_type_key);
+                        row.push_bind(user_entry_item.uploaded as i64)
+                            .push_bind(user_entry_item.updated as i64);
+                    });
+                    query_builder.push(format!(
+                        " ON CONFLICT (`{}`) DO UPDATE SET `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`, `{}`=excluded.`{}`",
+                        column_identity,
+                        structure.column_completed,
+                        structure.column_completed,
+                        structure.column_active,
+                        structure.column_active,
+                        structure.column_downloaded,
+                        structure.column_downloaded,
+                        structure.column_key,
+                        structure.column_key,
+                        structure.column_uploaded,
+                        structure.column_uploaded,
+                        structure.column_updated,
+                        structure.column_updated
+                    ));
+                    match query_builder.build().execute(&mut *users_transaction).await {
                         Ok(_) => {}
                         Err(e) => {
                             error!("[SQLite] Error: {}", e);
                             return Err(e);
                         }
                     }
+                    users_handled_entries += chunk.len() as u64;
+                    info!("[SQLite] Handled {} users", users_handled_entries);
                 }
             }
-            if (users_handled_entries as f64 / 1000f64).fract() == 0.0 || users.len() as u64 == users_handled_entries {
-                info!("[SQLite] Handled {} users", users_handled_entries);
+            false => {
+                let string_format = format!(
+                    "UPDATE OR IGNORE `{}` SET `{}`=?, `{}`=?, `{}`=?, `{}`=?, `{}`=?, `{}`=? WHERE `{}`=?",
+                    structure.table_name,
+                    structure.column_completed,
+                    structure.column_active,
+                    structure.column_downloaded,
+                    structure.column_key,
+                    structure.column_uploaded,
+                    structure.column_updated,
+                    column_identity
+                );
+                for (user_entry_item, _) in saves.iter() {
+                    users_handled_entries += 1;
+                    let query = sqlx::query(string_format.as_str())
+                        .bind(user_entry_item.completed as i64)
+                        .bind(user_entry_item.active as i64)
+                        .bind(user_entry_item.downloaded as i64);
+                    let query = DatabaseConnectorSQLite::bind_hash(query, user_entry_item.key.0, structure.bin_type_key)
+                        .bind(user_entry_item.uploaded as i64)
+                        .bind(user_entry_item.updated as i64);
+                    match DatabaseConnectorSQLite::bind_user(query, user_entry_item, structure.id_uuid).execute(&mut *users_transaction).await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            error!("[SQLite] Error: {}", e);
+                            return Err(e);
+                        }
+                    }
+                    if (users_handled_entries as f64 / 1000f64).fract() == 0.0 {
+                        info!("[SQLite] Handled {} users", users_handled_entries);
+                    }
+                }
             }
         }
         info!("[SQLite] Handled {} users", users_handled_entries);
@@ -1333,7 +884,7 @@ impl DatabaseConnectorSQLite {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn reset_seeds_peers(&self, tracker: Arc<TorrentTracker>) -> Result<(), Error>
+    async fn reset_seeds_peers(&self, tracker: Arc<TorrentTracker>) -> Result<(), Error>
     {
         let mut reset_seeds_peers_transaction = self.pool.begin().await?;
         let structure = tracker.config.deref().clone().database_structure.clone().torrents;
@@ -1350,10 +901,60 @@ impl DatabaseConnectorSQLite {
                 return Err(e);
             }
         }
-        let _ = self.commit(reset_seeds_peers_transaction).await;
+        self.commit(reset_seeds_peers_transaction).await?;
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug")]
+    async fn schema_version(&self) -> Result<u64, Error>
+    {
+        self.get_schema_version().await
+    }
+
+    #[tracing::instrument(level = "debug")]
+    async fn migrate(&self, tracker: Arc<TorrentTracker>, dry_run: bool) -> Result<Vec<DatabaseMigration>, Error>
+    {
+        self.apply_migrations(DatabaseConnectorSQLite::migrations(&tracker.config), dry_run).await
+    }
+}
+
+impl DatabaseConnectorSQLite {
+    /// Binds a 20 bytes hash as a blob, or as its hex string when the column is not binary.
+    pub fn bind_hash<'q>(query: Query<'q, Sqlite, SqliteArguments<'q>>, hash: [u8; 20], bin_type: bool) -> Query<'q, Sqlite, SqliteArguments<'q>>
+    {
+        match bin_type {
+            true => { query.bind(hash.to_vec()) }
+            false => { query.bind(hex::encode(hash)) }
+        }
+    }
+
+    /// Binds the identity of a user, its UUID or its numeric ID depending on the configured structure.
+    pub fn bind_user<'q>(query: Query<'q, Sqlite, SqliteArguments<'q>>, user_entry_item: &UserEntryItem, id_uuid: bool) -> Query<'q, Sqlite, SqliteArguments<'q>>
+    {
+        match id_uuid {
+            true => { query.bind(user_entry_item.user_uuid.clone().unwrap()) }
+            false => { query.bind(user_entry_item.user_id.unwrap() as i64) }
+        }
+    }
+
+    /// Pushes a 20 bytes hash into a multi-row statement, the same way `bind_hash` binds it.
+    pub fn push_hash(separated: &mut Separated<'_, '_, Sqlite, &'static str>, hash: [u8; 20], bin_type: bool)
+    {
+        match bin_type {
+            true => { separated.push_bind(hash.to_vec()); }
+            false => { separated.push_bind(hex::encode(hash)); }
+        }
+    }
+
+    /// Pushes the identity of a user into a multi-row statement, the same way `bind_user` binds it.
+    pub fn push_user(separated: &mut Separated<'_, '_, Sqlite, &'static str>, user_entry_item: &UserEntryItem, id_uuid: bool)
+    {
+        match id_uuid {
+            true => { separated.push_bind(user_entry_item.user_uuid.clone().unwrap()); }
+            false => { separated.push_bind(user_entry_item.user_id.unwrap() as i64); }
+        }
+    }
+
     #[tracing::instrument(level = "debug")]
     pub async fn commit(&self, transaction: Transaction<'_, Sqlite>) -> Result<(), Error>
     {
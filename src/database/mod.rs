@@ -1,3 +1,5 @@
 pub mod enums;
 pub mod impls;
-pub mod structs;
\ No newline at end of file
+pub mod structs;
+pub mod traits;
+pub mod tests;
\ No newline at end of file
@@ -1,4 +1,5 @@
 pub mod database_connector;
 pub mod database_connector_sqlite;
 pub mod database_connector_mysql;
-pub mod database_connector_pgsql;
\ No newline at end of file
+pub mod database_connector_pgsql;
+pub mod database_migration;
\ No newline at end of file
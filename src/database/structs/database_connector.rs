@@ -1,12 +1,3 @@
-use crate::database::enums::database_drivers::DatabaseDrivers;
-use crate::database::structs::database_connector_mysql::DatabaseConnectorMySQL;
-use crate::database::structs::database_connector_pgsql::DatabaseConnectorPgSQL;
-use crate::database::structs::database_connector_sqlite::DatabaseConnectorSQLite;
-
+/// Builds the `DatabaseBackend` matching the configured engine.
 #[derive(Debug, Clone)]
-pub struct DatabaseConnector {
-    pub(crate) mysql: Option<DatabaseConnectorMySQL>,
-    pub(crate) sqlite: Option<DatabaseConnectorSQLite>,
-    pub(crate) pgsql: Option<DatabaseConnectorPgSQL>,
-    pub(crate) engine: Option<DatabaseDrivers>,
-}
\ No newline at end of file
+pub struct DatabaseConnector {}
\ No newline at end of file
@@ -3,4 +3,5 @@ use sqlx::{MySql, Pool};
 #[derive(Debug, Clone)]
 pub struct DatabaseConnectorMySQL {
     pub(crate) pool: Pool<MySql>,
+    pub(crate) schema_version_table: String,
 }
\ No newline at end of file
@@ -3,4 +3,5 @@ use sqlx::{Pool, Postgres};
 #[derive(Debug, Clone)]
 pub struct DatabaseConnectorPgSQL {
     pub(crate) pool: Pool<Postgres>,
+    pub(crate) schema_version_table: String,
 }
\ No newline at end of file
@@ -3,4 +3,5 @@ use sqlx::{Pool, Sqlite};
 #[derive(Debug, Clone)]
 pub struct DatabaseConnectorSQLite {
     pub(crate) pool: Pool<Sqlite>,
+    pub(crate) schema_version_table: String,
 }
\ No newline at end of file
@@ -0,0 +1,7 @@
+/// One ordered step of the schema, rendered for an engine with the configured table and column names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseMigration {
+    pub version: u64,
+    pub name: String,
+    pub statements: Vec<String>,
+}
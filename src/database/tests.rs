@@ -0,0 +1,197 @@
+#![cfg(test)]
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use crate::config::structs::configuration::Configuration;
+use crate::database::structs::database_connector_sqlite::DatabaseConnectorSQLite;
+use crate::database::structs::database_migration::DatabaseMigration;
+use crate::tracker::enums::updates_action::UpdatesAction;
+use crate::tracker::structs::info_hash::InfoHash;
+use crate::tracker::structs::torrent_entry::TorrentEntry;
+use crate::tracker::structs::torrent_tracker::TorrentTracker;
+use crate::tracker::structs::user_entry_item::UserEntryItem;
+use crate::tracker::structs::user_id::UserId;
+
+async fn sqlite_tracker(path: &std::path::Path) -> Arc<TorrentTracker>
+{
+    let mut config = Configuration::init();
+    config.database.path = format!("sqlite://{}", path.display());
+    config.database.insert_vacant = true;
+    config.database.remove_action = true;
+    config.database.update_peers = true;
+    Arc::new(TorrentTracker::new(Arc::new(config), false).await)
+}
+
+#[tokio::test]
+async fn sqlite_binds_values_that_would_break_interpolated_sql()
+{
+    let path = std::env::temp_dir().join(format!("torrust-actix-bind-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let tracker = sqlite_tracker(&path).await;
+    let pool = DatabaseConnectorSQLite::create(format!("sqlite://{}", path.display()).as_str()).await.unwrap();
+    sqlx::query("CREATE TABLE `torrents` (`infohash` BLOB PRIMARY KEY NOT NULL, `seeds` INTEGER DEFAULT 0, `peers` INTEGER DEFAULT 0, `completed` INTEGER DEFAULT 0)").execute(&pool).await.unwrap();
+    sqlx::query("CREATE TABLE `users` (`uuid` TEXT PRIMARY KEY NOT NULL, `key` BLOB NOT NULL, `uploaded` INTEGER NOT NULL DEFAULT 0, `downloaded` INTEGER NOT NULL DEFAULT 0, `completed` INTEGER NOT NULL DEFAULT 0, `updated` INTEGER NOT NULL DEFAULT 0, `active` INTEGER NOT NULL DEFAULT 0)").execute(&pool).await.unwrap();
+
+    let info_hash = InfoHash([0x27; 20]);
+    let mut torrent_entry = TorrentEntry::new();
+    torrent_entry.completed = 5;
+    tracker.sqlx.save_torrents(tracker.clone(), BTreeMap::from([(info_hash, (torrent_entry, UpdatesAction::Add))])).await.unwrap();
+    let uuid = String::from("o'brien'); DROP TABLE `torrents`; --");
+    let user_entry_item = UserEntryItem {
+        key: UserId([0x28; 20]),
+        user_id: None,
+        user_uuid: Some(uuid.clone()),
+        uploaded: 10,
+        downloaded: 20,
+        completed: 1,
+        updated: 1700000000,
+        active: 1,
+        torrents_active: BTreeMap::new(),
+    };
+    tracker.sqlx.save_users(tracker.clone(), BTreeMap::from([(UserId([0x29; 20]), (user_entry_item.clone(), UpdatesAction::Add))])).await.unwrap();
+
+    let restored = sqlite_tracker(&path).await;
+    assert_eq!(restored.sqlx.load_torrents(restored.clone()).await.unwrap(), (1, 5));
+    assert_eq!(restored.get_torrent(info_hash).unwrap().completed, 5);
+    assert_eq!(restored.sqlx.load_users(restored.clone()).await.unwrap(), 1);
+    let users = restored.users.read().clone();
+    let restored_user = users.values().next().unwrap();
+    assert_eq!((restored_user.user_uuid.clone(), restored_user.key, restored_user.uploaded), (Some(uuid), user_entry_item.key, 10));
+
+    tracker.sqlx.save_users(tracker.clone(), BTreeMap::from([(UserId([0x29; 20]), (user_entry_item, UpdatesAction::Remove))])).await.unwrap();
+    let emptied = sqlite_tracker(&path).await;
+    assert_eq!(emptied.sqlx.load_users(emptied.clone()).await.unwrap(), 0);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn sqlite_migrations_follow_the_schema_version_and_custom_names()
+{
+    let path = std::env::temp_dir().join(format!("torrust-actix-migrate-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut config = Configuration::init();
+    config.database.path = format!("sqlite://{}", path.display());
+    config.database.insert_vacant = true;
+    config.database_structure.torrents.table_name = String::from("swarms");
+    config.database_structure.users.table_name = String::from("members");
+    config.database_structure.users.column_key = String::from("passkey");
+    config.database_structure.schema_version.table_name = String::from("migrations");
+    let tracker = Arc::new(TorrentTracker::new(Arc::new(config.clone()), false).await);
+
+    let pending = tracker.sqlx.migrate(tracker.clone(), true).await.unwrap();
+    assert_eq!(pending.iter().map(|migration| migration.version).collect::<Vec<u64>>(), vec![1]);
+    assert!(pending[0].statements[0].starts_with("CREATE TABLE IF NOT EXISTS `swarms`"));
+    assert!(pending[0].statements[4].contains("`members`") && pending[0].statements[4].contains("`passkey` BLOB NOT NULL"));
+    assert_eq!(tracker.sqlx.schema_version().await.unwrap(), 0);
+
+    assert_eq!(tracker.sqlx.migrate(tracker.clone(), false).await.unwrap(), pending);
+    assert_eq!(tracker.sqlx.schema_version().await.unwrap(), 1);
+    assert!(tracker.sqlx.migrate(tracker.clone(), false).await.unwrap().is_empty());
+    let mut torrent_entry = TorrentEntry::new();
+    torrent_entry.completed = 3;
+    tracker.sqlx.save_torrents(tracker.clone(), BTreeMap::from([(InfoHash([0x31; 20]), (torrent_entry, UpdatesAction::Add))])).await.unwrap();
+    assert_eq!(tracker.sqlx.load_torrents(tracker.clone()).await.unwrap(), (1, 3));
+
+    let connector = DatabaseConnectorSQLite { pool: DatabaseConnectorSQLite::create(config.database.path.as_str()).await.unwrap(), schema_version_table: String::from("migrations") };
+    assert!(sqlx::query("SELECT `version` FROM `schema_version`").fetch_all(&connector.pool).await.is_err());
+    assert_eq!(sqlx::query("SELECT `version` FROM `migrations`").fetch_all(&connector.pool).await.unwrap().len(), 1);
+    let mut migrations = DatabaseConnectorSQLite::migrations(&config);
+    migrations.push(DatabaseMigration {
+        version: 2,
+        name: String::from("members_note"),
+        statements: vec![String::from("ALTER TABLE `members` ADD COLUMN `note` TEXT NOT NULL DEFAULT ''")],
+    });
+    let applied = connector.apply_migrations(migrations, false).await.unwrap();
+    assert_eq!(applied.iter().map(|migration| migration.version).collect::<Vec<u64>>(), vec![2]);
+    assert_eq!(connector.get_schema_version().await.unwrap(), 2);
+    assert!(sqlx::query("SELECT `note` FROM `members`").fetch_all(&connector.pool).await.is_ok());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn sqlite_flushes_torrents_and_lists_in_batches()
+{
+    let path = std::env::temp_dir().join(format!("torrust-actix-batch-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut config = Configuration::init();
+    config.database.path = format!("sqlite://{}", path.display());
+    config.database.insert_vacant = true;
+    config.database.remove_action = true;
+    config.database.update_peers = true;
+    config.database.batch_size = 7;
+    let tracker = Arc::new(TorrentTracker::new(Arc::new(config), false).await);
+    let pool = DatabaseConnectorSQLite::create(format!("sqlite://{}", path.display()).as_str()).await.unwrap();
+    sqlx::query("CREATE TABLE `torrents` (`infohash` BLOB PRIMARY KEY NOT NULL, `seeds` INTEGER DEFAULT 0, `peers` INTEGER DEFAULT 0, `completed` INTEGER DEFAULT 0)").execute(&pool).await.unwrap();
+    sqlx::query("CREATE TABLE `whitelist` (`infohash` BLOB PRIMARY KEY NOT NULL)").execute(&pool).await.unwrap();
+
+    let torrents: BTreeMap<InfoHash, (TorrentEntry, UpdatesAction)> = (0..50u8).map(|index| {
+        let mut torrent_entry = TorrentEntry::new();
+        torrent_entry.completed = index as u64;
+        (InfoHash([index; 20]), (torrent_entry, UpdatesAction::Add))
+    }).collect();
+    tracker.sqlx.save_torrents(tracker.clone(), torrents).await.unwrap();
+    let updates: BTreeMap<InfoHash, (TorrentEntry, UpdatesAction)> = (0..20u8).map(|index| {
+        let mut torrent_entry = TorrentEntry::new();
+        torrent_entry.completed = 100;
+        (InfoHash([index; 20]), (torrent_entry, match index < 10 { true => UpdatesAction::Remove, false => UpdatesAction::Update }))
+    }).collect();
+    tracker.sqlx.save_torrents(tracker.clone(), updates).await.unwrap();
+    let whitelist = vec![(InfoHash([1; 20]), UpdatesAction::Add), (InfoHash([2; 20]), UpdatesAction::Add), (InfoHash([1; 20]), UpdatesAction::Remove)];
+    assert_eq!(tracker.sqlx.save_whitelist(tracker.clone(), whitelist).await.unwrap(), 3);
+
+    let restored = sqlite_tracker(&path).await;
+    assert_eq!(restored.sqlx.load_torrents(restored.clone()).await.unwrap(), (40, 10 * 100 + (20..50).sum::<u64>()));
+    assert!(restored.get_torrent(InfoHash([5; 20])).is_none());
+    assert_eq!(restored.get_torrent(InfoHash([15; 20])).unwrap().completed, 100);
+    assert_eq!(restored.sqlx.load_whitelist(restored.clone()).await.unwrap(), 1);
+    assert!(restored.check_whitelist(InfoHash([2; 20])));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn sqlite_failed_list_commit_keeps_the_queued_updates()
+{
+    let path = std::env::temp_dir().join(format!("torrust-actix-commit-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let tracker = sqlite_tracker(&path).await;
+    let pool = DatabaseConnectorSQLite::create(format!("sqlite://{}", path.display()).as_str()).await.unwrap();
+    sqlx::query("CREATE TABLE `whitelist` (`infohash` BLOB PRIMARY KEY NOT NULL)").execute(&pool).await.unwrap();
+    // A deferred foreign key is only checked on COMMIT, so the inserts succeed and the commit fails.
+    sqlx::query("CREATE TABLE `parent` (`id` INTEGER PRIMARY KEY)").execute(&pool).await.unwrap();
+    sqlx::query("CREATE TABLE `child` (`parent` INTEGER REFERENCES `parent` (`id`) DEFERRABLE INITIALLY DEFERRED)").execute(&pool).await.unwrap();
+    sqlx::query("CREATE TRIGGER `whitelist_child` AFTER INSERT ON `whitelist` BEGIN INSERT INTO `child` VALUES (1); END").execute(&pool).await.unwrap();
+
+    tracker.add_whitelist_updates(vec![(InfoHash([1; 20]), UpdatesAction::Add), (InfoHash([2; 20]), UpdatesAction::Add)]);
+    assert!(tracker.save_whitelist_updates(tracker.clone()).await.is_err());
+    assert_eq!(tracker.get_whitelist_updates().len(), 2);
+    let restored = sqlite_tracker(&path).await;
+    assert_eq!(restored.sqlx.load_whitelist(restored.clone()).await.unwrap(), 0);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+#[should_panic(expected = "[DB: users] Column: uuid")]
+fn database_structure_rejects_identifiers_that_are_not_plain_names()
+{
+    let mut config = Configuration::init();
+    config.database_structure.users.column_uuid = String::from("uuid` = '' OR 1=1; --");
+    Configuration::validate(config);
+}
+
+#[test]
+#[should_panic(expected = "[DB: schema_version]")]
+fn database_structure_rejects_a_schema_version_table_that_is_not_a_plain_name()
+{
+    let mut config = Configuration::init();
+    config.database_structure.schema_version.table_name = String::from("schema_version`; DROP TABLE `torrents");
+    Configuration::validate(config);
+}
+
+#[test]
+#[should_panic(expected = "batch_size")]
+fn database_rejects_batch_sizes_outside_the_allowed_range()
+{
+    let mut config = Configuration::init();
+    config.database.batch_size = 0;
+    Configuration::validate(config);
+}
@@ -0,0 +1,55 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use async_trait::async_trait;
+use sqlx::Error;
+use crate::database::structs::database_migration::DatabaseMigration;
+use crate::tracker::enums::updates_action::UpdatesAction;
+use crate::tracker::structs::info_hash::InfoHash;
+use crate::tracker::structs::torrent_entry::TorrentEntry;
+use crate::tracker::structs::torrent_tracker::TorrentTracker;
+use crate::tracker::structs::user_entry_item::UserEntryItem;
+use crate::tracker::structs::user_id::UserId;
+
+/// Storage used by the tracker to load its state on boot and to flush the pending updates.
+/// The sqlx engines implement it, other backends can be handed to `TorrentTracker::new_with_backend`.
+#[async_trait]
+pub trait DatabaseBackend: Debug + Send + Sync {
+    /// Loads the torrents into the tracker, returns the amount of torrents and the completed count.
+    async fn load_torrents(&self, tracker: Arc<TorrentTracker>) -> Result<(u64, u64), Error>;
+
+    async fn load_whitelist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>;
+
+    async fn load_blacklist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>;
+
+    async fn load_keys(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>;
+
+    async fn load_users(&self, tracker: Arc<TorrentTracker>) -> Result<u64, Error>;
+
+    async fn save_whitelist(&self, tracker: Arc<TorrentTracker>, whitelists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>;
+
+    async fn save_blacklist(&self, tracker: Arc<TorrentTracker>, blacklists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, Error>;
+
+    async fn save_keys(&self, tracker: Arc<TorrentTracker>, keys: BTreeMap<InfoHash, (i64, UpdatesAction)>) -> Result<u64, Error>;
+
+    async fn save_torrents(&self, tracker: Arc<TorrentTracker>, torrents: BTreeMap<InfoHash, (TorrentEntry, UpdatesAction)>) -> Result<(), Error>;
+
+    async fn save_users(&self, tracker: Arc<TorrentTracker>, users: BTreeMap<UserId, (UserEntryItem, UpdatesAction)>) -> Result<(), Error>;
+
+    /// Resets the seeds and peers counters of every stored torrent.
+    async fn reset_seeds_peers(&self, tracker: Arc<TorrentTracker>) -> Result<(), Error>;
+
+    /// Returns the latest applied schema migration, 0 when the `database_structure.schema_version` table does not exist yet.
+    /// Backends without a schema always report 0.
+    async fn schema_version(&self) -> Result<u64, Error>
+    {
+        Ok(0)
+    }
+
+    /// Applies the schema migrations newer than `schema_version` in order and returns them.
+    /// With `dry_run` the pending migrations are only returned. Backends without a schema have nothing to migrate.
+    async fn migrate(&self, _tracker: Arc<TorrentTracker>, _dry_run: bool) -> Result<Vec<DatabaseMigration>, Error>
+    {
+        Ok(Vec::new())
+    }
+}
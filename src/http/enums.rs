@@ -0,0 +1 @@
+pub mod client_address_policy;
\ No newline at end of file
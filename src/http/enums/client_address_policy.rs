@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[allow(non_camel_case_types)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClientAddressPolicy {
+    #[default]
+    ignore,
+    other_family,
+    trust,
+}
@@ -1,8 +1,8 @@
 use std::borrow::Cow;
 use std::fs::File;
 use std::future::Future;
-use std::io::{BufReader, Write};
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::io::BufReader;
+use std::net::{IpAddr, SocketAddr};
 use std::process::exit;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -11,19 +11,26 @@ use actix_cors::Cors;
 use actix_web::{App, http, HttpRequest, HttpResponse, HttpServer, web};
 use actix_web::dev::ServerHandle;
 use actix_web::http::header::ContentType;
-use actix_web::web::{Data, ServiceConfig};
-use bip_bencode::{ben_bytes, ben_int, ben_list, ben_map, BMutAccess};
+use actix_web::web::{Bytes, Data, ServiceConfig};
+use bip_bencode::{ben_bytes, ben_int, ben_map, BMutAccess};
 use log::{debug, error, info};
 use crate::common::common::parse_query;
+use crate::common::structs::cidr::Cidr;
 use crate::common::structs::custom_error::CustomError;
 use crate::config::structs::http_trackers_config::HttpTrackersConfig;
+use crate::http::enums::client_address_policy::ClientAddressPolicy;
 use crate::http::structs::http_service_data::HttpServiceData;
 use crate::http::types::{HttpServiceQueryHashingMapErr, HttpServiceQueryHashingMapOk};
+use crate::stats::enums::error_family::ErrorFamily;
 use crate::stats::enums::stats_event::StatsEvent;
+use crate::tracker::enums::request_interval_throttle::RequestIntervalThrottle;
 use crate::tracker::enums::torrent_peers_type::TorrentPeersType;
+use crate::tracker::structs::announce_query_request::AnnounceQueryRequest;
+use crate::tracker::structs::announce_response::AnnounceResponse;
 use crate::tracker::structs::info_hash::InfoHash;
 use crate::tracker::structs::torrent_tracker::TorrentTracker;
 use crate::tracker::structs::user_id::UserId;
+use crate::udp::enums::server_error::ServerError;
 
 #[tracing::instrument(level = "debug")]
 pub fn http_service_cors() -> Cors
@@ -207,7 +214,7 @@ pub async fn http_service_announce_key(request: HttpRequest, path: web::Path<Str
         let key = path.clone();
         let key_check = http_service_check_key_validation(data.torrent_tracker.clone(), key).await;
         if let Some(value) = key_check {
-            http_stat_update(ip, data.torrent_tracker.clone(), StatsEvent::Tcp4Failure, StatsEvent::Tcp6Failure, 1);
+            http_stat_failure(ip, data.torrent_tracker.clone(), ServerError::UnknownKey);
             return value;
         }
     }
@@ -216,11 +223,11 @@ pub async fn http_service_announce_key(request: HttpRequest, path: web::Path<Str
         let user_key = path.clone();
         let user_key_check = http_service_check_user_key_validation(data.torrent_tracker.clone(), user_key.clone()).await;
         if user_key_check.is_none() {
-            return http_service_announce_handler(request, ip, data.torrent_tracker.clone(), Some(http_service_decode_hex_user_id(user_key.clone()).await.unwrap())).await;
+            return http_service_announce_handler(request, ip, data.torrent_tracker.clone(), data.http_trackers_config.clone(), Some(http_service_decode_hex_user_id(user_key.clone()).await.unwrap())).await;
         }
     }
 
-    http_service_announce_handler(request, ip, data.torrent_tracker.clone(), None).await
+    http_service_announce_handler(request, ip, data.torrent_tracker.clone(), data.http_trackers_config.clone(), None).await
 }
 
 #[tracing::instrument(level = "debug")]
@@ -238,7 +245,7 @@ pub async fn http_service_announce_userkey(request: HttpRequest, path: web::Path
         let key = path.clone().0;
         let key_check = http_service_check_key_validation(data.torrent_tracker.clone(), key).await;
         if let Some(value) = key_check {
-            http_stat_update(ip, data.torrent_tracker.clone(), StatsEvent::Tcp4Failure, StatsEvent::Tcp6Failure, 1);
+            http_stat_failure(ip, data.torrent_tracker.clone(), ServerError::UnknownKey);
             return value;
         }
     }
@@ -247,11 +254,11 @@ pub async fn http_service_announce_userkey(request: HttpRequest, path: web::Path
         let user_key = path.clone().1;
         let user_key_check = http_service_check_user_key_validation(data.torrent_tracker.clone(), user_key.clone()).await;
         if user_key_check.is_none() {
-            return http_service_announce_handler(request, ip, data.torrent_tracker.clone(), Some(http_service_decode_hex_user_id(user_key.clone()).await.unwrap())).await;
+            return http_service_announce_handler(request, ip, data.torrent_tracker.clone(), data.http_trackers_config.clone(), Some(http_service_decode_hex_user_id(user_key.clone()).await.unwrap())).await;
         }
     }
 
-    http_service_announce_handler(request, ip, data.torrent_tracker.clone(), None).await
+    http_service_announce_handler(request, ip, data.torrent_tracker.clone(), data.http_trackers_config.clone(), None).await
 }
 
 #[tracing::instrument(level = "debug")]
@@ -269,31 +276,32 @@ pub async fn http_service_announce(request: HttpRequest, data: Data<Arc<HttpServ
     };
 
     if data.torrent_tracker.config.tracker_config.clone().keys_enabled {
-        http_stat_update(ip, data.torrent_tracker.clone(), StatsEvent::Tcp4Failure, StatsEvent::Tcp6Failure, 1);
+        http_stat_failure(ip, data.torrent_tracker.clone(), ServerError::UnknownKey);
         return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map!{
             "failure reason" => ben_bytes!("missing key")
         }.encode());
     }
 
-    http_service_announce_handler(request, ip, data.torrent_tracker.clone(), None).await
+    http_service_announce_handler(request, ip, data.torrent_tracker.clone(), data.http_trackers_config.clone(), None).await
 }
 
 #[tracing::instrument(level = "debug")]
-pub async fn http_service_announce_handler(request: HttpRequest, ip: IpAddr, data: Arc<TorrentTracker>, user_key: Option<UserId>) -> HttpResponse
+pub async fn http_service_announce_handler(request: HttpRequest, ip: IpAddr, data: Arc<TorrentTracker>, http_trackers_config: Arc<HttpTrackersConfig>, user_key: Option<UserId>) -> HttpResponse
 {
     let query_map_result = parse_query(Some(request.query_string().to_string()));
     let query_map = match http_service_query_hashing(query_map_result) {
         Ok(result) => { result }
         Err(err) => {
-            http_stat_update(ip, data.clone(), StatsEvent::Tcp4Failure, StatsEvent::Tcp6Failure, 1);
+            http_stat_failure(ip, data.clone(), ServerError::BadRequest);
             return err;
         }
     };
 
     let announce = data.validate_announce(ip, query_map).await;
     let announce_unwrapped = match announce {
-        Ok(result) => { result }
+        Ok(result) => { http_service_announce_client_addresses(result, http_trackers_config.client_address_policy) }
         Err(e) => {
+            http_stat_failure(ip, data.clone(), ServerError::BadRequest);
             return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
                 "failure reason" => ben_bytes!(e.to_string())
             }.encode());
@@ -301,253 +309,118 @@ pub async fn http_service_announce_handler(request: HttpRequest, ip: IpAddr, dat
     };
 
     if data.config.tracker_config.clone().whitelist_enabled && !data.check_whitelist(announce_unwrapped.info_hash) {
+        http_stat_failure(ip, data.clone(), ServerError::TorrentNotWhitelisted);
         return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
             "failure reason" => ben_bytes!("unknown info_hash")
         }.encode());
     }
 
     if data.config.tracker_config.clone().blacklist_enabled && data.check_blacklist(announce_unwrapped.info_hash) {
+        http_stat_failure(ip, data.clone(), ServerError::TorrentBlacklisted);
         return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
             "failure reason" => ben_bytes!("forbidden info_hash")
         }.encode());
     }
 
-    let (_torrent_peer, torrent_entry) = match data.handle_announce(data.clone(), announce_unwrapped.clone(), user_key).await {
-        Ok(result) => { result }
-        Err(e) => {
-            http_stat_update(ip, data.clone(), StatsEvent::Tcp4Failure, StatsEvent::Tcp6Failure, 1);
-            return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
-                "failure reason" => ben_bytes!(e.to_string())
-            }.encode());
-        }
+    let requester_ipv4 = announce_unwrapped.remote_addr.is_ipv4() || announce_unwrapped.remote_addr_alternate.is_some_and(|addr| addr.is_ipv4());
+    let requester_ipv6 = announce_unwrapped.remote_addr.is_ipv6() || announce_unwrapped.remote_addr_alternate.is_some_and(|addr| addr.is_ipv6());
+    let peers_type = match (requester_ipv4, requester_ipv6) {
+        (true, true) => { TorrentPeersType::All }
+        (true, false) => { TorrentPeersType::IPv4 }
+        _ => { TorrentPeersType::IPv6 }
     };
 
-    if announce_unwrapped.compact {
-        let mut peers_list: Vec<u8> = Vec::new();
-        return match ip {
-            IpAddr::V4(_) => {
-                if announce_unwrapped.left != 0 {
-                    let seeds = data.get_peers(
-                        torrent_entry.seeds.clone(),
-                        TorrentPeersType::IPv4,
-                        Some(ip),
-                        72
-                    );
-                    if seeds.is_some() {
-                        for (_, torrent_peer) in seeds.unwrap().iter() {
-                            let peer_pre_parse = match torrent_peer.peer_addr.ip().to_string().parse::<Ipv4Addr>() {
-                                Ok(ip) => { ip }
-                                Err(e) => {
-                                    error!("[IPV4 Error] {} - {}", torrent_peer.peer_addr.ip(), e);
-                                    return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
-                                        "failure reason" => ben_bytes!(e.to_string())
-                                    }.encode());
-                                }
-                            };
-                            let _ = peers_list.write(&u32::from(peer_pre_parse).to_be_bytes());
-                            peers_list.write_all(&announce_unwrapped.clone().port.to_be_bytes()).unwrap();
-                        }
-                    }
-                }
-                if peers_list.len() != 72 {
-                    let peers = data.get_peers(
-                        torrent_entry.peers.clone(),
-                        TorrentPeersType::IPv4,
-                        Some(ip),
-                        72
-                    );
-                    if peers.is_some() {
-                        for (_, torrent_peer) in peers.unwrap().iter() {
-                            if peers_list.len() != 72 {
-                                let peer_pre_parse = match torrent_peer.peer_addr.ip().to_string().parse::<Ipv4Addr>() {
-                                    Ok(ip) => { ip }
-                                    Err(e) => {
-                                        error!("[IPV4 Error] {} - {}", torrent_peer.peer_addr.ip(), e);
-                                        return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
-                                            "failure reason" => ben_bytes!(e.to_string())
-                                        }.encode());
-                                    }
-                                };
-                                let _ = peers_list.write(&u32::from(peer_pre_parse).to_be_bytes());
-                                peers_list.write_all(&announce_unwrapped.clone().port.to_be_bytes()).unwrap();
-                                continue;
-                            }
-                            break;
-                        }
-                    }
-                }
-                HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
-                    "interval" => ben_int!(data.config.tracker_config.clone().request_interval as i64),
-                    "min interval" => ben_int!(data.config.tracker_config.clone().request_interval_minimum as i64),
-                    "complete" => ben_int!(torrent_entry.seeds.len() as i64),
-                    "incomplete" => ben_int!(torrent_entry.clone().peers.len() as i64),
-                    "downloaded" => ben_int!(torrent_entry.completed as i64),
-                    "peers" => ben_bytes!(peers_list)
-                }.encode())
-            }
-            IpAddr::V6(_) => {
-                if announce_unwrapped.left != 0 {
-                    let seeds = data.get_peers(
-                        torrent_entry.seeds.clone(),
-                        TorrentPeersType::IPv6,
-                        Some(ip),
-                        72
-                    );
-                    if seeds.is_some() {
-                        for (_, torrent_peer) in seeds.unwrap().iter() {
-                            let peer_pre_parse = match torrent_peer.peer_addr.ip().to_string().parse::<Ipv6Addr>() {
-                                Ok(ip) => { ip }
-                                Err(e) => {
-                                    error!("[IPV6 Error] {} - {}", torrent_peer.peer_addr.ip(), e);
-                                    return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
-                                            "failure reason" => ben_bytes!(e.to_string())
-                                        }.encode());
-                                }
-                            };
-                            let _ = peers_list.write(&u128::from(peer_pre_parse).to_be_bytes());
-                            peers_list.write_all(&announce_unwrapped.clone().port.to_be_bytes()).unwrap();
-                        }
-                    }
-                }
-                if peers_list.len() != 72 {
-                    let peers = data.get_peers(
-                        torrent_entry.peers.clone(),
-                        TorrentPeersType::IPv6,
-                        Some(ip),
-                        72
-                    );
-                    if peers.is_some() {
-                        for (_, torrent_peer) in peers.unwrap().iter() {
-                            if peers_list.len() != 72 {
-                                let peer_pre_parse = match torrent_peer.peer_addr.ip().to_string().parse::<Ipv6Addr>() {
-                                    Ok(ip) => { ip }
-                                    Err(e) => {
-                                        error!("[IPV6 Error] {} - {}", torrent_peer.peer_addr.ip(), e);
-                                        return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
-                                            "failure reason" => ben_bytes!(e.to_string())
-                                        }.encode());
-                                    }
-                                };
-                                let _ = peers_list.write(&u128::from(peer_pre_parse).to_be_bytes());
-                                peers_list.write_all(&announce_unwrapped.clone().port.to_be_bytes()).unwrap();
-                                continue;
-                            }
-                            break;
-                        }
-                    }
+    let request_interval_throttle = data.config.tracker_config.request_interval_throttle;
+    let throttled = data.check_announce_throttle(&announce_unwrapped, peers_type);
+    if throttled.is_some() && request_interval_throttle == RequestIntervalThrottle::failure {
+        http_stat_failure(ip, data.clone(), ServerError::AnnounceThrottled);
+        return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
+            "failure reason" => ben_bytes!("announce interval too short")
+        }.encode());
+    }
+    let throttled_warning = throttled.is_some() && request_interval_throttle == RequestIntervalThrottle::warning;
+
+    let (torrent_peer, announce_selection) = match throttled {
+        Some(result) => { result }
+        None => {
+            match data.handle_announce(data.clone(), announce_unwrapped.clone(), peers_type, user_key, true).await {
+                Ok(result) => { result }
+                Err(e) => {
+                    http_stat_failure(ip, data.clone(), ServerError::InternalServerError);
+                    return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
+                        "failure reason" => ben_bytes!(e.to_string())
+                    }.encode());
                 }
-                HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
-                    "interval" => ben_int!(data.config.tracker_config.clone().request_interval as i64),
-                    "min interval" => ben_int!(data.config.tracker_config.clone().request_interval_minimum as i64),
-                    "complete" => ben_int!(torrent_entry.seeds.len() as i64),
-                    "incomplete" => ben_int!(torrent_entry.peers.len() as i64),
-                    "downloaded" => ben_int!(torrent_entry.completed as i64),
-                    "peers6" => ben_bytes!(peers_list)
-                }.encode())
             }
         }
+    };
+
+    let mut response = ben_map! {
+        "interval" => ben_int!(data.config.tracker_config.clone().request_interval as i64),
+        "min interval" => ben_int!(data.config.tracker_config.clone().request_interval_minimum as i64),
+        "complete" => ben_int!(announce_selection.seeds as i64),
+        "incomplete" => ben_int!(announce_selection.peers as i64),
+        "downloaded" => ben_int!(announce_selection.completed as i64),
+        "external ip" => ben_bytes!(match ip {
+            IpAddr::V4(ipv4) => { ipv4.octets().to_vec() }
+            IpAddr::V6(ipv6) => { ipv6.octets().to_vec() }
+        })
+    };
+    let response_mut = response.dict_mut().unwrap();
+    if throttled_warning {
+        response_mut.insert(Cow::from("warning message".as_bytes()), ben_bytes!("announce interval too short"));
+    }
+    if let Some(tracker_id) = torrent_peer.tracker_id {
+        response_mut.insert(Cow::from("tracker id".as_bytes()), ben_bytes!(format!("{:016x}", tracker_id)));
     }
 
-    let mut peers_list = ben_list!();
-    let peers_list_mut = peers_list.list_mut().unwrap();
-    match ip {
-        IpAddr::V4(_) => {
-            if announce_unwrapped.left != 0 {
-                let seeds = data.get_peers(
-                    torrent_entry.seeds.clone(),
-                    TorrentPeersType::IPv4,
-                    Some(ip),
-                    72
-                );
-                if seeds.is_some() {
-                    for (peer_id, torrent_peer) in seeds.unwrap().iter() {
-                        peers_list_mut.push(ben_map! {
-                            "peer id" => ben_bytes!(peer_id.to_string()),
-                            "ip" => ben_bytes!(torrent_peer.peer_addr.ip().to_string()),
-                            "port" => ben_int!(torrent_peer.peer_addr.port() as i64)
-                        });
-                    }
-                }
-            }
-            if peers_list_mut.len() != 72 {
-                let peers = data.get_peers(
-                    torrent_entry.peers.clone(),
-                    TorrentPeersType::IPv4,
-                    Some(ip),
-                    72
-                );
-                if peers.is_some() {
-                    for (peer_id, torrent_peer) in peers.unwrap().iter() {
-                        if peers_list_mut.len() != 72 {
-                            peers_list_mut.push(ben_map! {
-                                "peer id" => ben_bytes!(peer_id.to_string()),
-                                "ip" => ben_bytes!(torrent_peer.peer_addr.ip().to_string()),
-                                "port" => ben_int!(torrent_peer.peer_addr.port() as i64)
-                            });
-                            continue;
-                        }
-                        break;
-                    }
+    let announce_response = AnnounceResponse::new(announce_selection.peers_selection);
+    if announce_unwrapped.compact {
+        if requester_ipv4 {
+            response_mut.insert(Cow::from("peers".as_bytes()), ben_bytes!(announce_response.compact_ipv4()));
+        }
+        if requester_ipv6 {
+            response_mut.insert(Cow::from("peers6".as_bytes()), ben_bytes!(announce_response.compact_ipv6()));
+        }
+    } else {
+        if requester_ipv4 {
+            response_mut.insert(Cow::from("peers".as_bytes()), announce_response.dict_ipv4(announce_unwrapped.no_peer_id));
+        }
+        if requester_ipv6 {
+            response_mut.insert(Cow::from("peers6".as_bytes()), announce_response.dict_ipv6(announce_unwrapped.no_peer_id));
+        }
+    }
+    HttpResponse::Ok().content_type(ContentType::plaintext()).body(response.encode())
+}
+
+#[tracing::instrument(level = "debug")]
+pub fn http_service_announce_client_addresses(mut announce: AnnounceQueryRequest, client_address_policy: ClientAddressPolicy) -> AnnounceQueryRequest
+{
+    match client_address_policy {
+        ClientAddressPolicy::ignore => {}
+        ClientAddressPolicy::other_family => {
+            for addr in [announce.ip, announce.ipv4, announce.ipv6].into_iter().flatten() {
+                if addr.is_ipv4() != announce.remote_addr.is_ipv4() {
+                    announce.remote_addr_alternate = Some(addr);
                 }
             }
-            HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
-                "interval" => ben_int!(data.config.tracker_config.clone().request_interval as i64),
-                "min interval" => ben_int!(data.config.tracker_config.clone().request_interval_minimum as i64),
-                "complete" => ben_int!(torrent_entry.seeds.len() as i64),
-                "incomplete" => ben_int!(torrent_entry.peers.len() as i64),
-                "downloaded" => ben_int!(torrent_entry.completed as i64),
-                "peers" => peers_list
-            }.encode())
         }
-        IpAddr::V6(_) => {
-            if announce_unwrapped.left != 0 {
-                let seeds = data.get_peers(
-                    torrent_entry.seeds.clone(),
-                    TorrentPeersType::IPv6,
-                    Some(ip),
-                    72
-                );
-                if seeds.is_some() {
-                    for (peer_id, torrent_peer) in seeds.unwrap().iter() {
-                        peers_list_mut.push(ben_map! {
-                            "peer id" => ben_bytes!(peer_id.to_string()),
-                            "ip" => ben_bytes!(torrent_peer.peer_addr.ip().to_string()),
-                            "port" => ben_int!(torrent_peer.peer_addr.port() as i64)
-                        });
-                    }
-                }
+        ClientAddressPolicy::trust => {
+            if let Some(addr) = announce.ip {
+                announce.remote_addr = addr.ip();
+                announce.port = addr.port();
             }
-            if peers_list_mut.len() != 72 {
-                let peers = data.get_peers(
-                    torrent_entry.peers.clone(),
-                    TorrentPeersType::IPv6,
-                    Some(ip),
-                    72
-                );
-                if peers.is_some() {
-                    for (peer_id, torrent_peer) in peers.unwrap().iter() {
-                        if peers_list_mut.len() != 72 {
-                            peers_list_mut.push(ben_map! {
-                                "peer id" => ben_bytes!(peer_id.to_string()),
-                                "ip" => ben_bytes!(torrent_peer.peer_addr.ip().to_string()),
-                                "port" => ben_int!(torrent_peer.peer_addr.port() as i64)
-                            });
-                            continue;
-                        }
-                        break;
-                    }
+            for addr in [announce.ipv4, announce.ipv6].into_iter().flatten() {
+                if addr.is_ipv4() == announce.remote_addr.is_ipv4() {
+                    announce.remote_addr = addr.ip();
+                    announce.port = addr.port();
+                } else {
+                    announce.remote_addr_alternate = Some(addr);
                 }
             }
-            HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
-                "interval" => ben_int!(data.config.tracker_config.clone().request_interval as i64),
-                "min interval" => ben_int!(data.config.tracker_config.clone().request_interval_minimum as i64),
-                "complete" => ben_int!(torrent_entry.seeds.len() as i64),
-                "incomplete" => ben_int!(torrent_entry.peers.len() as i64),
-                "downloaded" => ben_int!(torrent_entry.completed as i64),
-                "peers6" => peers_list
-            }.encode())
         }
     }
+    announce
 }
 
 #[tracing::instrument(level = "debug")]
@@ -575,7 +448,10 @@ pub async fn http_service_scrape_key(request: HttpRequest, path: web::Path<Strin
     if data.torrent_tracker.config.tracker_config.clone().keys_enabled {
         let key = path.into_inner();
         let key_check = http_service_check_key_validation(data.torrent_tracker.clone(), key).await;
-        if let Some(value) = key_check { return value; }
+        if let Some(value) = key_check {
+            http_stat_failure(ip, data.torrent_tracker.clone(), ServerError::UnknownKey);
+            return value;
+        }
     }
 
     http_service_scrape_handler(request, ip, data.torrent_tracker.clone()).await
@@ -588,14 +464,18 @@ pub async fn http_service_scrape_handler(request: HttpRequest, ip: IpAddr, data:
     let query_map = match http_service_query_hashing(query_map_result) {
         Ok(result) => { result }
         Err(err) => {
-            http_stat_update(ip, data.clone(), StatsEvent::Tcp4Failure, StatsEvent::Tcp6Failure, 1);
+            http_stat_failure(ip, data.clone(), ServerError::BadRequest);
             return err;
         }
     };
 
+    if data.config.tracker_config.full_scrape_enabled && !query_map.contains_key("info_hash") {
+        return http_service_full_scrape(ip, query_map, data);
+    }
+
     let scrape = data.validate_scrape(query_map).await;
     if scrape.is_err() {
-        http_stat_update(ip, data.clone(), StatsEvent::Tcp4Failure, StatsEvent::Tcp6Failure, 1);
+        http_stat_failure(ip, data.clone(), ServerError::BadRequest);
         return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
             "failure reason" => ben_bytes!(scrape.unwrap_err().to_string())
         }.encode());
@@ -620,7 +500,7 @@ pub async fn http_service_scrape_handler(request: HttpRequest, ip: IpAddr, data:
             }.encode())
         }
         Err(e) => {
-            http_stat_update(ip, data.clone(), StatsEvent::Tcp4Failure, StatsEvent::Tcp6Failure, 1);
+            http_stat_failure(ip, data.clone(), ServerError::BadRequest);
             HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
                 "failure reason" => ben_bytes!(e.to_string())
             }.encode())
@@ -628,6 +508,33 @@ pub async fn http_service_scrape_handler(request: HttpRequest, ip: IpAddr, data:
     }
 }
 
+#[tracing::instrument(level = "debug")]
+pub fn http_service_full_scrape(ip: IpAddr, query_map: HttpServiceQueryHashingMapOk, data: Arc<TorrentTracker>) -> HttpResponse
+{
+    let tracker_config = &data.config.tracker_config;
+    let key_allowed = !tracker_config.full_scrape_key.is_empty() && query_map.get("key")
+        .and_then(|key| key.first())
+        .is_some_and(|key| key.as_slice() == tracker_config.full_scrape_key.as_bytes());
+    if !key_allowed && !Cidr::contains_any(&data.full_scrape_cidrs, ip) {
+        http_stat_failure(ip, data.clone(), ServerError::PeerNotAuthenticated);
+        return HttpResponse::Ok().content_type(ContentType::plaintext()).body(ben_map! {
+            "failure reason" => ben_bytes!("full scrape not allowed")
+        }.encode());
+    }
+
+    let header = Bytes::from_static(b"d5:filesd");
+    let footer = Bytes::from(format!(
+        "e8:intervali{}e12:min intervali{}ee",
+        tracker_config.request_interval,
+        tracker_config.request_interval_minimum
+    ));
+    let chunks = std::iter::once(header)
+        .chain(data.get_full_scrape())
+        .chain(std::iter::once(footer))
+        .map(Ok::<Bytes, actix_web::Error>);
+    HttpResponse::Ok().content_type(ContentType::plaintext()).streaming(futures_util::stream::iter(chunks))
+}
+
 #[tracing::instrument(level = "debug")]
 pub async fn http_service_scrape(request: HttpRequest, data: Data<Arc<HttpServiceData>>) -> HttpResponse
 {
@@ -834,4 +741,13 @@ pub fn http_stat_update(ip: IpAddr, data: Arc<TorrentTracker>, stats_ipv4: Stats
             data_clone.update_stats(stat_ipv6, count);
         }
     }
-}
\ No newline at end of file
+}
+
+/// Counts a failed HTTP request. HTTP shares the error kinds of the UDP `ServerError`, counted under the tcp4 or tcp6
+/// family of the requester, kinds that only exist in UDP like `ConnectionIdInvalid` are never reported for TCP.
+#[tracing::instrument(level = "debug")]
+pub fn http_stat_failure(ip: IpAddr, data: Arc<TorrentTracker>, error: ServerError)
+{
+    http_stat_update(ip, data.clone(), StatsEvent::Tcp4Failure, StatsEvent::Tcp6Failure, 1);
+    data.update_error_stats(ErrorFamily::tcp(ip), error, 1);
+}
@@ -3,4 +3,5 @@ pub mod structs;
 pub mod impls;
 pub mod types;
 #[allow(clippy::module_inception)]
-pub mod http;
\ No newline at end of file
+pub mod http;
+pub mod tests;
\ No newline at end of file
@@ -0,0 +1,81 @@
+#![cfg(test)]
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+use actix_web::test::TestRequest;
+use bip_bencode::{BDecodeOpt, BRefAccess, BencodeRef};
+use crate::config::structs::configuration::Configuration;
+use crate::http::http::http_service_announce_handler;
+use crate::stats::enums::error_family::ErrorFamily;
+use crate::tracker::structs::torrent_tracker::TorrentTracker;
+use crate::udp::enums::server_error::ServerError;
+
+async fn test_tracker(config: Configuration) -> Arc<TorrentTracker>
+{
+    let mut config = config;
+    config.database.path = String::from("sqlite::memory:");
+    Arc::new(TorrentTracker::new(Arc::new(config), false).await)
+}
+
+/// Runs an announce of the leecher `[peer; 20]` for the info hash `[1; 20]` through the HTTP handler and returns the
+/// bencoded response body.
+async fn http_announce(tracker: Arc<TorrentTracker>, ip: IpAddr, peer: u8, extra: &str) -> Vec<u8>
+{
+    let uri = format!("/announce?info_hash={}&peer_id={}&port=6881&uploaded=0&downloaded=0&left=1&compact=1{}", "%01".repeat(20), format!("%{:02x}", peer).repeat(20), extra);
+    let http_trackers_config = Arc::new(tracker.config.http_server[0].clone());
+    let response = http_service_announce_handler(TestRequest::with_uri(&uri).to_http_request(), ip, tracker, http_trackers_config, None).await;
+    actix_web::body::to_bytes(response.into_body()).await.unwrap().to_vec()
+}
+
+#[tokio::test]
+async fn announce_reports_the_external_ip_in_the_requester_address_family()
+{
+    let tracker = test_tracker(Configuration::init()).await;
+
+    let body = http_announce(tracker.clone(), IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)), 1, "").await;
+    let decoded = BencodeRef::decode(&body, BDecodeOpt::default()).unwrap();
+    assert_eq!(decoded.dict().unwrap().lookup(b"external ip").unwrap().bytes().unwrap(), &[192, 0, 2, 1]);
+
+    let ipv6 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    let body = http_announce(tracker, IpAddr::V6(ipv6), 2, "").await;
+    let decoded = BencodeRef::decode(&body, BDecodeOpt::default()).unwrap();
+    assert_eq!(decoded.dict().unwrap().lookup(b"external ip").unwrap().bytes().unwrap(), &ipv6.octets());
+}
+
+#[tokio::test]
+async fn announce_with_a_zero_numwant_returns_no_peers()
+{
+    let tracker = test_tracker(Configuration::init()).await;
+    for peer in 1..=3 {
+        http_announce(tracker.clone(), IpAddr::V4(Ipv4Addr::new(192, 0, 2, peer)), peer, "").await;
+    }
+
+    let body = http_announce(tracker.clone(), IpAddr::V4(Ipv4Addr::new(192, 0, 2, 4)), 4, "&numwant=0").await;
+    let decoded = BencodeRef::decode(&body, BDecodeOpt::default()).unwrap();
+    assert_eq!(decoded.dict().unwrap().lookup(b"incomplete").unwrap().int(), Some(4));
+    assert!(decoded.dict().unwrap().lookup(b"peers").unwrap().bytes().unwrap().is_empty());
+
+    let body = http_announce(tracker, IpAddr::V4(Ipv4Addr::new(192, 0, 2, 4)), 4, "&numwant=2").await;
+    let decoded = BencodeRef::decode(&body, BDecodeOpt::default()).unwrap();
+    assert_eq!(decoded.dict().unwrap().lookup(b"peers").unwrap().bytes().unwrap().len(), 2 * 6);
+}
+
+#[tokio::test]
+async fn rejected_announce_counts_the_error_under_the_tcp_family()
+{
+    let mut config = Configuration::init();
+    config.tracker_config.whitelist_enabled = true;
+    let tracker = test_tracker(config).await;
+
+    let body = http_announce(tracker.clone(), IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)), 1, "").await;
+    let decoded = BencodeRef::decode(&body, BDecodeOpt::default()).unwrap();
+    assert!(decoded.dict().unwrap().lookup(b"failure reason").is_some());
+    http_announce(tracker.clone(), IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)), 2, "").await;
+    http_announce(tracker.clone(), IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2)), 3, "").await;
+
+    let stats = tracker.get_stats();
+    assert_eq!(stats.errors.get(ErrorFamily::Tcp4, ServerError::TorrentNotWhitelisted), 1);
+    assert_eq!(stats.errors.get(ErrorFamily::Tcp6, ServerError::TorrentNotWhitelisted), 2);
+    assert_eq!(stats.errors.get(ErrorFamily::Udp4, ServerError::TorrentNotWhitelisted), 0);
+    assert_eq!((stats.tcp4_failure, stats.tcp6_failure), (1, 2));
+}
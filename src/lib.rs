@@ -6,4 +6,5 @@ pub mod stats;
 pub mod api;
 pub mod http;
 pub mod udp;
-pub mod structs;
\ No newline at end of file
+pub mod structs;
+pub mod client;
@@ -57,6 +57,18 @@ fn main() -> std::io::Result<()>
         .block_on(async {
             let tracker = Arc::new(TorrentTracker::new(config.clone(), args.create_database).await);
 
+            if args.migrate { tracker.migrate(&args, tracker.clone()).await; }
+
+            if tracker.config.database.clone().persistent {
+                if tracker.config.database.clone().migrate_on_boot {
+                    if tracker.migrate_database(tracker.clone(), false).await.is_err() {
+                        panic!("[MIGRATE] Unable to continue loading");
+                    }
+                } else if let Ok(migrations) = tracker.migrate_database(tracker.clone(), true).await && !migrations.is_empty() {
+                    error!("[MIGRATE] {} schema migrations pending, start with --migrate or enable migrate_on_boot to apply them", migrations.len());
+                }
+            }
+
             if tracker.config.database.clone().persistent {
                 tracker.load_torrents(tracker.clone()).await;
                 if tracker.config.tracker_config.clone().whitelist_enabled {
@@ -78,6 +90,18 @@ fn main() -> std::io::Result<()>
                 tracker.set_stats(StatsEvent::Completed, config.tracker_config.clone().total_downloads as i64);
             }
 
+            if tracker.config.tracker_config.clone().peers_snapshot_enabled {
+                match tracker.load_peers_snapshot(tracker.config.tracker_config.peers_snapshot_file.as_str()) {
+                    Ok(_) => {}
+                    Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                        info!("[PEERS SNAPSHOT] No snapshot found at {}, starting with empty swarms", tracker.config.tracker_config.peers_snapshot_file);
+                    }
+                    Err(error) => {
+                        error!("[PEERS SNAPSHOT] Unable to restore {}: {}", tracker.config.tracker_config.peers_snapshot_file, error);
+                    }
+                }
+            }
+
             if args.create_selfsigned { tracker.cert_gen(&args).await; }
 
             if args.export { tracker.export(&args, tracker.clone()).await; }
@@ -198,7 +222,7 @@ fn main() -> std::io::Result<()>
                     let threads: u64 = udp_server_object.threads;
                     let tracker_clone = tracker.clone();
                     let tokio_udp = Arc::new(Builder::new_multi_thread().thread_name("udp").worker_threads(threads as usize).enable_all().build()?);
-                    udp_futures.push(udp_service(address, threads, tracker_clone, udp_rx.clone(), tokio_udp.clone()).await);
+                    udp_futures.push(udp_service(address, threads, tracker_clone, udp_server_object.clone(), udp_rx.clone(), tokio_udp.clone()).await);
                     udp_tokio_threads.push(tokio_udp.clone());
                 }
             }
@@ -214,13 +238,20 @@ fn main() -> std::io::Result<()>
                         return;
                     }
 
+                    tracker_spawn_stats.update_memory_stats();
                     let stats = tracker_spawn_stats.get_stats();
                     info!("[STATS] Torrents: {} - Updates: {} - Seeds: {} - Peers: {} - Completed: {}", stats.torrents, stats.torrents_updates, stats.seeds, stats.peers, stats.completed);
+                    info!("[STATS] Announces Throttled: {}", stats.announces_throttled);
+                    info!("[STATS] Expiry Queue: {} - Peers Expired: {} - Torrents Expired: {} - Stale: {} - Torrents Evicted: {}", stats.expiry_queue, stats.peers_expired, stats.torrents_expired, stats.expiry_stale, stats.torrents_evicted);
+                    info!("[STATS] Memory Torrents: {} - Whitelist: {} - Blacklist: {} - Keys: {} - Users: {} - Updates: {}", stats.memory_torrents, stats.memory_whitelist, stats.memory_blacklist, stats.memory_keys, stats.memory_users, stats.memory_updates);
+                    info!("[STATS] Flush Queue: {} - Failures: {} - Journal: {} - Replayed: {}", stats.flush_queue, stats.flush_failures, stats.flush_journal, stats.flush_replayed);
                     info!("[STATS] WList: {} - WList Updates: {} - BLists: {} - BLists Updates: {} - Keys: {} - Keys Updates {}", stats.whitelist, stats.whitelist_updates, stats.blacklist, stats.blacklist_updates, stats.keys, stats.keys_updates);
                     info!("[STATS TCP IPv4] Connect: {} - API: {} - A: {} - S: {} - F: {} - 404: {}", stats.tcp4_connections_handled, stats.tcp4_api_handled, stats.tcp4_announces_handled, stats.tcp4_scrapes_handled, stats.tcp4_failure, stats.tcp4_not_found);
                     info!("[STATS TCP IPv6] Connect: {} - API: {} - A: {} - S: {} - F: {} - 404: {}", stats.tcp6_connections_handled, stats.tcp6_api_handled, stats.tcp6_announces_handled, stats.tcp6_scrapes_handled, stats.tcp6_failure, stats.tcp6_not_found);
+                    info!("[STATS TCP] Tracker ID Issued: {} - Tracker ID Recognized: {}", stats.tcp_tracker_id_issued, stats.tcp_tracker_id_recognized);
                     info!("[STATS UDP IPv4] Connect: {} - A: {} - S: {} - IR: {} - BR: {}", stats.udp4_connections_handled, stats.udp4_announces_handled, stats.udp4_scrapes_handled, stats.udp4_invalid_request, stats.udp4_bad_request);
                     info!("[STATS UDP IPv6] Connect: {} - A: {} - S: {} - IR: {} - BR: {}", stats.udp6_connections_handled, stats.udp6_announces_handled, stats.udp6_scrapes_handled, stats.udp6_invalid_request, stats.udp6_bad_request);
+                    info!("[STATS UDP] Connection ID Invalid: {} - IP Address Overridden: {}", stats.udp_connection_id_invalid, stats.udp_announce_ip_overridden);
                 }
             });
 
@@ -249,6 +280,45 @@ fn main() -> std::io::Result<()>
                 });
             }
 
+            if tracker.config.tracker_config.clone().full_scrape_enabled {
+                let full_scrape_handler = tokio_shutdown.clone();
+                let tracker_spawn_full_scrape = tracker.clone();
+                info!("[BOOT] Starting thread for full scrape updates with {} seconds delay...", tracker_spawn_full_scrape.config.tracker_config.clone().full_scrape_interval);
+                tokio_core.spawn(async move {
+                    loop {
+                        let tracker_full_scrape = tracker_spawn_full_scrape.clone();
+                        if let Err(error) = tokio::task::spawn_blocking(move || tracker_full_scrape.update_full_scrape()).await {
+                            error!("[FULL SCRAPE] Unable to update the full scrape: {}", error);
+                        }
+                        if shutdown_waiting(Duration::from_secs(tracker_spawn_full_scrape.config.tracker_config.clone().full_scrape_interval), full_scrape_handler.clone()).await {
+                            info!("[BOOT] Shutting down thread for full scrape updates...");
+                            return;
+                        }
+                    }
+                });
+            }
+
+            if tracker.config.tracker_config.clone().peers_snapshot_enabled {
+                let peers_snapshot_handler = tokio_shutdown.clone();
+                let tracker_spawn_peers_snapshot = tracker.clone();
+                info!("[BOOT] Starting thread for peers snapshots with {} seconds delay...", tracker_spawn_peers_snapshot.config.tracker_config.clone().peers_snapshot_interval);
+                tokio_core.spawn(async move {
+                    loop {
+                        if shutdown_waiting(Duration::from_secs(tracker_spawn_peers_snapshot.config.tracker_config.clone().peers_snapshot_interval), peers_snapshot_handler.clone()).await {
+                            info!("[BOOT] Shutting down thread for peers snapshots...");
+                            return;
+                        }
+
+                        let tracker_peers_snapshot = tracker_spawn_peers_snapshot.clone();
+                        match tokio::task::spawn_blocking(move || tracker_peers_snapshot.save_peers_snapshot(tracker_peers_snapshot.config.tracker_config.peers_snapshot_file.as_str())).await {
+                            Ok(Ok(amount)) => { info!("[PEERS SNAPSHOT] Saved {} peers", amount); }
+                            Ok(Err(error)) => { error!("[PEERS SNAPSHOT] Unable to save the snapshot: {}", error); }
+                            Err(error) => { error!("[PEERS SNAPSHOT] Unable to save the snapshot: {}", error); }
+                        }
+                    }
+                });
+            }
+
             if tracker.config.database.clone().persistent {
                 let updates_handler = tokio_shutdown.clone();
                 let tracker_spawn_updates = tracker.clone();
@@ -261,32 +331,10 @@ fn main() -> std::io::Result<()>
                             return;
                         }
 
-                        info!("[TORRENTS UPDATES] Start updating torrents into the DB.");
-                        let _ = tracker_spawn_updates.save_torrent_updates(tracker_spawn_updates.clone()).await;
-                        info!("[TORRENTS UPDATES] Torrent updates inserted into DB.");
-
-                        if tracker_spawn_updates.config.tracker_config.clone().whitelist_enabled {
-                            info!("[WHITELIST UPDATES] Start updating whitelists into the DB.");
-                            let _ = tracker_spawn_updates.save_whitelist_updates(tracker_spawn_updates.clone()).await;
-                            info!("[WHITELIST UPDATES] Whitelists updates inserted into DB.");
-                        }
-
-                        if tracker_spawn_updates.config.tracker_config.clone().blacklist_enabled {
-                            info!("[BLACKLIST UPDATES] Start updating blacklists into the DB.");
-                            let _ = tracker_spawn_updates.save_blacklist_updates(tracker_spawn_updates.clone()).await;
-                            info!("[BLACKLIST UPDATES] Blacklists updates inserted into DB.");
-                        }
-
-                        if tracker_spawn_updates.config.tracker_config.clone().keys_enabled {
-                            info!("[KEY UPDATES] Start updating keys into the DB.");
-                            let _ = tracker_spawn_updates.save_key_updates(tracker_spawn_updates.clone()).await;
-                            info!("[KEY UPDATES] Keys updates inserted into DB.");
-                        }
-
-                        if tracker_spawn_updates.config.tracker_config.clone().users_enabled {
-                            info!("[USERS UPDATES] Start updating users into the DB.");
-                            let _ = tracker_spawn_updates.save_user_updates(tracker_spawn_updates.clone()).await;
-                            info!("[USERS UPDATES] Keys updates inserted into DB.");
+                        info!("[DATABASE UPDATES] Start updating into the DB.");
+                        match tracker_spawn_updates.flush_updates(tracker_spawn_updates.clone(), false).await {
+                            Ok(_) => { info!("[DATABASE UPDATES] Updates inserted into DB."); }
+                            Err(_) => { error!("[DATABASE UPDATES] Updates kept for a retry, {} queued and {} journaled.", tracker_spawn_updates.count_flush_queue(), tracker_spawn_updates.get_stats().flush_journal); }
                         }
                     }
                 });
@@ -320,23 +368,20 @@ fn main() -> std::io::Result<()>
 
                     task::sleep(Duration::from_secs(1)).await;
 
+                    if tracker.config.tracker_config.clone().peers_snapshot_enabled {
+                        match tracker.save_peers_snapshot(tracker.config.tracker_config.peers_snapshot_file.as_str()) {
+                            Ok(amount) => { info!("[PEERS SNAPSHOT] Saved {} peers", amount); }
+                            Err(error) => { error!("[PEERS SNAPSHOT] Unable to save the snapshot: {}", error); }
+                        }
+                    }
+
                     if tracker.config.database.clone().persistent {
                         tracker.set_stats(StatsEvent::Completed, config.tracker_config.clone().total_downloads as i64);
                         Configuration::save_from_config(tracker.config.clone(), "config.toml");
                         info!("Saving completed data to an INI...");
                         info!("Saving data to the database...");
-                        let _ = tracker.save_torrent_updates(tracker.clone()).await;
-                        if tracker.config.tracker_config.clone().whitelist_enabled {
-                            let _ = tracker.save_whitelist_updates(tracker.clone()).await;
-                        }
-                        if tracker.config.tracker_config.clone().blacklist_enabled {
-                            let _ = tracker.save_blacklist_updates(tracker.clone()).await;
-                        }
-                        if tracker.config.tracker_config.clone().keys_enabled {
-                            let _ = tracker.save_key_updates(tracker.clone()).await;
-                        }
-                        if tracker.config.tracker_config.clone().users_enabled {
-                            let _ = tracker.save_user_updates(tracker.clone()).await;
+                        if tracker.flush_updates(tracker.clone(), true).await.is_err() {
+                            error!("Unable to save the data to the database, {} updates journaled into {}", tracker.get_stats().flush_journal, tracker.config.database.flush_journal_file);
                         }
                     } else {
                         tracker.set_stats(StatsEvent::Completed, config.tracker_config.clone().total_downloads as i64);
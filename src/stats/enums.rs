@@ -1 +1,2 @@
-pub mod stats_event;
\ No newline at end of file
+pub mod stats_event;
+pub mod error_family;
\ No newline at end of file
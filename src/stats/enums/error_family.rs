@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFamily {
+    Tcp4,
+    Tcp6,
+    Udp4,
+    Udp6,
+}
@@ -13,6 +13,12 @@ pub enum StatsEvent {
     Seeds,
     Peers,
     Completed,
+    AnnouncesThrottled,
+    ExpiryQueue,
+    PeersExpired,
+    TorrentsExpired,
+    ExpiryStale,
+    TorrentsEvicted,
     WhitelistEnabled,
     Whitelist,
     WhitelistUpdates,
@@ -21,6 +27,16 @@ pub enum StatsEvent {
     BlacklistUpdates,
     Key,
     KeyUpdates,
+    MemoryTorrents,
+    MemoryWhitelist,
+    MemoryBlacklist,
+    MemoryKeys,
+    MemoryUsers,
+    MemoryUpdates,
+    FlushQueue,
+    FlushFailures,
+    FlushJournal,
+    FlushReplayed,
     Tcp4NotFound,
     Tcp4Failure,
     Tcp4ConnectionsHandled,
@@ -33,6 +49,8 @@ pub enum StatsEvent {
     Tcp6ApiHandled,
     Tcp6AnnouncesHandled,
     Tcp6ScrapesHandled,
+    TcpTrackerIdIssued,
+    TcpTrackerIdRecognized,
     Udp4BadRequest,
     Udp4InvalidRequest,
     Udp4ConnectionsHandled,
@@ -42,5 +60,7 @@ pub enum StatsEvent {
     Udp6InvalidRequest,
     Udp6ConnectionsHandled,
     Udp6AnnouncesHandled,
-    Udp6ScrapesHandled
+    Udp6ScrapesHandled,
+    UdpConnectionIdInvalid,
+    UdpAnnounceIpOverridden,
 }
\ No newline at end of file
@@ -1 +1,3 @@
-pub mod torrent_tracker;
\ No newline at end of file
+pub mod torrent_tracker;
+pub mod error_family;
+pub mod error_stats;
\ No newline at end of file
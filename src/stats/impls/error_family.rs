@@ -0,0 +1,37 @@
+use std::net::IpAddr;
+use crate::stats::enums::error_family::ErrorFamily;
+
+impl ErrorFamily {
+    pub const ALL: [ErrorFamily; 4] = [ErrorFamily::Tcp4, ErrorFamily::Tcp6, ErrorFamily::Udp4, ErrorFamily::Udp6];
+
+    pub fn tcp(ip: IpAddr) -> ErrorFamily
+    {
+        match ip {
+            IpAddr::V4(_) => { ErrorFamily::Tcp4 }
+            IpAddr::V6(_) => { ErrorFamily::Tcp6 }
+        }
+    }
+
+    pub fn udp(ip: IpAddr) -> ErrorFamily
+    {
+        match ip {
+            IpAddr::V4(_) => { ErrorFamily::Udp4 }
+            IpAddr::V6(_) => { ErrorFamily::Udp6 }
+        }
+    }
+
+    pub fn is_udp(&self) -> bool
+    {
+        matches!(self, ErrorFamily::Udp4 | ErrorFamily::Udp6)
+    }
+
+    pub fn label(&self) -> &'static str
+    {
+        match self {
+            ErrorFamily::Tcp4 => { "tcp4" }
+            ErrorFamily::Tcp6 => { "tcp6" }
+            ErrorFamily::Udp4 => { "udp4" }
+            ErrorFamily::Udp6 => { "udp6" }
+        }
+    }
+}
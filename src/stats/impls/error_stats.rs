@@ -0,0 +1,43 @@
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use crate::stats::enums::error_family::ErrorFamily;
+use crate::stats::structs::error_stats::ErrorStats;
+use crate::udp::enums::server_error::ServerError;
+
+impl ErrorStats {
+    pub fn get(&self, family: ErrorFamily, error: ServerError) -> i64
+    {
+        self.counts[family as usize][error as usize]
+    }
+
+    /// Every error kind that can occur for the family, with its count.
+    pub fn family(&self, family: ErrorFamily) -> impl Iterator<Item = (ServerError, i64)> + '_
+    {
+        ServerError::ALL.into_iter().filter(move |error| error.applies_to(family)).map(move |error| (error, self.get(family, error)))
+    }
+}
+
+/// Serializes as `{"udp4": {"bad_request": 0, ...}, ...}`, leaving out kinds that cannot occur for a family.
+impl Serialize for ErrorStats {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        struct Family<'a>(&'a ErrorStats, ErrorFamily);
+
+        impl Serialize for Family<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            {
+                let mut map = serializer.serialize_map(None)?;
+                for (error, count) in self.0.family(self.1) {
+                    map.serialize_entry(error.kind(), &count)?;
+                }
+                map.end()
+            }
+        }
+
+        let mut map = serializer.serialize_map(Some(ErrorFamily::ALL.len()))?;
+        for family in ErrorFamily::ALL {
+            map.serialize_entry(family.label(), &Family(self, family))?;
+        }
+        map.end()
+    }
+}
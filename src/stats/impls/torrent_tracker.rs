@@ -1,7 +1,10 @@
 use std::sync::atomic::Ordering;
+use crate::stats::enums::error_family::ErrorFamily;
 use crate::stats::enums::stats_event::StatsEvent;
+use crate::stats::structs::error_stats::ErrorStats;
 use crate::stats::structs::stats::Stats;
 use crate::tracker::structs::torrent_tracker::TorrentTracker;
+use crate::udp::enums::server_error::ServerError;
 
 impl TorrentTracker {
     #[tracing::instrument(level = "debug")]
@@ -20,6 +23,12 @@ impl TorrentTracker {
             seeds: self.stats.seeds.load(Ordering::SeqCst),
             peers: self.stats.peers.load(Ordering::SeqCst),
             completed: self.stats.completed.load(Ordering::SeqCst),
+            announces_throttled: self.stats.announces_throttled.load(Ordering::SeqCst),
+            expiry_queue: self.stats.expiry_queue.load(Ordering::SeqCst),
+            peers_expired: self.stats.peers_expired.load(Ordering::SeqCst),
+            torrents_expired: self.stats.torrents_expired.load(Ordering::SeqCst),
+            expiry_stale: self.stats.expiry_stale.load(Ordering::SeqCst),
+            torrents_evicted: self.stats.torrents_evicted.load(Ordering::SeqCst),
             whitelist_enabled: self.stats.whitelist_enabled.load(Ordering::SeqCst),
             whitelist: self.stats.whitelist.load(Ordering::SeqCst),
             whitelist_updates: self.stats.whitelist_updates.load(Ordering::SeqCst),
@@ -29,6 +38,16 @@ impl TorrentTracker {
             keys_enabled: self.stats.keys_enabled.load(Ordering::SeqCst),
             keys: self.stats.keys.load(Ordering::SeqCst),
             keys_updates: self.stats.keys_updates.load(Ordering::SeqCst),
+            memory_torrents: self.stats.memory_torrents.load(Ordering::SeqCst),
+            memory_whitelist: self.stats.memory_whitelist.load(Ordering::SeqCst),
+            memory_blacklist: self.stats.memory_blacklist.load(Ordering::SeqCst),
+            memory_keys: self.stats.memory_keys.load(Ordering::SeqCst),
+            memory_users: self.stats.memory_users.load(Ordering::SeqCst),
+            memory_updates: self.stats.memory_updates.load(Ordering::SeqCst),
+            flush_queue: self.stats.flush_queue.load(Ordering::SeqCst),
+            flush_failures: self.stats.flush_failures.load(Ordering::SeqCst),
+            flush_journal: self.stats.flush_journal.load(Ordering::SeqCst),
+            flush_replayed: self.stats.flush_replayed.load(Ordering::SeqCst),
             tcp4_not_found: self.stats.tcp4_not_found.load(Ordering::SeqCst),
             tcp4_failure: self.stats.tcp4_failure.load(Ordering::SeqCst),
             tcp4_connections_handled: self.stats.tcp4_connections_handled.load(Ordering::SeqCst),
@@ -41,6 +60,8 @@ impl TorrentTracker {
             tcp6_api_handled: self.stats.tcp6_api_handled.load(Ordering::SeqCst),
             tcp6_announces_handled: self.stats.tcp6_announces_handled.load(Ordering::SeqCst),
             tcp6_scrapes_handled: self.stats.tcp6_scrapes_handled.load(Ordering::SeqCst),
+            tcp_tracker_id_issued: self.stats.tcp_tracker_id_issued.load(Ordering::SeqCst),
+            tcp_tracker_id_recognized: self.stats.tcp_tracker_id_recognized.load(Ordering::SeqCst),
             udp4_bad_request: self.stats.udp4_bad_request.load(Ordering::SeqCst),
             udp4_invalid_request: self.stats.udp4_invalid_request.load(Ordering::SeqCst),
             udp4_connections_handled: self.stats.udp4_connections_handled.load(Ordering::SeqCst),
@@ -51,6 +72,11 @@ impl TorrentTracker {
             udp6_connections_handled: self.stats.udp6_connections_handled.load(Ordering::SeqCst),
             udp6_announces_handled: self.stats.udp6_announces_handled.load(Ordering::SeqCst),
             udp6_scrapes_handled: self.stats.udp6_scrapes_handled.load(Ordering::SeqCst),
+            udp_connection_id_invalid: self.stats.udp_connection_id_invalid.load(Ordering::SeqCst),
+            udp_announce_ip_overridden: self.stats.udp_announce_ip_overridden.load(Ordering::SeqCst),
+            errors: ErrorStats {
+                counts: self.stats.errors.each_ref().map(|family| family.each_ref().map(|count| count.load(Ordering::SeqCst))),
+            },
         }
     }
 
@@ -102,6 +128,30 @@ impl TorrentTracker {
                 if value > 0 { self.stats.completed.fetch_add(value, Ordering::SeqCst); }
                 if value < 0 { self.stats.completed.fetch_sub(-value, Ordering::SeqCst); }
             }
+            StatsEvent::AnnouncesThrottled => {
+                if value > 0 { self.stats.announces_throttled.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.announces_throttled.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::ExpiryQueue => {
+                if value > 0 { self.stats.expiry_queue.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.expiry_queue.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::PeersExpired => {
+                if value > 0 { self.stats.peers_expired.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.peers_expired.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::TorrentsExpired => {
+                if value > 0 { self.stats.torrents_expired.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.torrents_expired.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::ExpiryStale => {
+                if value > 0 { self.stats.expiry_stale.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.expiry_stale.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::TorrentsEvicted => {
+                if value > 0 { self.stats.torrents_evicted.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.torrents_evicted.fetch_sub(-value, Ordering::SeqCst); }
+            }
             StatsEvent::WhitelistEnabled => {
                 if value > 0 { self.stats.whitelist_enabled.store(true, Ordering::SeqCst); }
                 if value < 0 { self.stats.whitelist_enabled.store(false, Ordering::SeqCst); }
@@ -134,6 +184,46 @@ impl TorrentTracker {
                 if value > 0 { self.stats.keys_updates.fetch_add(value, Ordering::SeqCst); }
                 if value < 0 { self.stats.keys_updates.fetch_sub(-value, Ordering::SeqCst); }
             }
+            StatsEvent::MemoryTorrents => {
+                if value > 0 { self.stats.memory_torrents.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.memory_torrents.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::MemoryWhitelist => {
+                if value > 0 { self.stats.memory_whitelist.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.memory_whitelist.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::MemoryBlacklist => {
+                if value > 0 { self.stats.memory_blacklist.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.memory_blacklist.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::MemoryKeys => {
+                if value > 0 { self.stats.memory_keys.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.memory_keys.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::MemoryUsers => {
+                if value > 0 { self.stats.memory_users.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.memory_users.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::MemoryUpdates => {
+                if value > 0 { self.stats.memory_updates.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.memory_updates.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::FlushQueue => {
+                if value > 0 { self.stats.flush_queue.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.flush_queue.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::FlushFailures => {
+                if value > 0 { self.stats.flush_failures.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.flush_failures.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::FlushJournal => {
+                if value > 0 { self.stats.flush_journal.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.flush_journal.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::FlushReplayed => {
+                if value > 0 { self.stats.flush_replayed.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.flush_replayed.fetch_sub(-value, Ordering::SeqCst); }
+            }
             StatsEvent::Tcp4NotFound => {
                 if value > 0 { self.stats.tcp4_not_found.fetch_add(value, Ordering::SeqCst); }
                 if value < 0 { self.stats.tcp4_not_found.fetch_sub(-value, Ordering::SeqCst); }
@@ -182,6 +272,14 @@ impl TorrentTracker {
                 if value > 0 { self.stats.tcp6_scrapes_handled.fetch_add(value, Ordering::SeqCst); }
                 if value < 0 { self.stats.tcp6_scrapes_handled.fetch_sub(-value, Ordering::SeqCst); }
             }
+            StatsEvent::TcpTrackerIdIssued => {
+                if value > 0 { self.stats.tcp_tracker_id_issued.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.tcp_tracker_id_issued.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::TcpTrackerIdRecognized => {
+                if value > 0 { self.stats.tcp_tracker_id_recognized.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.tcp_tracker_id_recognized.fetch_sub(-value, Ordering::SeqCst); }
+            }
             StatsEvent::Udp4BadRequest => {
                 if value > 0 { self.stats.udp4_bad_request.fetch_add(value, Ordering::SeqCst); }
                 if value < 0 { self.stats.udp4_bad_request.fetch_sub(-value, Ordering::SeqCst); }
@@ -222,6 +320,14 @@ impl TorrentTracker {
                 if value > 0 { self.stats.udp6_scrapes_handled.fetch_add(value, Ordering::SeqCst); }
                 if value < 0 { self.stats.udp6_scrapes_handled.fetch_sub(-value, Ordering::SeqCst); }
             }
+            StatsEvent::UdpConnectionIdInvalid => {
+                if value > 0 { self.stats.udp_connection_id_invalid.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.udp_connection_id_invalid.fetch_sub(-value, Ordering::SeqCst); }
+            }
+            StatsEvent::UdpAnnounceIpOverridden => {
+                if value > 0 { self.stats.udp_announce_ip_overridden.fetch_add(value, Ordering::SeqCst); }
+                if value < 0 { self.stats.udp_announce_ip_overridden.fetch_sub(-value, Ordering::SeqCst); }
+            }
         };
         self.get_stats()
     }
@@ -263,6 +369,24 @@ impl TorrentTracker {
             StatsEvent::Completed => {
                 self.stats.completed.store(value, Ordering::SeqCst);
             }
+            StatsEvent::AnnouncesThrottled => {
+                self.stats.announces_throttled.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::ExpiryQueue => {
+                self.stats.expiry_queue.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::PeersExpired => {
+                self.stats.peers_expired.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::TorrentsExpired => {
+                self.stats.torrents_expired.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::ExpiryStale => {
+                self.stats.expiry_stale.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::TorrentsEvicted => {
+                self.stats.torrents_evicted.store(value, Ordering::SeqCst);
+            }
             StatsEvent::WhitelistEnabled => {
                 if value > 0 { self.stats.whitelist_enabled.store(true, Ordering::SeqCst); }
                 if value < 0 { self.stats.whitelist_enabled.store(false, Ordering::SeqCst); }
@@ -289,6 +413,36 @@ impl TorrentTracker {
             StatsEvent::KeyUpdates => {
                 self.stats.keys_updates.store(value, Ordering::SeqCst);
             }
+            StatsEvent::MemoryTorrents => {
+                self.stats.memory_torrents.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::MemoryWhitelist => {
+                self.stats.memory_whitelist.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::MemoryBlacklist => {
+                self.stats.memory_blacklist.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::MemoryKeys => {
+                self.stats.memory_keys.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::MemoryUsers => {
+                self.stats.memory_users.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::MemoryUpdates => {
+                self.stats.memory_updates.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::FlushQueue => {
+                self.stats.flush_queue.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::FlushFailures => {
+                self.stats.flush_failures.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::FlushJournal => {
+                self.stats.flush_journal.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::FlushReplayed => {
+                self.stats.flush_replayed.store(value, Ordering::SeqCst);
+            }
             StatsEvent::Tcp4NotFound => {
                 self.stats.tcp4_not_found.store(value, Ordering::SeqCst);
             }
@@ -325,6 +479,12 @@ impl TorrentTracker {
             StatsEvent::Tcp6ScrapesHandled => {
                 self.stats.tcp6_scrapes_handled.store(value, Ordering::SeqCst);
             }
+            StatsEvent::TcpTrackerIdIssued => {
+                self.stats.tcp_tracker_id_issued.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::TcpTrackerIdRecognized => {
+                self.stats.tcp_tracker_id_recognized.store(value, Ordering::SeqCst);
+            }
             StatsEvent::Udp4BadRequest => {
                 self.stats.udp4_bad_request.store(value, Ordering::SeqCst);
             }
@@ -355,7 +515,19 @@ impl TorrentTracker {
             StatsEvent::Udp6ScrapesHandled => {
                 self.stats.udp6_scrapes_handled.store(value, Ordering::SeqCst);
             }
+            StatsEvent::UdpConnectionIdInvalid => {
+                self.stats.udp_connection_id_invalid.store(value, Ordering::SeqCst);
+            }
+            StatsEvent::UdpAnnounceIpOverridden => {
+                self.stats.udp_announce_ip_overridden.store(value, Ordering::SeqCst);
+            }
         };
         self.get_stats()
     }
+
+    #[tracing::instrument(level = "debug")]
+    pub fn update_error_stats(&self, family: ErrorFamily, error: ServerError, value: i64)
+    {
+        self.stats.errors[family as usize][error as usize].fetch_add(value, Ordering::SeqCst);
+    }
 }
\ No newline at end of file
@@ -1,2 +1,3 @@
 pub mod stats;
-pub mod stats_atomics;
\ No newline at end of file
+pub mod stats_atomics;
+pub mod error_stats;
\ No newline at end of file
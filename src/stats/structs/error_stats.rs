@@ -0,0 +1,8 @@
+use crate::stats::enums::error_family::ErrorFamily;
+use crate::udp::enums::server_error::ServerError;
+
+/// Failure counters indexed by `ErrorFamily` and `ServerError`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ErrorStats {
+    pub counts: [[i64; ServerError::ALL.len()]; ErrorFamily::ALL.len()],
+}
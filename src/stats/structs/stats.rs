@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use crate::stats::structs::error_stats::ErrorStats;
 
 #[derive(Serialize, Deserialize, Clone, Copy)]
 pub struct Stats {
@@ -14,6 +15,12 @@ pub struct Stats {
     pub seeds: i64,
     pub peers: i64,
     pub completed: i64,
+    pub announces_throttled: i64,
+    pub expiry_queue: i64,
+    pub peers_expired: i64,
+    pub torrents_expired: i64,
+    pub expiry_stale: i64,
+    pub torrents_evicted: i64,
     pub whitelist_enabled: bool,
     pub whitelist: i64,
     pub whitelist_updates: i64,
@@ -23,6 +30,16 @@ pub struct Stats {
     pub keys_enabled: bool,
     pub keys: i64,
     pub keys_updates: i64,
+    pub memory_torrents: i64,
+    pub memory_whitelist: i64,
+    pub memory_blacklist: i64,
+    pub memory_keys: i64,
+    pub memory_users: i64,
+    pub memory_updates: i64,
+    pub flush_queue: i64,
+    pub flush_failures: i64,
+    pub flush_journal: i64,
+    pub flush_replayed: i64,
     pub tcp4_not_found: i64,
     pub tcp4_failure: i64,
     pub tcp4_connections_handled: i64,
@@ -35,6 +52,8 @@ pub struct Stats {
     pub tcp6_api_handled: i64,
     pub tcp6_announces_handled: i64,
     pub tcp6_scrapes_handled: i64,
+    pub tcp_tracker_id_issued: i64,
+    pub tcp_tracker_id_recognized: i64,
     pub udp4_bad_request: i64,
     pub udp4_invalid_request: i64,
     pub udp4_connections_handled: i64,
@@ -45,4 +64,8 @@ pub struct Stats {
     pub udp6_connections_handled: i64,
     pub udp6_announces_handled: i64,
     pub udp6_scrapes_handled: i64,
+    pub udp_connection_id_invalid: i64,
+    pub udp_announce_ip_overridden: i64,
+    #[serde(skip_deserializing)]
+    pub errors: ErrorStats,
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
 use std::sync::atomic::{AtomicBool, AtomicI64};
 use serde::{Deserialize, Serialize};
+use crate::stats::enums::error_family::ErrorFamily;
+use crate::udp::enums::server_error::ServerError;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StatsAtomics {
@@ -15,6 +17,12 @@ pub struct StatsAtomics {
     pub seeds: AtomicI64,
     pub peers: AtomicI64,
     pub completed: AtomicI64,
+    pub announces_throttled: AtomicI64,
+    pub expiry_queue: AtomicI64,
+    pub peers_expired: AtomicI64,
+    pub torrents_expired: AtomicI64,
+    pub expiry_stale: AtomicI64,
+    pub torrents_evicted: AtomicI64,
     pub whitelist_enabled: AtomicBool,
     pub whitelist: AtomicI64,
     pub whitelist_updates: AtomicI64,
@@ -24,6 +32,16 @@ pub struct StatsAtomics {
     pub keys_enabled: AtomicBool,
     pub keys: AtomicI64,
     pub keys_updates: AtomicI64,
+    pub memory_torrents: AtomicI64,
+    pub memory_whitelist: AtomicI64,
+    pub memory_blacklist: AtomicI64,
+    pub memory_keys: AtomicI64,
+    pub memory_users: AtomicI64,
+    pub memory_updates: AtomicI64,
+    pub flush_queue: AtomicI64,
+    pub flush_failures: AtomicI64,
+    pub flush_journal: AtomicI64,
+    pub flush_replayed: AtomicI64,
     pub tcp4_not_found: AtomicI64,
     pub tcp4_failure: AtomicI64,
     pub tcp4_connections_handled: AtomicI64,
@@ -36,6 +54,8 @@ pub struct StatsAtomics {
     pub tcp6_api_handled: AtomicI64,
     pub tcp6_announces_handled: AtomicI64,
     pub tcp6_scrapes_handled: AtomicI64,
+    pub tcp_tracker_id_issued: AtomicI64,
+    pub tcp_tracker_id_recognized: AtomicI64,
     pub udp4_bad_request: AtomicI64,
     pub udp4_invalid_request: AtomicI64,
     pub udp4_connections_handled: AtomicI64,
@@ -46,4 +66,7 @@ pub struct StatsAtomics {
     pub udp6_connections_handled: AtomicI64,
     pub udp6_announces_handled: AtomicI64,
     pub udp6_scrapes_handled: AtomicI64,
+    pub udp_connection_id_invalid: AtomicI64,
+    pub udp_announce_ip_overridden: AtomicI64,
+    pub errors: [[AtomicI64; ServerError::ALL.len()]; ErrorFamily::ALL.len()],
 }
\ No newline at end of file
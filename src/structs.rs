@@ -9,6 +9,12 @@ pub struct Cli {
     /// Create the database for the engine that is used in the config.toml
     #[arg(long)]
     pub create_database: bool,
+    /// Apply the pending schema migrations to the database that is used in the config.toml
+    #[arg(long)]
+    pub migrate: bool,
+    /// Print the SQL of the pending schema migrations instead of applying them
+    #[arg(long, requires("migrate"))]
+    pub migrate_dry_run: bool,
     /// Create a development self-signed key and certificate file in PEM format
     #[arg(long)]
     pub create_selfsigned: bool,
@@ -1,4 +1,6 @@
 pub mod announce_event;
 pub mod announce_event_def;
 pub mod torrent_peers_type;
-pub mod updates_action;
\ No newline at end of file
+pub mod updates_action;
+pub mod peers_selection;
+pub mod request_interval_throttle;
\ No newline at end of file
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[allow(non_camel_case_types)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RequestIntervalThrottle {
+    #[default]
+    disabled,
+    cached,
+    failure,
+    warning,
+}
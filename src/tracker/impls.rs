@@ -23,4 +23,11 @@ pub mod torrent_tracker_export;
 pub mod torrent_tracker_cert_gen;
 pub mod torrent_tracker_torrents_blacklist_updates;
 pub mod torrent_tracker_torrents_whitelist_updates;
-pub mod torrent_tracker_keys_updates;
\ No newline at end of file
+pub mod torrent_tracker_keys_updates;
+pub mod torrent_tracker_full_scrape;
+pub mod announce_response;
+pub mod torrent_tracker_peers_snapshot;
+pub mod torrent_tracker_memory;
+pub mod flush_journal;
+pub mod torrent_tracker_flush;
+pub mod torrent_tracker_migrate;
\ No newline at end of file
@@ -0,0 +1,101 @@
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use bip_bencode::{ben_bytes, ben_int, ben_list, ben_map, BMutAccess, BencodeMut};
+use crate::tracker::structs::announce_response::AnnounceResponse;
+use crate::tracker::structs::peer_id::PeerId;
+use crate::tracker::structs::torrent_peer::TorrentPeer;
+use crate::udp::structs::port::Port;
+use crate::udp::structs::response_peer::ResponsePeer;
+
+impl AnnounceResponse {
+    pub fn new(peers: Vec<(PeerId, TorrentPeer)>) -> AnnounceResponse
+    {
+        AnnounceResponse { peers }
+    }
+
+    fn peers_ipv4(&self) -> impl Iterator<Item = (&PeerId, SocketAddrV4)>
+    {
+        self.peers.iter().filter_map(|(peer_id, torrent_peer)| {
+            match torrent_peer.peer_addr_ipv4() {
+                Some(SocketAddr::V4(peer_addr)) => { Some((peer_id, peer_addr)) }
+                _ => { None }
+            }
+        })
+    }
+
+    fn peers_ipv6(&self) -> impl Iterator<Item = (&PeerId, SocketAddrV6)>
+    {
+        self.peers.iter().filter_map(|(peer_id, torrent_peer)| {
+            match torrent_peer.peer_addr_ipv6() {
+                Some(SocketAddr::V6(peer_addr)) => { Some((peer_id, peer_addr)) }
+                _ => { None }
+            }
+        })
+    }
+
+    pub fn compact_ipv4(&self) -> Vec<u8>
+    {
+        let mut peers_list = Vec::new();
+        for (_, peer_addr) in self.peers_ipv4() {
+            peers_list.extend_from_slice(&peer_addr.ip().octets());
+            peers_list.extend_from_slice(&peer_addr.port().to_be_bytes());
+        }
+        peers_list
+    }
+
+    pub fn compact_ipv6(&self) -> Vec<u8>
+    {
+        let mut peers_list = Vec::new();
+        for (_, peer_addr) in self.peers_ipv6() {
+            peers_list.extend_from_slice(&peer_addr.ip().octets());
+            peers_list.extend_from_slice(&peer_addr.port().to_be_bytes());
+        }
+        peers_list
+    }
+
+    pub fn dict_ipv4<'a>(&self, no_peer_id: bool) -> BencodeMut<'a>
+    {
+        Self::dict_list(self.peers_ipv4().map(|(peer_id, peer_addr)| (peer_id, SocketAddr::V4(peer_addr))), no_peer_id)
+    }
+
+    pub fn dict_ipv6<'a>(&self, no_peer_id: bool) -> BencodeMut<'a>
+    {
+        Self::dict_list(self.peers_ipv6().map(|(peer_id, peer_addr)| (peer_id, SocketAddr::V6(peer_addr))), no_peer_id)
+    }
+
+    fn dict_list<'a, 'b>(peers: impl Iterator<Item = (&'b PeerId, SocketAddr)>, no_peer_id: bool) -> BencodeMut<'a>
+    {
+        let mut peers_list = ben_list!();
+        let peers_list_mut = peers_list.list_mut().unwrap();
+        for (peer_id, peer_addr) in peers {
+            if no_peer_id {
+                peers_list_mut.push(ben_map! {
+                    "ip" => ben_bytes!(peer_addr.ip().to_string()),
+                    "port" => ben_int!(peer_addr.port() as i64)
+                });
+            } else {
+                peers_list_mut.push(ben_map! {
+                    "peer id" => ben_bytes!(peer_id.to_string()),
+                    "ip" => ben_bytes!(peer_addr.ip().to_string()),
+                    "port" => ben_int!(peer_addr.port() as i64)
+                });
+            }
+        }
+        peers_list
+    }
+
+    pub fn response_peers_ipv4(&self) -> Vec<ResponsePeer<Ipv4Addr>>
+    {
+        self.peers_ipv4().map(|(_, peer_addr)| ResponsePeer::<Ipv4Addr> {
+            ip_address: *peer_addr.ip(),
+            port: Port(peer_addr.port()),
+        }).collect()
+    }
+
+    pub fn response_peers_ipv6(&self) -> Vec<ResponsePeer<Ipv6Addr>>
+    {
+        self.peers_ipv6().map(|(_, peer_addr)| ResponsePeer::<Ipv6Addr> {
+            ip_address: *peer_addr.ip(),
+            port: Port(peer_addr.port()),
+        }).collect()
+    }
+}
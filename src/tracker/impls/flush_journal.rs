@@ -0,0 +1,59 @@
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::fs;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use crate::tracker::structs::flush_journal::FlushJournal;
+
+impl FlushJournal {
+    #[tracing::instrument(level = "debug")]
+    pub fn load(path: &str) -> Result<FlushJournal, std::io::Error>
+    {
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+
+    /// Writes the journal through a temporary file, so a crash never leaves a truncated journal behind.
+    #[tracing::instrument(level = "debug")]
+    pub fn save(&self, path: &str) -> Result<(), std::io::Error>
+    {
+        let temporary = format!("{}.tmp", path);
+        let mut writer = BufWriter::new(File::create(&temporary)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&temporary, path)
+    }
+
+    #[tracing::instrument(level = "debug")]
+    pub fn len(&self) -> usize
+    {
+        self.torrents.len() + self.whitelist.len() + self.blacklist.len() + self.keys.len() + self.users.len()
+    }
+
+    #[tracing::instrument(level = "debug")]
+    pub fn is_empty(&self) -> bool
+    {
+        self.len() == 0
+    }
+
+    /// Keeps only the newest entry for every torrent, list entry, key and user.
+    #[tracing::instrument(level = "debug")]
+    pub fn compact(&mut self)
+    {
+        self.torrents.sort_by_key(|entry| Reverse(entry.0));
+        let mut seen = BTreeSet::new();
+        self.torrents.retain(|(_, info_hash, _, _)| seen.insert(*info_hash));
+        self.whitelist.sort_by_key(|entry| Reverse(entry.0));
+        let mut seen = BTreeSet::new();
+        self.whitelist.retain(|(_, info_hash, _)| seen.insert(*info_hash));
+        self.blacklist.sort_by_key(|entry| Reverse(entry.0));
+        let mut seen = BTreeSet::new();
+        self.blacklist.retain(|(_, info_hash, _)| seen.insert(*info_hash));
+        self.keys.sort_by_key(|entry| Reverse(entry.0));
+        let mut seen = BTreeSet::new();
+        self.keys.retain(|(_, info_hash, _, _)| seen.insert(*info_hash));
+        self.users.sort_by_key(|entry| Reverse(entry.0));
+        let mut seen = BTreeSet::new();
+        self.users.retain(|(_, user_id, _, _)| seen.insert(*user_id));
+    }
+}
@@ -8,6 +8,7 @@ impl TorrentEntry {
             seeds: BTreeMap::new(),
             completed: 0u64,
             updated: std::time::Instant::now(),
+            peers_cursor: 0,
         }
     }
 }
@@ -5,4 +5,12 @@ impl TorrentPeer {
     pub fn peer_addr_from_ip_and_port_and_opt_host_ip(remote_ip: IpAddr, port: u16) -> SocketAddr {
         SocketAddr::new(remote_ip, port)
     }
+
+    pub fn peer_addr_ipv4(&self) -> Option<SocketAddr> {
+        [Some(self.peer_addr), self.peer_addr_alternate].into_iter().flatten().find(|addr| addr.is_ipv4())
+    }
+
+    pub fn peer_addr_ipv6(&self) -> Option<SocketAddr> {
+        [Some(self.peer_addr), self.peer_addr_alternate].into_iter().flatten().find(|addr| addr.is_ipv6())
+    }
 }
\ No newline at end of file
@@ -1,20 +1,32 @@
 use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicI64};
 use chrono::Utc;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use crate::common::structs::cidr::Cidr;
 use crate::config::structs::configuration::Configuration;
 use crate::database::structs::database_connector::DatabaseConnector;
+use crate::database::traits::DatabaseBackend;
 use crate::stats::structs::stats_atomics::StatsAtomics;
+use crate::tracker::structs::flush_state::FlushState;
+use crate::tracker::structs::torrent_sharding::TorrentSharding;
 use crate::tracker::structs::torrent_tracker::TorrentTracker;
+use crate::udp::structs::connection_id_secrets::ConnectionIdSecrets;
 
 impl TorrentTracker {
     #[tracing::instrument(level = "debug")]
     pub async fn new(config: Arc<Configuration>, create_database: bool) -> TorrentTracker
+    {
+        TorrentTracker::new_with_backend(config.clone(), DatabaseConnector::new(config, create_database).await)
+    }
+
+    #[tracing::instrument(level = "debug")]
+    pub fn new_with_backend(config: Arc<Configuration>, sqlx: Box<dyn DatabaseBackend>) -> TorrentTracker
     {
         TorrentTracker {
             config: config.clone(),
-            torrents_sharding: Arc::new(Default::default()),
+            torrents_sharding: Arc::new(TorrentSharding::new(config.tracker_config.shards as usize)),
             torrents_updates: Arc::new(RwLock::new(HashMap::new())),
             torrents_whitelist: Arc::new(RwLock::new(Vec::new())),
             torrents_whitelist_updates: Arc::new(RwLock::new(HashMap::new())),
@@ -35,6 +47,12 @@ impl TorrentTracker {
                 seeds: AtomicI64::new(0),
                 peers: AtomicI64::new(0),
                 completed: AtomicI64::new(0),
+                announces_throttled: AtomicI64::new(0),
+                expiry_queue: AtomicI64::new(0),
+                peers_expired: AtomicI64::new(0),
+                torrents_expired: AtomicI64::new(0),
+                expiry_stale: AtomicI64::new(0),
+                torrents_evicted: AtomicI64::new(0),
                 whitelist_enabled: AtomicBool::new(config.tracker_config.clone().whitelist_enabled),
                 whitelist: AtomicI64::new(0),
                 whitelist_updates: AtomicI64::new(0),
@@ -44,6 +62,16 @@ impl TorrentTracker {
                 keys_enabled: AtomicBool::new(config.tracker_config.clone().keys_enabled),
                 keys: AtomicI64::new(0),
                 keys_updates: AtomicI64::new(0),
+                memory_torrents: AtomicI64::new(0),
+                memory_whitelist: AtomicI64::new(0),
+                memory_blacklist: AtomicI64::new(0),
+                memory_keys: AtomicI64::new(0),
+                memory_users: AtomicI64::new(0),
+                memory_updates: AtomicI64::new(0),
+                flush_queue: AtomicI64::new(0),
+                flush_failures: AtomicI64::new(0),
+                flush_journal: AtomicI64::new(0),
+                flush_replayed: AtomicI64::new(0),
                 tcp4_connections_handled: AtomicI64::new(0),
                 tcp4_api_handled: AtomicI64::new(0),
                 tcp4_announces_handled: AtomicI64::new(0),
@@ -54,6 +82,8 @@ impl TorrentTracker {
                 tcp6_api_handled: AtomicI64::new(0),
                 tcp6_announces_handled: AtomicI64::new(0),
                 tcp6_scrapes_handled: AtomicI64::new(0),
+                tcp_tracker_id_issued: AtomicI64::new(0),
+                tcp_tracker_id_recognized: AtomicI64::new(0),
                 tcp6_not_found: AtomicI64::new(0),
                 tcp6_failure: AtomicI64::new(0),
                 udp4_invalid_request: AtomicI64::new(0),
@@ -66,10 +96,17 @@ impl TorrentTracker {
                 udp6_connections_handled: AtomicI64::new(0),
                 udp6_announces_handled: AtomicI64::new(0),
                 udp6_scrapes_handled: AtomicI64::new(0),
+                udp_connection_id_invalid: AtomicI64::new(0),
+                udp_announce_ip_overridden: AtomicI64::new(0),
+                errors: Default::default(),
             }),
             users: Arc::new(RwLock::new(BTreeMap::new())),
             users_updates: Arc::new(RwLock::new(HashMap::new())),
-            sqlx: DatabaseConnector::new(config.clone(), create_database).await,
+            flush_state: Arc::new(Mutex::new(FlushState::default())),
+            full_scrape: Arc::new(RwLock::new(Vec::new())),
+            full_scrape_cidrs: Arc::new(config.tracker_config.full_scrape_cidrs.iter().filter_map(|cidr| Cidr::from_str(cidr).ok()).collect()),
+            udp_connection_id_secrets: Arc::new(RwLock::new(ConnectionIdSecrets::new(Utc::now().timestamp() as u64 / ConnectionIdSecrets::BUCKET_SECONDS))),
+            sqlx,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,238 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use log::{error, info};
+use crate::stats::enums::stats_event::StatsEvent;
+use crate::tracker::enums::updates_action::UpdatesAction;
+use crate::tracker::structs::flush_journal::FlushJournal;
+use crate::tracker::structs::torrent_entry::TorrentEntry;
+use crate::tracker::structs::torrent_tracker::TorrentTracker;
+
+impl TorrentTracker {
+    /// Flushes all pending updates into the database. Failed updates stay queued and are retried with an exponential
+    /// backoff from `persistent_interval` up to `flush_backoff_max` seconds. Once the database is down for longer than
+    /// `flush_journal_after` seconds, or when `force` is set, the queue is spilled to `flush_journal_file`, which is
+    /// replayed into the queue on every following attempt until the database accepted it.
+    #[tracing::instrument(level = "debug")]
+    pub async fn flush_updates(&self, torrent_tracker: Arc<TorrentTracker>, force: bool) -> Result<(), ()>
+    {
+        let now = Instant::now();
+        let retry_at = self.flush_state.lock().retry_at;
+        if !force && let Some(retry_at) = retry_at && now < retry_at {
+            info!("[FLUSH] Database unavailable, next attempt in {} seconds", retry_at.saturating_duration_since(now).as_secs());
+            self.set_stats(StatsEvent::FlushQueue, self.count_flush_queue() as i64);
+            return Err(());
+        }
+
+        let path = self.config.database.flush_journal_file.clone();
+        let journal = match Path::new(&path).exists() {
+            true => match FlushJournal::load(&path) {
+                Ok(journal) => {
+                    self.requeue_flush_journal(&journal);
+                    Some(journal)
+                }
+                Err(error) => {
+                    error!("[FLUSH] Unable to read the journal {}, moving it aside: {}", path, error);
+                    let _ = fs::rename(&path, format!("{}.broken", path));
+                    None
+                }
+            },
+            false => None
+        };
+
+        let mut failed = self.save_torrent_updates(torrent_tracker.clone()).await.is_err();
+        if self.config.tracker_config.whitelist_enabled {
+            failed |= self.save_whitelist_updates(torrent_tracker.clone()).await.is_err();
+        }
+        if self.config.tracker_config.blacklist_enabled {
+            failed |= self.save_blacklist_updates(torrent_tracker.clone()).await.is_err();
+        }
+        if self.config.tracker_config.keys_enabled {
+            failed |= self.save_key_updates(torrent_tracker.clone()).await.is_err();
+        }
+        if self.config.tracker_config.users_enabled {
+            failed |= self.save_user_updates(torrent_tracker.clone()).await.is_err();
+        }
+
+        let mut spill = false;
+        if failed {
+            self.update_stats(StatsEvent::FlushFailures, 1);
+            let mut state = self.flush_state.lock();
+            state.failures += 1;
+            let failing_since = *state.failing_since.get_or_insert(now);
+            let backoff = self.config.database.persistent_interval
+                .saturating_mul(1u64 << (state.failures - 1).min(16))
+                .min(self.config.database.flush_backoff_max);
+            state.retry_at = Some(now + Duration::from_secs(backoff));
+            spill = force || now.saturating_duration_since(failing_since) >= Duration::from_secs(self.config.database.flush_journal_after);
+            error!("[FLUSH] Database flush failed {} times in a row, retrying in {} seconds", state.failures, backoff);
+        } else {
+            let mut state = self.flush_state.lock();
+            if state.failures > 0 {
+                info!("[FLUSH] Database reachable again after {} failed flushes", state.failures);
+            }
+            *state = Default::default();
+        }
+
+        let mut remaining = match journal {
+            None => FlushJournal::default(),
+            Some(journal) => {
+                let remaining = self.pending_flush_journal(&journal);
+                let replayed = journal.len() - remaining.len();
+                if replayed > 0 {
+                    info!("[FLUSH] Replayed {} journaled updates", replayed);
+                    self.update_stats(StatsEvent::FlushReplayed, replayed as i64);
+                }
+                remaining
+            }
+        };
+        if spill {
+            let spilled = self.copy_flush_queue(&mut remaining);
+            remaining.compact();
+            remaining.created = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64;
+            match remaining.save(&path) {
+                Ok(_) => {
+                    info!("[FLUSH] Spilled {} queued updates into the journal {}", spilled.len(), path);
+                    self.remove_flush_queue(&spilled);
+                }
+                Err(error) => { error!("[FLUSH] Unable to write the journal {}: {}", path, error); }
+            }
+        } else if remaining.is_empty() {
+            if Path::new(&path).exists() && let Err(error) = fs::remove_file(&path) {
+                error!("[FLUSH] Unable to remove the journal {}: {}", path, error);
+            }
+        } else if let Err(error) = remaining.save(&path) {
+            error!("[FLUSH] Unable to write the journal {}: {}", path, error);
+        }
+
+        self.set_stats(StatsEvent::FlushQueue, self.count_flush_queue() as i64);
+        self.set_stats(StatsEvent::FlushJournal, remaining.len() as i64);
+        match failed {
+            true => Err(()),
+            false => Ok(())
+        }
+    }
+
+    /// Puts the journaled updates back into the queues under their original timestamps, so newer queued updates of the
+    /// same torrent, list entry, key or user still take precedence.
+    #[tracing::instrument(level = "debug")]
+    pub fn requeue_flush_journal(&self, journal: &FlushJournal)
+    {
+        for (timestamp, info_hash, completed, updates_action) in journal.torrents.iter() {
+            let mut torrent_entry = match updates_action {
+                UpdatesAction::Remove => TorrentEntry::new(),
+                _ => self.get_torrent(*info_hash).unwrap_or_default()
+            };
+            torrent_entry.completed = torrent_entry.completed.max(*completed);
+            if self.torrents_updates.write().insert(*timestamp, (*info_hash, torrent_entry, *updates_action)).is_none() {
+                self.update_stats(StatsEvent::TorrentsUpdates, 1);
+            }
+        }
+        for (timestamp, info_hash, updates_action) in journal.whitelist.iter() {
+            if self.torrents_whitelist_updates.write().insert(*timestamp, (*info_hash, *updates_action)).is_none() {
+                self.update_stats(StatsEvent::WhitelistUpdates, 1);
+            }
+        }
+        for (timestamp, info_hash, updates_action) in journal.blacklist.iter() {
+            if self.torrents_blacklist_updates.write().insert(*timestamp, (*info_hash, *updates_action)).is_none() {
+                self.update_stats(StatsEvent::BlacklistUpdates, 1);
+            }
+        }
+        for (timestamp, info_hash, timeout, updates_action) in journal.keys.iter() {
+            if self.keys_updates.write().insert(*timestamp, (*info_hash, *timeout, *updates_action)).is_none() {
+                self.update_stats(StatsEvent::KeyUpdates, 1);
+            }
+        }
+        for (timestamp, user_id, user_entry_item, updates_action) in journal.users.iter() {
+            if self.users_updates.write().insert(*timestamp, (*user_id, user_entry_item.clone(), *updates_action)).is_none() {
+                self.update_stats(StatsEvent::UsersUpdates, 1);
+            }
+        }
+    }
+
+    /// Returns the journaled updates that are still queued, those gone were either stored or superseded by newer updates.
+    #[tracing::instrument(level = "debug")]
+    pub fn pending_flush_journal(&self, journal: &FlushJournal) -> FlushJournal
+    {
+        let mut pending = journal.clone();
+        let torrents = self.torrents_updates.read_recursive();
+        pending.torrents.retain(|(timestamp, ..)| torrents.contains_key(timestamp));
+        let whitelist = self.torrents_whitelist_updates.read_recursive();
+        pending.whitelist.retain(|(timestamp, ..)| whitelist.contains_key(timestamp));
+        let blacklist = self.torrents_blacklist_updates.read_recursive();
+        pending.blacklist.retain(|(timestamp, ..)| blacklist.contains_key(timestamp));
+        let keys = self.keys_updates.read_recursive();
+        pending.keys.retain(|(timestamp, ..)| keys.contains_key(timestamp));
+        let users = self.users_updates.read_recursive();
+        pending.users.retain(|(timestamp, ..)| users.contains_key(timestamp));
+        pending
+    }
+
+    /// Copies every queued update into the journal, returns the copied updates so they can leave the queues once the
+    /// journal is written.
+    #[tracing::instrument(level = "debug")]
+    pub fn copy_flush_queue(&self, journal: &mut FlushJournal) -> FlushJournal
+    {
+        let mut copied = FlushJournal::default();
+        for (timestamp, (info_hash, torrent_entry, updates_action)) in self.get_torrent_updates().into_iter() {
+            copied.torrents.push((timestamp, info_hash, torrent_entry.completed, updates_action));
+        }
+        if self.config.tracker_config.whitelist_enabled {
+            for (timestamp, (info_hash, updates_action)) in self.get_whitelist_updates().into_iter() {
+                copied.whitelist.push((timestamp, info_hash, updates_action));
+            }
+        }
+        if self.config.tracker_config.blacklist_enabled {
+            for (timestamp, (info_hash, updates_action)) in self.get_blacklist_updates().into_iter() {
+                copied.blacklist.push((timestamp, info_hash, updates_action));
+            }
+        }
+        if self.config.tracker_config.keys_enabled {
+            for (timestamp, (info_hash, timeout, updates_action)) in self.get_key_updates().into_iter() {
+                copied.keys.push((timestamp, info_hash, timeout, updates_action));
+            }
+        }
+        if self.config.tracker_config.users_enabled {
+            for (timestamp, (user_id, user_entry_item, updates_action)) in self.get_user_updates().into_iter() {
+                copied.users.push((timestamp, user_id, user_entry_item, updates_action));
+            }
+        }
+        journal.torrents.extend(copied.torrents.iter().cloned());
+        journal.whitelist.extend(copied.whitelist.iter().cloned());
+        journal.blacklist.extend(copied.blacklist.iter().cloned());
+        journal.keys.extend(copied.keys.iter().cloned());
+        journal.users.extend(copied.users.iter().cloned());
+        copied
+    }
+
+    #[tracing::instrument(level = "debug")]
+    pub fn remove_flush_queue(&self, copied: &FlushJournal)
+    {
+        for (timestamp, ..) in copied.torrents.iter() {
+            self.remove_torrent_update(timestamp);
+        }
+        for (timestamp, ..) in copied.whitelist.iter() {
+            self.remove_whitelist_update(timestamp);
+        }
+        for (timestamp, ..) in copied.blacklist.iter() {
+            self.remove_blacklist_update(timestamp);
+        }
+        for (timestamp, ..) in copied.keys.iter() {
+            self.remove_key_update(timestamp);
+        }
+        for (timestamp, ..) in copied.users.iter() {
+            self.remove_user_update(timestamp);
+        }
+    }
+
+    #[tracing::instrument(level = "debug")]
+    pub fn count_flush_queue(&self) -> usize
+    {
+        self.torrents_updates.read_recursive().len()
+            + self.torrents_whitelist_updates.read_recursive().len()
+            + self.torrents_blacklist_updates.read_recursive().len()
+            + self.keys_updates.read_recursive().len()
+            + self.users_updates.read_recursive().len()
+    }
+}
@@ -0,0 +1,33 @@
+use actix_web::web::Bytes;
+use bip_bencode::{ben_int, ben_map};
+use crate::tracker::structs::torrent_tracker::TorrentTracker;
+
+impl TorrentTracker {
+    #[tracing::instrument(level = "debug")]
+    pub fn update_full_scrape(&self)
+    {
+        // Every shard becomes one pre-encoded chunk of the "files" dictionary, shards are ordered by the
+        // leading bits of the info_hash, so concatenating them keeps the bencode keys sorted.
+        let mut full_scrape = Vec::with_capacity(self.torrents_sharding.shards_amount());
+        for shard in 0..self.torrents_sharding.shards_amount() {
+            let mut chunk = Vec::new();
+            for (info_hash, torrent_entry) in self.torrents_sharding.get_shard_content(shard).iter() {
+                chunk.extend_from_slice(b"20:");
+                chunk.extend_from_slice(&info_hash.0);
+                chunk.extend(ben_map! {
+                    "complete" => ben_int!(torrent_entry.seeds.len() as i64),
+                    "downloaded" => ben_int!(torrent_entry.completed as i64),
+                    "incomplete" => ben_int!(torrent_entry.peers.len() as i64)
+                }.encode());
+            }
+            full_scrape.push(Bytes::from(chunk));
+        }
+        *self.full_scrape.write() = full_scrape;
+    }
+
+    #[tracing::instrument(level = "debug")]
+    pub fn get_full_scrape(&self) -> Vec<Bytes>
+    {
+        self.full_scrape.read_recursive().clone()
+    }
+}
@@ -1,13 +1,17 @@
 use std::collections::{BTreeMap, HashMap};
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use log::debug;
 use crate::common::structs::custom_error::CustomError;
 use crate::common::structs::number_of_bytes::NumberOfBytes;
+use crate::stats::enums::stats_event::StatsEvent;
 use crate::tracker::enums::announce_event::AnnounceEvent;
+use crate::tracker::enums::request_interval_throttle::RequestIntervalThrottle;
+use crate::tracker::enums::torrent_peers_type::TorrentPeersType;
 use crate::tracker::enums::updates_action::UpdatesAction;
 use crate::tracker::structs::announce_query_request::AnnounceQueryRequest;
+use crate::tracker::structs::announce_selection::AnnounceSelection;
 use crate::tracker::structs::info_hash::InfoHash;
 use crate::tracker::structs::peer_id::PeerId;
 use crate::tracker::structs::scrape_query_request::ScrapeQueryRequest;
@@ -204,8 +208,9 @@ impl TorrentTracker {
             }
         }
 
-        // Validate numwant
-        let mut numwant_integer = 72;
+        // Validate numwant, zero asks for no peers and the default is the maximum
+        let numwant_max = self.config.tracker_config.clone().peers_numwant_max;
+        let mut numwant_integer = numwant_max;
         match query.get("numwant") {
             None => {}
             Some(result) => {
@@ -218,13 +223,24 @@ impl TorrentTracker {
                         Ok(v) => v,
                         Err(_) => return Err(CustomError::new("missing or invalid numwant"))
                     };
-                    if numwant_integer == 0 || numwant_integer > 72 {
-                        numwant_integer = 72;
+                    if numwant_integer > numwant_max {
+                        numwant_integer = numwant_max;
                     }
                 }
             }
         }
 
+        // Validate ip, ipv4 and ipv6 (BEP 7), unparsable or mismatching values are ignored
+        let ip_addr = Self::validate_announce_address(&query, "ip", port_integer);
+        let ipv4_addr = Self::validate_announce_address(&query, "ipv4", port_integer).filter(|addr| addr.is_ipv4());
+        let ipv6_addr = Self::validate_announce_address(&query, "ipv6", port_integer).filter(|addr| addr.is_ipv6());
+
+        // Validate trackerid, an unknown format is treated as not given
+        let tracker_id = query.get("trackerid")
+            .and_then(|result| result.first())
+            .and_then(|result_array| String::from_utf8(result_array.to_vec()).ok())
+            .and_then(|trackerid| u64::from_str_radix(trackerid.as_str(), 16).ok());
+
         let announce_data = AnnounceQueryRequest {
             info_hash: InfoHash::from(&info_hash[0] as &[u8]),
             peer_id: PeerId::from(&peer_id[0] as &[u8]),
@@ -236,6 +252,11 @@ impl TorrentTracker {
             no_peer_id: no_peer_id_bool,
             event: event_integer,
             remote_addr,
+            remote_addr_alternate: None,
+            ip: ip_addr,
+            ipv4: ipv4_addr,
+            ipv6: ipv6_addr,
+            tracker_id,
             numwant: numwant_integer,
         };
 
@@ -243,11 +264,55 @@ impl TorrentTracker {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn handle_announce(&self, data: Arc<TorrentTracker>, announce_query: AnnounceQueryRequest, user_key: Option<UserId>) -> Result<(TorrentPeer, TorrentEntry), CustomError>
+    fn validate_announce_address(query: &HashMap<String, Vec<Vec<u8>>>, key: &str, port: u16) -> Option<SocketAddr>
+    {
+        let address = String::from_utf8(query.get(key)?.first()?.to_vec()).ok()?;
+        if let Ok(socket_addr) = address.parse::<SocketAddr>() {
+            return Some(socket_addr);
+        }
+        match address.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+            Ok(ip) => { Some(SocketAddr::new(ip, port)) }
+            Err(_) => { None }
+        }
+    }
+
+    /// Answers a regular announce within `request_interval_minimum` from the current swarm without changing it, the
+    /// peer, its expiry and the round robin cursor stay as they were, so the shard is only read locked. Returns None when
+    /// the announce has to be handled.
+    #[tracing::instrument(level = "debug")]
+    pub fn check_announce_throttle(&self, announce_query: &AnnounceQueryRequest, peers_type: TorrentPeersType) -> Option<(TorrentPeer, AnnounceSelection)>
+    {
+        if self.config.tracker_config.request_interval_throttle == RequestIntervalThrottle::disabled {
+            return None;
+        }
+        if !matches!(announce_query.event, AnnounceEvent::Started | AnnounceEvent::None) {
+            return None;
+        }
+        let shard = self.torrents_sharding.get_torrent_shard(announce_query.info_hash);
+        let lock = shard.read_recursive();
+        let torrent_entry = lock.get(&announce_query.info_hash)?;
+        let torrent_peer = torrent_entry.seeds.get(&announce_query.peer_id).or_else(|| torrent_entry.peers.get(&announce_query.peer_id))?.clone();
+        if torrent_peer.updated.elapsed() >= Duration::from_secs(self.config.tracker_config.request_interval_minimum) {
+            return None;
+        }
+        let announce_selection = AnnounceSelection {
+            seeds: torrent_entry.seeds.len() as u64,
+            peers: torrent_entry.peers.len() as u64,
+            completed: torrent_entry.completed,
+            peers_selection: self.peek_peers(torrent_entry, peers_type, Some(announce_query.remote_addr), announce_query.numwant as usize, announce_query.left != 0),
+        };
+        self.update_stats(StatsEvent::AnnouncesThrottled, 1);
+        Some((torrent_peer, announce_selection))
+    }
+
+    #[tracing::instrument(level = "debug")]
+    pub async fn handle_announce(&self, data: Arc<TorrentTracker>, announce_query: AnnounceQueryRequest, peers_type: TorrentPeersType, user_key: Option<UserId>, issue_tracker_id: bool) -> Result<(TorrentPeer, AnnounceSelection), CustomError>
     {
         let mut torrent_peer = TorrentPeer {
             peer_id: announce_query.peer_id,
             peer_addr: SocketAddr::new(announce_query.remote_addr, announce_query.port),
+            peer_addr_alternate: announce_query.remote_addr_alternate,
+            tracker_id: announce_query.tracker_id,
             updated: std::time::Instant::now(),
             uploaded: NumberOfBytes(announce_query.uploaded as i64),
             downloaded: NumberOfBytes(announce_query.downloaded as i64),
@@ -259,117 +324,103 @@ impl TorrentTracker {
             AnnounceEvent::Started | AnnounceEvent::None => {
                 torrent_peer.event = AnnounceEvent::Started;
                 debug!("[HANDLE ANNOUNCE] Adding to infohash {} peerid {}", announce_query.info_hash, announce_query.peer_id);
-                debug!("[DEBUG] Calling add_torrent_peer");
+                debug!("[DEBUG] Calling announce_torrent_peer");
 
-                let torrent_entry = data.add_torrent_peer(
+                let (_, announce_selection, torrent_entry) = data.announce_torrent_peer(
                     announce_query.info_hash,
                     announce_query.peer_id,
-                    torrent_peer.clone(),
-                    false
+                    &mut torrent_peer,
+                    peers_type,
+                    announce_query.numwant as usize,
+                    issue_tracker_id
                 );
 
-                if data.config.database.clone().persistent {
+                if let Some(torrent_entry) = torrent_entry {
                     let _ = data.add_torrent_update(
                         announce_query.info_hash,
-                        torrent_entry.1.clone(),
+                        torrent_entry,
                         UpdatesAction::Add
                     );
                 }
 
-                if data.config.tracker_config.clone().users_enabled && user_key.is_some() {
-                    if let Some(mut user) = data.get_user(user_key.unwrap()) {
-                        user.updated = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
-                        user.torrents_active.insert(announce_query.info_hash, SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs());
-                        data.add_user(user_key.unwrap(), user.clone());
-                        if data.config.database.clone().persistent {
-                            data.add_user_update(user_key.unwrap(), user, UpdatesAction::Add);
-                        }
+                if data.config.tracker_config.clone().users_enabled && user_key.is_some() && let Some(mut user) = data.get_user(user_key.unwrap()) {
+                    user.updated = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+                    user.torrents_active.insert(announce_query.info_hash, SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs());
+                    data.add_user(user_key.unwrap(), user.clone());
+                    if data.config.database.clone().persistent {
+                        data.add_user_update(user_key.unwrap(), user, UpdatesAction::Add);
                     }
                 }
 
-                Ok((torrent_peer, TorrentEntry {
-                    seeds: torrent_entry.1.seeds,
-                    peers: torrent_entry.1.peers,
-                    completed: torrent_entry.1.completed,
-                    updated: torrent_entry.1.updated
-                }))
+                Ok((torrent_peer, announce_selection))
             }
             AnnounceEvent::Stopped => {
                 torrent_peer.event = AnnounceEvent::Stopped;
                 debug!("[HANDLE ANNOUNCE] Removing from infohash {} peerid {}", announce_query.info_hash, announce_query.peer_id);
-                debug!("[DEBUG] Calling remove_torrent_peer");
+                debug!("[DEBUG] Calling announce_torrent_peer");
 
-                let torrent_entry = match data.remove_torrent_peer(
+                let (found, announce_selection, torrent_entry) = data.announce_torrent_peer(
                     announce_query.info_hash,
                     announce_query.peer_id,
-                    data.config.database.clone().persistent,
-                    false
-                ) {
-                    (Some(_), None) => {
-                        TorrentEntry::new()
-                    }
-                    (Some(_), Some(new_torrent)) => {
-                        if data.config.tracker_config.clone().users_enabled && user_key.is_some(){
-                            if let Some(mut user) = data.get_user(user_key.unwrap()) {
-                                user.uploaded += announce_query.uploaded;
-                                user.downloaded += announce_query.downloaded;
-                                user.updated = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
-                                user.torrents_active.remove(&announce_query.info_hash);
-                                data.add_user(user_key.unwrap(), user.clone());
-                                if data.config.database.clone().persistent {
-                                    data.add_user_update(user_key.unwrap(), user, UpdatesAction::Add);
-                                }
-                            }
-                        }
-                        new_torrent
-                    }
-                    _ => {
-                        TorrentEntry::new()
+                    &mut torrent_peer,
+                    peers_type,
+                    announce_query.numwant as usize,
+                    issue_tracker_id
+                );
+
+                if found && data.config.tracker_config.clone().users_enabled && user_key.is_some() && let Some(mut user) = data.get_user(user_key.unwrap()) {
+                    user.uploaded += announce_query.uploaded;
+                    user.downloaded += announce_query.downloaded;
+                    user.updated = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+                    user.torrents_active.remove(&announce_query.info_hash);
+                    data.add_user(user_key.unwrap(), user.clone());
+                    if data.config.database.clone().persistent {
+                        data.add_user_update(user_key.unwrap(), user, UpdatesAction::Add);
                     }
-                };
+                }
 
-                if data.config.database.clone().persistent {
+                if let Some(torrent_entry) = torrent_entry {
                     let _ = data.add_torrent_update(
                         announce_query.info_hash,
-                        torrent_entry.clone(),
+                        torrent_entry,
                         UpdatesAction::Add
                     );
                 }
 
-                Ok((torrent_peer, torrent_entry))
+                Ok((torrent_peer, announce_selection))
             }
             AnnounceEvent::Completed => {
                 torrent_peer.event = AnnounceEvent::Completed;
                 debug!("[HANDLE ANNOUNCE] Adding to infohash {} peerid {}", announce_query.info_hash, announce_query.peer_id);
-                debug!("[DEBUG] Calling add_torrent_peer");
+                debug!("[DEBUG] Calling announce_torrent_peer");
 
-                let torrent_entry = data.add_torrent_peer(
+                let (_, announce_selection, torrent_entry) = data.announce_torrent_peer(
                     announce_query.info_hash,
                     announce_query.peer_id,
-                    torrent_peer.clone(),
-                    true
+                    &mut torrent_peer,
+                    peers_type,
+                    announce_query.numwant as usize,
+                    issue_tracker_id
                 );
 
-                if data.config.database.clone().persistent {
+                if let Some(torrent_entry) = torrent_entry {
                     let _ = data.add_torrent_update(
                         announce_query.info_hash,
-                        torrent_entry.1.clone(),
+                        torrent_entry,
                         UpdatesAction::Add
                     );
                 }
 
-                if data.config.tracker_config.clone().users_enabled && user_key.is_some(){
-                    if let Some(mut user) = data.get_user(user_key.unwrap()) {
-                        user.completed += 1;
-                        user.updated = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
-                        data.add_user(user_key.unwrap(), user.clone());
-                        if data.config.database.clone().persistent {
-                            data.add_user_update(user_key.unwrap(), user, UpdatesAction::Add);
-                        }
+                if data.config.tracker_config.clone().users_enabled && user_key.is_some() && let Some(mut user) = data.get_user(user_key.unwrap()) {
+                    user.completed += 1;
+                    user.updated = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+                    data.add_user(user_key.unwrap(), user.clone());
+                    if data.config.database.clone().persistent {
+                        data.add_user_update(user_key.unwrap(), user, UpdatesAction::Add);
                     }
                 }
 
-                Ok((torrent_peer, torrent_entry.1))
+                Ok((torrent_peer, announce_selection))
             }
         }
     }
@@ -36,6 +36,7 @@ impl TorrentTracker {
                         peers: Default::default(),
                         completed,
                         updated: std::time::Instant::now(),
+                        peers_cursor: 0,
                     }, UpdatesAction::Add);
                 }
                 match tracker.save_torrent_updates(tracker.clone()).await {
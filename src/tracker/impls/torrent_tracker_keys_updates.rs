@@ -63,8 +63,12 @@ impl TorrentTracker {
         for (timestamp, (info_hash, timeout, updates_action)) in self.get_key_updates().iter() {
             match mapping.entry(*info_hash) {
                 Entry::Occupied(mut o) => {
-                    o.insert((o.get().0, *timeout, *updates_action));
-                    self.remove_key_update(timestamp);
+                    if *timestamp > o.get().0 {
+                        self.remove_key_update(&o.get().0);
+                        o.insert((*timestamp, *timeout, *updates_action));
+                    } else {
+                        self.remove_key_update(timestamp);
+                    }
                 }
                 Entry::Vacant(v) => {
                     v.insert((*timestamp, *timeout, *updates_action));
@@ -0,0 +1,98 @@
+use std::cmp::Reverse;
+use std::mem::size_of;
+use std::time::Instant;
+use crate::stats::enums::stats_event::StatsEvent;
+use crate::tracker::enums::updates_action::UpdatesAction;
+use crate::tracker::structs::info_hash::InfoHash;
+use crate::tracker::structs::peer_id::PeerId;
+use crate::tracker::structs::shard_stats::ShardStats;
+use crate::tracker::structs::torrent_entry::TorrentEntry;
+use crate::tracker::structs::torrent_peer::TorrentPeer;
+use crate::tracker::structs::torrent_tracker::TorrentTracker;
+use crate::tracker::structs::user_entry_item::UserEntryItem;
+use crate::tracker::structs::user_id::UserId;
+
+impl TorrentTracker {
+    /// Approximate bytes per BTreeMap entry, nodes hold up to 11 entries and are about two thirds full on average.
+    pub fn btree_entry_size<K, V>() -> usize
+    {
+        (size_of::<K>() + size_of::<V>()) * 3 / 2
+    }
+
+    /// Approximate bytes per HashMap entry, one control byte per bucket and a maximum load factor of 7/8.
+    pub fn hash_entry_size<K, V>() -> usize
+    {
+        (size_of::<K>() + size_of::<V>() + 1) * 8 / 7
+    }
+
+    /// Approximate bytes owned by a torrent entry besides its own slot, which are its seeds and peers.
+    pub fn torrent_entry_memory(torrent_entry: &TorrentEntry) -> usize
+    {
+        (torrent_entry.seeds.len() + torrent_entry.peers.len()) * Self::btree_entry_size::<PeerId, TorrentPeer>()
+    }
+
+    pub fn user_entry_memory(user_entry: &UserEntryItem) -> usize
+    {
+        user_entry.user_uuid.as_ref().map(|uuid| uuid.capacity()).unwrap_or(0) + user_entry.torrents_active.len() * Self::btree_entry_size::<InfoHash, u64>()
+    }
+
+    /// Counts the torrents, seeds and peers of a shard and estimates its memory, including its expiry queue.
+    #[tracing::instrument(level = "debug")]
+    pub fn update_shard_stats(&self, shard: usize) -> ShardStats
+    {
+        let mut shard_stats = ShardStats::default();
+        let mut memory = 0usize;
+        {
+            let lock = self.torrents_sharding.get_shard(shard).unwrap();
+            let lock = lock.read_recursive();
+            shard_stats.torrents = lock.len() as i64;
+            for torrent_entry in lock.values() {
+                shard_stats.seeds += torrent_entry.seeds.len() as i64;
+                shard_stats.peers += torrent_entry.peers.len() as i64;
+                memory += Self::torrent_entry_memory(torrent_entry);
+            }
+            memory += lock.len() * Self::btree_entry_size::<InfoHash, TorrentEntry>();
+        }
+        memory += self.torrents_sharding.expiry[shard].lock().capacity() * size_of::<Reverse<(Instant, InfoHash, PeerId)>>();
+        shard_stats.memory = memory as i64;
+        *self.torrents_sharding.shard_stats[shard].lock() = shard_stats;
+        shard_stats
+    }
+
+    #[tracing::instrument(level = "debug")]
+    pub fn get_shard_stats(&self) -> Vec<ShardStats>
+    {
+        self.torrents_sharding.shard_stats.iter().map(|shard_stats| *shard_stats.lock()).collect()
+    }
+
+    /// Refreshes the memory estimates, the torrents figure sums the per-shard values last refreshed by the cleanup.
+    #[tracing::instrument(level = "debug")]
+    pub fn update_memory_stats(&self)
+    {
+        let memory_torrents: i64 = self.get_shard_stats().iter().map(|shard_stats| shard_stats.memory).sum();
+        let memory_whitelist = self.torrents_whitelist.read_recursive().capacity() * size_of::<InfoHash>();
+        let memory_blacklist = self.torrents_blacklist.read_recursive().capacity() * size_of::<InfoHash>();
+        let memory_keys = self.keys.read_recursive().len() * Self::btree_entry_size::<InfoHash, i64>();
+        let memory_users = {
+            let lock = self.users.read_recursive();
+            lock.len() * Self::btree_entry_size::<UserId, UserEntryItem>() + lock.values().map(Self::user_entry_memory).sum::<usize>()
+        };
+        let memory_updates = {
+            let torrents_updates = self.torrents_updates.read_recursive();
+            let users_updates = self.users_updates.read_recursive();
+            torrents_updates.capacity() * Self::hash_entry_size::<u128, (InfoHash, TorrentEntry, UpdatesAction)>()
+                + torrents_updates.values().map(|(_, torrent_entry, _)| Self::torrent_entry_memory(torrent_entry)).sum::<usize>()
+                + self.torrents_whitelist_updates.read_recursive().capacity() * Self::hash_entry_size::<u128, (InfoHash, UpdatesAction)>()
+                + self.torrents_blacklist_updates.read_recursive().capacity() * Self::hash_entry_size::<u128, (InfoHash, UpdatesAction)>()
+                + self.keys_updates.read_recursive().capacity() * Self::hash_entry_size::<u128, (InfoHash, i64, UpdatesAction)>()
+                + users_updates.capacity() * Self::hash_entry_size::<u128, (UserId, UserEntryItem, UpdatesAction)>()
+                + users_updates.values().map(|(_, user_entry, _)| Self::user_entry_memory(user_entry)).sum::<usize>()
+        };
+        self.set_stats(StatsEvent::MemoryTorrents, memory_torrents);
+        self.set_stats(StatsEvent::MemoryWhitelist, memory_whitelist as i64);
+        self.set_stats(StatsEvent::MemoryBlacklist, memory_blacklist as i64);
+        self.set_stats(StatsEvent::MemoryKeys, memory_keys as i64);
+        self.set_stats(StatsEvent::MemoryUsers, memory_users as i64);
+        self.set_stats(StatsEvent::MemoryUpdates, memory_updates as i64);
+    }
+}
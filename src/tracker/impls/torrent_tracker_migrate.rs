@@ -0,0 +1,47 @@
+use std::process::exit;
+use std::sync::Arc;
+use log::{error, info};
+use crate::database::structs::database_migration::DatabaseMigration;
+use crate::structs::Cli;
+use crate::tracker::structs::torrent_tracker::TorrentTracker;
+
+impl TorrentTracker {
+    /// Handles `--migrate`, applying the pending schema migrations or printing their SQL with `--migrate-dry-run`.
+    #[tracing::instrument(level = "debug")]
+    pub async fn migrate(&self, args: &Cli, tracker: Arc<TorrentTracker>)
+    {
+        info!("[MIGRATE] Requesting to migrate the database schema");
+        let migrations = match self.migrate_database(tracker.clone(), args.migrate_dry_run).await {
+            Ok(migrations) => { migrations }
+            Err(_) => { exit(1) }
+        };
+        if args.migrate_dry_run {
+            for migration in migrations.iter() {
+                println!("-- Migration {} {}", migration.version, migration.name);
+                for statement in migration.statements.iter() {
+                    println!("{};", statement);
+                }
+            }
+            info!("[MIGRATE] Dry run, {} migrations pending", migrations.len());
+        }
+        exit(0)
+    }
+
+    /// Applies the pending schema migrations, or only returns them with `dry_run`.
+    #[tracing::instrument(level = "debug")]
+    pub async fn migrate_database(&self, tracker: Arc<TorrentTracker>, dry_run: bool) -> Result<Vec<DatabaseMigration>, ()>
+    {
+        match self.sqlx.migrate(tracker.clone(), dry_run).await {
+            Ok(migrations) => {
+                if !dry_run {
+                    info!("[MIGRATE] Applied {} migrations, schema at version {}", migrations.len(), self.sqlx.schema_version().await.unwrap_or(0));
+                }
+                Ok(migrations)
+            }
+            Err(error) => {
+                error!("[MIGRATE] Unable to migrate the database schema: {}", error);
+                Err(())
+            }
+        }
+    }
+}
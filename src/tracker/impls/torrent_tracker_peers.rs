@@ -1,160 +1,197 @@
 use std::collections::BTreeMap;
 use std::collections::btree_map::Entry;
-use std::net::{IpAddr, SocketAddr};
+use std::cmp::Reverse;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
 use log::info;
+use rand::seq::IteratorRandom;
 use crate::common::structs::number_of_bytes::NumberOfBytes;
 use crate::stats::enums::stats_event::StatsEvent;
+use crate::tracker::enums::announce_event::AnnounceEvent;
+use crate::tracker::enums::peers_selection::PeersSelection;
 use crate::tracker::enums::torrent_peers_type::TorrentPeersType;
+use crate::tracker::structs::announce_selection::AnnounceSelection;
 use crate::tracker::structs::info_hash::InfoHash;
 use crate::tracker::structs::peer_id::PeerId;
 use crate::tracker::structs::torrent_entry::TorrentEntry;
 use crate::tracker::structs::torrent_peer::TorrentPeer;
-use crate::tracker::structs::torrent_peers::TorrentPeers;
+use crate::tracker::structs::torrent_sharding::TorrentSharding;
 use crate::tracker::structs::torrent_tracker::TorrentTracker;
 
 impl TorrentTracker {
-    #[tracing::instrument(level = "debug")]
-    pub fn get_torrent_peers(&self, info_hash: InfoHash, amount: usize, ip_type: TorrentPeersType, self_ip: Option<IpAddr>) -> Option<TorrentPeers>
+    /// Picks the peers to return from a swarm the caller holds the shard lock for, only the selected peers are copied.
+    #[tracing::instrument(level = "debug", skip(torrent_entry))]
+    pub fn select_peers(&self, torrent_entry: &mut TorrentEntry, type_ip: TorrentPeersType, self_ip: Option<IpAddr>, amount: usize, include_seeds: bool) -> Vec<(PeerId, TorrentPeer)>
     {
-        let mut returned_data = TorrentPeers {
-            seeds_ipv4: BTreeMap::new(),
-            seeds_ipv6: BTreeMap::new(),
-            peers_ipv4: BTreeMap::new(),
-            peers_ipv6: BTreeMap::new()
-        };
-        match self.get_torrent(info_hash) {
-            None => { None }
-            Some(data) => {
-                match ip_type {
-                    TorrentPeersType::All => {
-                        returned_data.seeds_ipv4 = self.get_peers(data.seeds.clone(), TorrentPeersType::IPv4, self_ip, amount).unwrap_or_default();
-                        returned_data.seeds_ipv6 = self.get_peers(data.seeds.clone(), TorrentPeersType::IPv6, self_ip, amount).unwrap_or_default();
-                        returned_data.peers_ipv4 = self.get_peers(data.peers.clone(), TorrentPeersType::IPv4, self_ip, amount).unwrap_or_default();
-                        returned_data.peers_ipv6 = self.get_peers(data.peers.clone(), TorrentPeersType::IPv6, self_ip, amount).unwrap_or_default();
-                    }
-                    TorrentPeersType::IPv4 => {
-                        returned_data.seeds_ipv4 = self.get_peers(data.seeds.clone(), TorrentPeersType::IPv4, self_ip, amount).unwrap_or_default();
-                        returned_data.peers_ipv4 = self.get_peers(data.peers.clone(), TorrentPeersType::IPv4, self_ip, amount).unwrap_or_default();
-                    }
-                    TorrentPeersType::IPv6 => {
-                        returned_data.seeds_ipv6 = self.get_peers(data.seeds.clone(), TorrentPeersType::IPv6, self_ip, amount).unwrap_or_default();
-                        returned_data.peers_ipv6 = self.get_peers(data.peers.clone(), TorrentPeersType::IPv6, self_ip, amount).unwrap_or_default();
-                    }
+        let (selection, rotated) = self.choose_peers(torrent_entry, type_ip, self_ip, amount, include_seeds);
+        if rotated {
+            torrent_entry.peers_cursor = torrent_entry.peers_cursor.wrapping_add(amount);
+        }
+        selection
+    }
+
+    /// Picks the peers like `select_peers` without advancing the round robin cursor, for responses that must leave
+    /// the swarm untouched.
+    #[tracing::instrument(level = "debug", skip(torrent_entry))]
+    pub fn peek_peers(&self, torrent_entry: &TorrentEntry, type_ip: TorrentPeersType, self_ip: Option<IpAddr>, amount: usize, include_seeds: bool) -> Vec<(PeerId, TorrentPeer)>
+    {
+        self.choose_peers(torrent_entry, type_ip, self_ip, amount, include_seeds).0
+    }
+
+    /// Returns the picked peers, and whether the round robin cursor has to move on by `amount`.
+    fn choose_peers(&self, torrent_entry: &TorrentEntry, type_ip: TorrentPeersType, self_ip: Option<IpAddr>, amount: usize, include_seeds: bool) -> (Vec<(PeerId, TorrentPeer)>, bool)
+    {
+        if amount == 0 {
+            return (Vec::new(), false);
+        }
+        let seeds = torrent_entry.seeds.iter().filter(move |(_, torrent_peer)| include_seeds && Self::peer_matches(torrent_peer, type_ip, self_ip));
+        let peers = torrent_entry.peers.iter().filter(move |(_, torrent_peer)| Self::peer_matches(torrent_peer, type_ip, self_ip));
+        let mut rng = rand::rng();
+        let mut rotated = false;
+        let selection = match self.config.tracker_config.peers_selection {
+            PeersSelection::random => {
+                seeds.chain(peers).choose_multiple(&mut rng, amount)
+            }
+            PeersSelection::round_robin => {
+                let pool = seeds.chain(peers);
+                let pool_size = pool.clone().count();
+                if pool_size <= amount {
+                    pool.collect()
+                } else {
+                    rotated = true;
+                    pool.cycle().skip(torrent_entry.peers_cursor % pool_size).take(amount).collect()
                 }
-                Some(returned_data)
             }
+            PeersSelection::prefer_seeds => {
+                let mut selection = seeds.choose_multiple(&mut rng, amount);
+                let remaining = amount - selection.len();
+                selection.extend(peers.choose_multiple(&mut rng, remaining));
+                selection
+            }
+        };
+        (selection.into_iter().map(|(peer_id, torrent_peer)| (*peer_id, torrent_peer.clone())).collect(), rotated)
+    }
+
+    fn peer_matches(torrent_peer: &TorrentPeer, type_ip: TorrentPeersType, self_ip: Option<IpAddr>) -> bool
+    {
+        if let Some(ip) = self_ip && (ip == torrent_peer.peer_addr.ip() || Some(ip) == torrent_peer.peer_addr_alternate.map(|addr| addr.ip())) {
+            return false;
+        }
+        match type_ip {
+            TorrentPeersType::All => { true }
+            TorrentPeersType::IPv4 => { torrent_peer.peer_addr_ipv4().is_some() }
+            TorrentPeersType::IPv6 => { torrent_peer.peer_addr_ipv6().is_some() }
         }
     }
 
     #[tracing::instrument(level = "debug")]
-    pub fn get_peers(&self, peers: BTreeMap<PeerId, TorrentPeer>, type_ip: TorrentPeersType, self_ip: Option<IpAddr>, amount: usize) -> Option<BTreeMap<PeerId, TorrentPeer>>
+    pub fn get_torrent_peer(&self, info_hash: InfoHash, peer_id: PeerId) -> Option<TorrentPeer>
     {
-        if amount != 0 {
-            return peers.iter().take(amount).map(|(peer_id, torrent_peer)| {
-                match type_ip {
-                    TorrentPeersType::All => { None }
-                    TorrentPeersType::IPv4 => {
-                        match self_ip {
-                            None => {
-                                match torrent_peer.peer_addr {
-                                    SocketAddr::V4(_) => { Some((*peer_id, torrent_peer.clone())) }
-                                    SocketAddr::V6(_) => { None}
-                                }
-                            }
-                            Some(ip) => {
-                                if ip != torrent_peer.peer_addr.ip() {
-                                    match torrent_peer.peer_addr {
-                                        SocketAddr::V4(_) => { Some((*peer_id, torrent_peer.clone())) }
-                                        SocketAddr::V6(_) => { None }
-                                    }
-                                } else {
-                                    None
-                                }
-                            }
-                        }
-                    }
-                    TorrentPeersType::IPv6 => {
-                        match self_ip {
-                            None => {
-                                match torrent_peer.peer_addr {
-                                    SocketAddr::V4(_) => { None }
-                                    SocketAddr::V6(_) => { Some((*peer_id, torrent_peer.clone())) }
-                                }
-                            }
-                            Some(ip) => {
-                                if ip != torrent_peer.peer_addr.ip() {
-                                    match torrent_peer.peer_addr {
-                                        SocketAddr::V4(_) => { None }
-                                        SocketAddr::V6(_) => { Some((*peer_id, torrent_peer.clone())) }
-                                    }
-                                } else {
-                                    None
-                                }
-                            }
-                        }
-                    }
-                }
-            }).collect();
+        let shard = self.torrents_sharding.get_torrent_shard(info_hash);
+        let lock = shard.read_recursive();
+        let torrent_entry = lock.get(&info_hash)?;
+        torrent_entry.seeds.get(&peer_id).or_else(|| torrent_entry.peers.get(&peer_id)).cloned()
+    }
+
+    /// Adds or replaces the peer, returns true when the torrent was created by it.
+    #[tracing::instrument(level = "debug")]
+    pub fn add_torrent_peer(&self, info_hash: InfoHash, peer_id: PeerId, torrent_peer: TorrentPeer, completed: bool) -> bool
+    {
+        let shard = self.torrents_sharding.get_torrent_shard(info_hash);
+        let mut lock = shard.write();
+        self.insert_torrent_peer(&mut lock, info_hash, peer_id, torrent_peer, completed)
+    }
+
+    /// Removes the peer, returns whether it was found and whether the torrent is still present afterward.
+    #[tracing::instrument(level = "debug")]
+    pub fn remove_torrent_peer(&self, info_hash: InfoHash, peer_id: PeerId, persistent: bool, cleanup: bool) -> (bool, bool)
+    {
+        let shard = self.torrents_sharding.get_torrent_shard(info_hash);
+        let mut lock = shard.write();
+        if cleanup {
+            info!("[PEERS] Removing from torrent {} peer {}", info_hash, peer_id);
         }
-        peers.iter().map(|(peer_id, torrent_peer)| {
-            match type_ip {
-                TorrentPeersType::All => { None }
-                TorrentPeersType::IPv4 => {
-                    match self_ip {
-                        None => {
-                            match torrent_peer.peer_addr {
-                                SocketAddr::V4(_) => { Some((*peer_id, torrent_peer.clone())) }
-                                SocketAddr::V6(_) => { None}
-                            }
-                        }
-                        Some(ip) => {
-                            if ip != torrent_peer.peer_addr.ip() {
-                                match torrent_peer.peer_addr {
-                                    SocketAddr::V4(_) => { Some((*peer_id, torrent_peer.clone())) }
-                                    SocketAddr::V6(_) => { None }
-                                }
-                            } else {
-                                None
-                            }
-                        }
-                    }
-                }
-                TorrentPeersType::IPv6 => {
-                    match self_ip {
-                        None => {
-                            match torrent_peer.peer_addr {
-                                SocketAddr::V4(_) => { None }
-                                SocketAddr::V6(_) => { Some((*peer_id, torrent_peer.clone())) }
-                            }
-                        }
-                        Some(ip) => {
-                            if ip != torrent_peer.peer_addr.ip() {
-                                match torrent_peer.peer_addr {
-                                    SocketAddr::V4(_) => { None }
-                                    SocketAddr::V6(_) => { Some((*peer_id, torrent_peer.clone())) }
-                                }
-                            } else {
-                                None
-                            }
-                        }
-                    }
-                }
-            }
-        }).collect()
+        self.delete_torrent_peer(&mut lock, info_hash, peer_id, persistent)
     }
 
+    /// Applies an announce and picks the returned peers under a single shard lock, so the swarm is never cloned.
+    /// The torrent entry itself is only copied when persistence needs it for the database updates. The returned flag
+    /// is false when a stopped peer was unknown or its torrent was removed with it. With `issue_tracker_id` the tracker
+    /// id of the peer is resolved against its previous state under the same lock.
     #[tracing::instrument(level = "debug")]
-    pub fn add_torrent_peer(&self, info_hash: InfoHash, peer_id: PeerId, torrent_peer: TorrentPeer, completed: bool) -> (Option<TorrentEntry>, TorrentEntry)
+    pub fn announce_torrent_peer(&self, info_hash: InfoHash, peer_id: PeerId, torrent_peer: &mut TorrentPeer, type_ip: TorrentPeersType, amount: usize, issue_tracker_id: bool) -> (bool, AnnounceSelection, Option<TorrentEntry>)
     {
-        let shard = self.torrents_sharding.clone().get_shard(info_hash.0[0]).unwrap();
+        let persistent = self.config.database.persistent;
+        let self_ip = Some(torrent_peer.peer_addr.ip());
+        let include_seeds = torrent_peer.left != NumberOfBytes(0);
+        let shard = self.torrents_sharding.get_torrent_shard(info_hash);
         let mut lock = shard.write();
-        match lock.entry(info_hash) {
+        if issue_tracker_id {
+            let known_tracker_id = lock.get(&info_hash)
+                .and_then(|torrent_entry| torrent_entry.seeds.get(&peer_id).or_else(|| torrent_entry.peers.get(&peer_id)))
+                .and_then(|previous| previous.tracker_id);
+            torrent_peer.tracker_id = self.resolve_tracker_id(torrent_peer.tracker_id, known_tracker_id);
+        }
+        let found = match torrent_peer.event {
+            AnnounceEvent::Stopped => {
+                let (removed, present) = self.delete_torrent_peer(&mut lock, info_hash, peer_id, persistent);
+                removed && present
+            }
+            AnnounceEvent::Completed => {
+                self.insert_torrent_peer(&mut lock, info_hash, peer_id, torrent_peer.clone(), true);
+                true
+            }
+            _ => {
+                self.insert_torrent_peer(&mut lock, info_hash, peer_id, torrent_peer.clone(), false);
+                true
+            }
+        };
+        match lock.get_mut(&info_hash) {
+            None => {
+                (found, AnnounceSelection::default(), persistent.then(TorrentEntry::new))
+            }
+            Some(torrent_entry) => {
+                let announce_selection = AnnounceSelection {
+                    seeds: torrent_entry.seeds.len() as u64,
+                    peers: torrent_entry.peers.len() as u64,
+                    completed: torrent_entry.completed,
+                    peers_selection: self.select_peers(torrent_entry, type_ip, self_ip, amount, include_seeds),
+                };
+                (found, announce_selection, persistent.then(|| torrent_entry.clone()))
+            }
+        }
+    }
+
+    /// Keeps the tracker id the peer was given before, unless it presents a different one, otherwise issues a new one.
+    fn resolve_tracker_id(&self, tracker_id: Option<u64>, known_tracker_id: Option<u64>) -> Option<u64>
+    {
+        match (tracker_id, known_tracker_id) {
+            (Some(tracker_id), Some(known)) if tracker_id == known => {
+                self.update_stats(StatsEvent::TcpTrackerIdRecognized, 1);
+                Some(known)
+            }
+            (None, Some(known)) => {
+                Some(known)
+            }
+            _ => {
+                self.update_stats(StatsEvent::TcpTrackerIdIssued, 1);
+                Some(rand::random())
+            }
+        }
+    }
+
+    fn insert_torrent_peer(&self, shard: &mut BTreeMap<InfoHash, TorrentEntry>, info_hash: InfoHash, peer_id: PeerId, torrent_peer: TorrentPeer, completed: bool) -> bool
+    {
+        self.torrents_sharding.schedule_expiry(info_hash, peer_id, torrent_peer.updated);
+        self.update_stats(StatsEvent::ExpiryQueue, 1);
+        match shard.entry(info_hash) {
             Entry::Vacant(v) => {
                 let mut torrent_entry = TorrentEntry {
                     seeds: BTreeMap::new(),
                     peers: BTreeMap::new(),
                     completed: if completed && torrent_peer.left == NumberOfBytes(0) { self.update_stats(StatsEvent::Completed, 1); 1 } else { 0 },
-                    updated: std::time::Instant::now()
+                    updated: std::time::Instant::now(),
+                    peers_cursor: 0
                 };
                 self.update_stats(StatsEvent::Torrents, 1);
                 match torrent_peer.left {
@@ -167,11 +204,10 @@ impl TorrentTracker {
                         torrent_entry.peers.insert(peer_id, torrent_peer);
                     }
                 }
-                v.insert(torrent_entry.clone());
-                (None, torrent_entry)
+                v.insert(torrent_entry);
+                true
             }
             Entry::Occupied(mut o) => {
-                let previous_torrent = o.get().clone();
                 if o.get_mut().seeds.remove(&peer_id).is_some() {
                     self.update_stats(StatsEvent::Seeds, -1);
                 };
@@ -182,6 +218,7 @@ impl TorrentTracker {
                     self.update_stats(StatsEvent::Completed, 1);
                     o.get_mut().completed += 1;
                 }
+                o.get_mut().updated = torrent_peer.updated;
                 match torrent_peer.left {
                     NumberOfBytes(0) => {
                         self.update_stats(StatsEvent::Seeds, 1);
@@ -192,39 +229,89 @@ impl TorrentTracker {
                         o.get_mut().peers.insert(peer_id, torrent_peer);
                     }
                 }
-                (Some(previous_torrent), o.get().clone())
+                false
             }
         }
     }
 
-    #[tracing::instrument(level = "debug")]
-    pub fn remove_torrent_peer(&self, info_hash: InfoHash, peer_id: PeerId, persistent: bool, cleanup: bool) -> (Option<TorrentEntry>, Option<TorrentEntry>)
+    fn delete_torrent_peer(&self, shard: &mut BTreeMap<InfoHash, TorrentEntry>, info_hash: InfoHash, peer_id: PeerId, persistent: bool) -> (bool, bool)
     {
-        if !self.torrents_sharding.contains_peer(info_hash, peer_id) { return (None, None); }
-        let shard = self.torrents_sharding.clone().get_shard(info_hash.0[0]).unwrap();
-        let mut lock = shard.write();
-        match lock.entry(info_hash) {
+        match shard.entry(info_hash) {
             Entry::Vacant(_) => {
-                (None, None)
+                (false, false)
             }
             Entry::Occupied(mut o) => {
-                if cleanup {
-                    info!("[PEERS] Removing from torrent {} peer {}", info_hash, peer_id);
-                }
-                let previous_torrent = o.get().clone();
+                let mut removed = false;
                 if o.get_mut().seeds.remove(&peer_id).is_some() {
                     self.update_stats(StatsEvent::Seeds, -1);
+                    removed = true;
                 };
                 if o.get_mut().peers.remove(&peer_id).is_some() {
                     self.update_stats(StatsEvent::Peers, -1);
+                    removed = true;
                 };
-                if !persistent && o.get().seeds.is_empty() && o.get().peers.is_empty() {
-                    lock.remove(&info_hash);
+                if removed && !persistent && o.get().seeds.is_empty() && o.get().peers.is_empty() {
+                    o.remove();
                     self.update_stats(StatsEvent::Torrents, -1);
-                    return (Some(previous_torrent), None);
+                    return (true, false);
                 }
-                (Some(previous_torrent), Some(o.get().clone()))
+                (removed, true)
+            }
+        }
+    }
+
+    /// Removes the peers of a shard that did not announce within `peer_timeout`. Only due entries of the shard's
+    /// expiry queue are visited, and they are removed in batches per shard lock acquisition.
+    #[tracing::instrument(level = "debug")]
+    pub fn expire_peers(&self, shard: usize, peer_timeout: Duration, persistent: bool) -> (u64, u64, u64)
+    {
+        let (mut torrents, mut seeds, mut peers) = (0u64, 0u64, 0u64);
+        let Some(deadline) = Instant::now().checked_sub(peer_timeout) else {
+            return (torrents, seeds, peers);
+        };
+        let torrents_shard = self.torrents_sharding.get_shard(shard).unwrap();
+        let expiry = self.torrents_sharding.expiry[shard].clone();
+        loop {
+            let (mut batch, mut batch_torrents, mut batch_seeds, mut batch_peers, mut batch_stale) = (0usize, 0u64, 0u64, 0u64, 0u64);
+            {
+                let mut lock = torrents_shard.write();
+                let mut queue = expiry.lock();
+                while batch < TorrentSharding::EXPIRY_BATCH_SIZE && queue.peek().is_some_and(|Reverse((updated, _, _))| *updated < deadline) {
+                    let Reverse((updated, info_hash, peer_id)) = queue.pop().unwrap();
+                    batch += 1;
+                    let Some(torrent_entry) = lock.get_mut(&info_hash) else {
+                        batch_stale += 1;
+                        continue;
+                    };
+                    if torrent_entry.seeds.get(&peer_id).is_some_and(|torrent_peer| torrent_peer.updated == updated) {
+                        torrent_entry.seeds.remove(&peer_id);
+                        batch_seeds += 1;
+                    } else if torrent_entry.peers.get(&peer_id).is_some_and(|torrent_peer| torrent_peer.updated == updated) {
+                        torrent_entry.peers.remove(&peer_id);
+                        batch_peers += 1;
+                    } else {
+                        batch_stale += 1;
+                        continue;
+                    }
+                    if !persistent && torrent_entry.seeds.is_empty() && torrent_entry.peers.is_empty() {
+                        lock.remove(&info_hash);
+                        batch_torrents += 1;
+                    }
+                }
+            }
+            self.update_stats(StatsEvent::ExpiryQueue, 0i64 - batch as i64);
+            self.update_stats(StatsEvent::Seeds, 0i64 - batch_seeds as i64);
+            self.update_stats(StatsEvent::Peers, 0i64 - batch_peers as i64);
+            self.update_stats(StatsEvent::Torrents, 0i64 - batch_torrents as i64);
+            self.update_stats(StatsEvent::PeersExpired, (batch_seeds + batch_peers) as i64);
+            self.update_stats(StatsEvent::TorrentsExpired, batch_torrents as i64);
+            self.update_stats(StatsEvent::ExpiryStale, batch_stale as i64);
+            torrents += batch_torrents;
+            seeds += batch_seeds;
+            peers += batch_peers;
+            if batch < TorrentSharding::EXPIRY_BATCH_SIZE {
+                return (torrents, seeds, peers);
             }
         }
     }
-}
\ No newline at end of file
+}
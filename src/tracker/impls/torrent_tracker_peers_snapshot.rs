@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime};
+use log::info;
+use crate::common::structs::number_of_bytes::NumberOfBytes;
+use crate::tracker::enums::announce_event::AnnounceEvent;
+use crate::tracker::enums::updates_action::UpdatesAction;
+use crate::tracker::structs::peer_id::PeerId;
+use crate::tracker::structs::peers_snapshot::PeersSnapshot;
+use crate::tracker::structs::peers_snapshot_peer::PeersSnapshotPeer;
+use crate::tracker::structs::torrent_peer::TorrentPeer;
+use crate::tracker::structs::torrent_tracker::TorrentTracker;
+
+impl TorrentTracker {
+    /// Writes all live peers to `path` with wall-clock timestamps, through a temporary file so a crash never
+    /// leaves a truncated snapshot behind. Returns the amount of peers written.
+    #[tracing::instrument(level = "debug")]
+    pub fn save_peers_snapshot(&self, path: &str) -> Result<u64, std::io::Error>
+    {
+        let now = Instant::now();
+        let created = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64;
+        let mut snapshot = PeersSnapshot { created, torrents: BTreeMap::new() };
+        let mut amount = 0u64;
+        for shard in self.torrents_sharding.shards.iter() {
+            let lock = shard.read_recursive();
+            for (info_hash, torrent_entry) in lock.iter() {
+                if torrent_entry.seeds.is_empty() && torrent_entry.peers.is_empty() {
+                    continue;
+                }
+                let peers: Vec<PeersSnapshotPeer> = torrent_entry.seeds.iter().chain(torrent_entry.peers.iter()).map(|(peer_id, torrent_peer)| {
+                    PeersSnapshotPeer {
+                        peer_id: peer_id.to_string(),
+                        peer_addr: torrent_peer.peer_addr,
+                        peer_addr_alternate: torrent_peer.peer_addr_alternate,
+                        tracker_id: torrent_peer.tracker_id,
+                        updated: created.saturating_sub(now.saturating_duration_since(torrent_peer.updated).as_millis() as u64),
+                        uploaded: torrent_peer.uploaded.0,
+                        downloaded: torrent_peer.downloaded.0,
+                        left: torrent_peer.left.0,
+                    }
+                }).collect();
+                amount += peers.len() as u64;
+                snapshot.torrents.insert(*info_hash, peers);
+            }
+        }
+
+        let temporary = format!("{}.tmp", path);
+        let mut writer = BufWriter::new(File::create(&temporary)?);
+        serde_json::to_writer(&mut writer, &snapshot)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&temporary, path)?;
+        Ok(amount)
+    }
+
+    /// Returns the `updated` instant of a snapshot peer of `age`, or `None` when it is past `peers_timeout`. An age
+    /// beyond the monotonic clock's origin (shortly after a reboot) can't be represented, such a peer starts over
+    /// with the full timeout instead of being dropped while it is still valid.
+    pub fn restored_peer_updated(now: Instant, age: Duration, peers_timeout: Duration) -> Option<Instant>
+    {
+        peers_timeout.checked_sub(age)?;
+        Some(now.checked_sub(age).unwrap_or(now))
+    }
+
+    /// Restores the peers of a snapshot that are still within `peers_timeout`, returns the torrents and peers restored.
+    #[tracing::instrument(level = "debug")]
+    pub fn load_peers_snapshot(&self, path: &str) -> Result<(u64, u64), std::io::Error>
+    {
+        let snapshot: PeersSnapshot = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        let now = Instant::now();
+        let wall_clock = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64;
+        let peers_timeout = Duration::from_secs(self.config.tracker_config.peers_timeout);
+        let (mut torrents, mut peers) = (0u64, 0u64);
+        for (info_hash, snapshot_peers) in snapshot.torrents.into_iter() {
+            let mut restored = false;
+            for snapshot_peer in snapshot_peers.into_iter() {
+                let age = Duration::from_millis(wall_clock.saturating_sub(snapshot_peer.updated));
+                let (Ok(peer_id), Some(updated)) = (PeerId::from_str(&snapshot_peer.peer_id), Self::restored_peer_updated(now, age, peers_timeout)) else {
+                    continue;
+                };
+                self.add_torrent_peer(info_hash, peer_id, TorrentPeer {
+                    peer_id,
+                    peer_addr: snapshot_peer.peer_addr,
+                    peer_addr_alternate: snapshot_peer.peer_addr_alternate,
+                    tracker_id: snapshot_peer.tracker_id,
+                    updated,
+                    uploaded: NumberOfBytes(snapshot_peer.uploaded),
+                    downloaded: NumberOfBytes(snapshot_peer.downloaded),
+                    left: NumberOfBytes(snapshot_peer.left),
+                    event: AnnounceEvent::Started,
+                }, false);
+                restored = true;
+                peers += 1;
+            }
+            if restored {
+                torrents += 1;
+                if self.config.database.persistent && let Some(torrent_entry) = self.get_torrent(info_hash) {
+                    self.add_torrent_update(info_hash, torrent_entry, UpdatesAction::Add);
+                }
+            }
+        }
+        info!("[PEERS SNAPSHOT] Restored {} peers in {} torrents from {}", peers, torrents, path);
+        Ok((torrents, peers))
+    }
+}
@@ -1,11 +1,13 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::collections::btree_map::Entry;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use log::{error, info};
 use crate::stats::enums::stats_event::StatsEvent;
 use crate::tracker::enums::updates_action::UpdatesAction;
 use crate::tracker::structs::info_hash::InfoHash;
 use crate::tracker::structs::torrent_entry::TorrentEntry;
+use crate::tracker::structs::torrent_sharding::TorrentSharding;
 use crate::tracker::structs::torrent_tracker::TorrentTracker;
 
 impl TorrentTracker {
@@ -50,8 +52,12 @@ impl TorrentTracker {
     #[tracing::instrument(level = "debug")]
     pub fn add_torrent(&self, info_hash: InfoHash, torrent_entry: TorrentEntry) -> (TorrentEntry, bool)
     {
-        let shard = self.torrents_sharding.clone().get_shard(info_hash.0[0]).unwrap();
+        let shard = self.torrents_sharding.get_torrent_shard(info_hash);
         let mut lock = shard.write();
+        for (peer_id, torrent_peer) in torrent_entry.seeds.iter().chain(torrent_entry.peers.iter()) {
+            self.torrents_sharding.schedule_expiry(info_hash, *peer_id, torrent_peer.updated);
+            self.update_stats(StatsEvent::ExpiryQueue, 1);
+        }
         match lock.entry(info_hash) {
             Entry::Vacant(v) => {
                 self.update_stats(StatsEvent::Torrents, 1);
@@ -89,13 +95,14 @@ impl TorrentTracker {
     #[tracing::instrument(level = "debug")]
     pub fn get_torrent(&self, info_hash: InfoHash) -> Option<TorrentEntry>
     {
-        let shard = self.torrents_sharding.clone().get_shard(info_hash.0[0]).unwrap();
+        let shard = self.torrents_sharding.get_torrent_shard(info_hash);
         let lock = shard.read_recursive();
         lock.get(&info_hash).map(|torrent| TorrentEntry {
             seeds: torrent.seeds.clone(),
             peers: torrent.peers.clone(),
             completed: torrent.completed,
-            updated: torrent.updated
+            updated: torrent.updated,
+            peers_cursor: torrent.peers_cursor
         })
     }
 
@@ -113,7 +120,7 @@ impl TorrentTracker {
     pub fn remove_torrent(&self, info_hash: InfoHash) -> Option<TorrentEntry>
     {
         if !self.torrents_sharding.contains_torrent(info_hash) { return None; }
-        let shard = self.torrents_sharding.clone().get_shard(info_hash.0[0]).unwrap();
+        let shard = self.torrents_sharding.get_torrent_shard(info_hash);
         let mut lock = shard.write();
         match lock.remove(&info_hash) {
             None => { None }
@@ -143,4 +150,43 @@ impl TorrentTracker {
         }
         returned_data
     }
-}
\ No newline at end of file
+
+    /// Evicts the torrents of a shard without peers that saw no announce within `torrent_ttl`, whitelisted torrents
+    /// are kept. In persistent mode the removals are queued for the database, which applies them when `remove_action` is on.
+    #[tracing::instrument(level = "debug")]
+    pub fn evict_torrents(&self, shard: usize, torrent_ttl: Duration, persistent: bool) -> u64
+    {
+        let Some(deadline) = Instant::now().checked_sub(torrent_ttl) else {
+            return 0;
+        };
+        let is_dead = |torrent_entry: &TorrentEntry| torrent_entry.seeds.is_empty() && torrent_entry.peers.is_empty() && torrent_entry.updated < deadline;
+        let torrents_shard = self.torrents_sharding.get_shard(shard).unwrap();
+        let candidates: Vec<InfoHash> = torrents_shard.read_recursive().iter().filter(|(_, torrent_entry)| is_dead(torrent_entry)).map(|(info_hash, _)| *info_hash).collect();
+        if candidates.is_empty() {
+            return 0;
+        }
+        let whitelist: HashSet<InfoHash> = self.torrents_whitelist.read_recursive().iter().copied().collect();
+        let mut evicted = 0u64;
+        for batch in candidates.chunks(TorrentSharding::EXPIRY_BATCH_SIZE) {
+            let mut removed = Vec::with_capacity(batch.len());
+            {
+                let mut lock = torrents_shard.write();
+                for info_hash in batch.iter().filter(|info_hash| !whitelist.contains(info_hash)) {
+                    if lock.get(info_hash).is_some_and(is_dead) {
+                        lock.remove(info_hash);
+                        removed.push(*info_hash);
+                    }
+                }
+            }
+            for info_hash in removed.iter() {
+                if persistent {
+                    self.add_torrent_update(*info_hash, TorrentEntry::default(), UpdatesAction::Remove);
+                }
+            }
+            self.update_stats(StatsEvent::Torrents, 0i64 - removed.len() as i64);
+            self.update_stats(StatsEvent::TorrentsEvicted, removed.len() as i64);
+            evicted += removed.len() as u64;
+        }
+        evicted
+    }
+}
@@ -73,8 +73,12 @@ impl TorrentTracker {
         for (timestamp, (info_hash, updates_action)) in self.get_blacklist_updates().iter() {
             match mapping.entry(*info_hash) {
                 Entry::Occupied(mut o) => {
-                    o.insert((o.get().0, *updates_action));
-                    self.remove_blacklist_update(timestamp);
+                    if *timestamp > o.get().0 {
+                        self.remove_blacklist_update(&o.get().0);
+                        o.insert((*timestamp, *updates_action));
+                    } else {
+                        self.remove_blacklist_update(timestamp);
+                    }
                 }
                 Entry::Vacant(v) => {
                     v.insert((*timestamp, *updates_action));
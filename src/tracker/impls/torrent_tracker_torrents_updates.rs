@@ -75,8 +75,12 @@ impl TorrentTracker {
         for (timestamp, (info_hash, torrent_entry, updates_action)) in self.get_torrent_updates().iter() {
             match mapping.entry(*info_hash) {
                 Entry::Occupied(mut o) => {
-                    o.insert((o.get().0, torrent_entry.clone(), *updates_action));
-                    self.remove_torrent_update(timestamp);
+                    if *timestamp > o.get().0 {
+                        self.remove_torrent_update(&o.get().0);
+                        o.insert((*timestamp, torrent_entry.clone(), *updates_action));
+                    } else {
+                        self.remove_torrent_update(timestamp);
+                    }
                 }
                 Entry::Vacant(v) => {
                     v.insert((*timestamp, torrent_entry.clone(), *updates_action));
@@ -64,8 +64,12 @@ impl TorrentTracker {
         for (timestamp, (user_id, user_entry_item, updates_action)) in self.get_user_updates().iter() {
             match mapping.entry(*user_id) {
                 Entry::Occupied(mut o) => {
-                    o.insert((o.get().0, user_entry_item.clone(), *updates_action));
-                    self.remove_user_update(timestamp);
+                    if *timestamp > o.get().0 {
+                        self.remove_user_update(&o.get().0);
+                        o.insert((*timestamp, user_entry_item.clone(), *updates_action));
+                    } else {
+                        self.remove_user_update(timestamp);
+                    }
                 }
                 Entry::Vacant(v) => {
                     v.insert((*timestamp, user_entry_item.clone(), *updates_action));
@@ -10,5 +10,11 @@ pub mod torrent_peer;
 pub mod user_entry_item;
 pub mod user_id;
 pub mod user_id_visitor;
-pub mod torrent_peers;
-pub mod torrent_sharding;
\ No newline at end of file
+pub mod torrent_sharding;
+pub mod announce_response;
+pub mod announce_selection;
+pub mod peers_snapshot;
+pub mod peers_snapshot_peer;
+pub mod shard_stats;
+pub mod flush_state;
+pub mod flush_journal;
\ No newline at end of file
@@ -1,4 +1,4 @@
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use serde::Deserialize;
 use crate::tracker::enums::announce_event::AnnounceEvent;
 use crate::tracker::structs::info_hash::InfoHash;
@@ -17,5 +17,10 @@ pub struct AnnounceQueryRequest {
     pub(crate) no_peer_id: bool,
     pub(crate) event: AnnounceEvent,
     pub(crate) remote_addr: IpAddr,
+    pub(crate) remote_addr_alternate: Option<SocketAddr>,
+    pub(crate) ip: Option<SocketAddr>,
+    pub(crate) ipv4: Option<SocketAddr>,
+    pub(crate) ipv6: Option<SocketAddr>,
+    pub(crate) tracker_id: Option<u64>,
     pub(crate) numwant: u64,
 }
\ No newline at end of file
@@ -0,0 +1,7 @@
+use crate::tracker::structs::peer_id::PeerId;
+use crate::tracker::structs::torrent_peer::TorrentPeer;
+
+#[derive(Debug, Clone)]
+pub struct AnnounceResponse {
+    pub(crate) peers: Vec<(PeerId, TorrentPeer)>,
+}
@@ -0,0 +1,10 @@
+use crate::tracker::structs::peer_id::PeerId;
+use crate::tracker::structs::torrent_peer::TorrentPeer;
+
+#[derive(Debug, Clone, Default)]
+pub struct AnnounceSelection {
+    pub seeds: u64,
+    pub peers: u64,
+    pub completed: u64,
+    pub peers_selection: Vec<(PeerId, TorrentPeer)>,
+}
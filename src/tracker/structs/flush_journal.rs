@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use crate::tracker::enums::updates_action::UpdatesAction;
+use crate::tracker::structs::info_hash::InfoHash;
+use crate::tracker::structs::user_entry_item::UserEntryItem;
+use crate::tracker::structs::user_id::UserId;
+
+/// Pending updates spilled to disk while the database is unreachable, keyed by their original update timestamp.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct FlushJournal {
+    pub created: u64,
+    pub torrents: Vec<(u128, InfoHash, u64, UpdatesAction)>,
+    pub whitelist: Vec<(u128, InfoHash, UpdatesAction)>,
+    pub blacklist: Vec<(u128, InfoHash, UpdatesAction)>,
+    pub keys: Vec<(u128, InfoHash, i64, UpdatesAction)>,
+    pub users: Vec<(u128, UserId, UserEntryItem, UpdatesAction)>,
+}
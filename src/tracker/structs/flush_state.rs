@@ -0,0 +1,8 @@
+use std::time::Instant;
+
+#[derive(Debug, Default)]
+pub struct FlushState {
+    pub failures: u32,
+    pub failing_since: Option<Instant>,
+    pub retry_at: Option<Instant>,
+}
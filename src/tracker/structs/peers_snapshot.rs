@@ -0,0 +1,10 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::tracker::structs::info_hash::InfoHash;
+use crate::tracker::structs::peers_snapshot_peer::PeersSnapshotPeer;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PeersSnapshot {
+    pub created: u64,
+    pub torrents: BTreeMap<InfoHash, Vec<PeersSnapshotPeer>>,
+}
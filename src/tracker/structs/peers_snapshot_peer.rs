@@ -0,0 +1,14 @@
+use std::net::SocketAddr;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PeersSnapshotPeer {
+    pub peer_id: String,
+    pub peer_addr: SocketAddr,
+    pub peer_addr_alternate: Option<SocketAddr>,
+    pub tracker_id: Option<u64>,
+    pub updated: u64,
+    pub uploaded: i64,
+    pub downloaded: i64,
+    pub left: i64,
+}
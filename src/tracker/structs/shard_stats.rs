@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShardStats {
+    pub torrents: i64,
+    pub seeds: i64,
+    pub peers: i64,
+    pub memory: i64,
+}
@@ -11,5 +11,7 @@ pub struct TorrentEntry {
     pub peers: BTreeMap<PeerId, TorrentPeer>,
     pub completed: u64,
     #[serde(with = "serde_millis")]
-    pub updated: std::time::Instant
+    pub updated: std::time::Instant,
+    #[serde(skip_serializing)]
+    pub peers_cursor: usize
 }
\ No newline at end of file
@@ -10,6 +10,8 @@ use crate::tracker::structs::peer_id::PeerId;
 pub struct TorrentPeer {
     pub peer_id: PeerId,
     pub peer_addr: SocketAddr,
+    pub peer_addr_alternate: Option<SocketAddr>,
+    pub tracker_id: Option<u64>,
     #[serde(with = "serde_millis")]
     pub updated: std::time::Instant,
     #[serde(with = "NumberOfBytesDef")]
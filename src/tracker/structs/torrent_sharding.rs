@@ -1,265 +1,15 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use crate::tracker::structs::info_hash::InfoHash;
+use crate::tracker::structs::shard_stats::ShardStats;
 use crate::tracker::structs::torrent_entry::TorrentEntry;
+use crate::tracker::types::peers_expiry::PeersExpiry;
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct TorrentSharding {
-    pub shard_000: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_001: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_002: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_003: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_004: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_005: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_006: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_007: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_008: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_009: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_010: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_011: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_012: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_013: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_014: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_015: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_016: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_017: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_018: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_019: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_020: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_021: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_022: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_023: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_024: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_025: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_026: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_027: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_028: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_029: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_030: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_031: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_032: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_033: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_034: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_035: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_036: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_037: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_038: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_039: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_040: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_041: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_042: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_043: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_044: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_045: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_046: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_047: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_048: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_049: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_050: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_051: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_052: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_053: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_054: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_055: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_056: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_057: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_058: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_059: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_060: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_061: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_062: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_063: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_064: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_065: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_066: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_067: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_068: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_069: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_070: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_071: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_072: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_073: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_074: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_075: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_076: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_077: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_078: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_079: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_080: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_081: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_082: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_083: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_084: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_085: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_086: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_087: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_088: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_089: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_090: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_091: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_092: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_093: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_094: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_095: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_096: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_097: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_098: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_099: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_100: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_101: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_102: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_103: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_104: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_105: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_106: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_107: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_108: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_109: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_110: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_111: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_112: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_113: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_114: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_115: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_116: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_117: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_118: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_119: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_120: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_121: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_122: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_123: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_124: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_125: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_126: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_127: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_128: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_129: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_130: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_131: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_132: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_133: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_134: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_135: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_136: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_137: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_138: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_139: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_140: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_141: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_142: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_143: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_144: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_145: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_146: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_147: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_148: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_149: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_150: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_151: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_152: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_153: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_154: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_155: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_156: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_157: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_158: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_159: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_160: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_161: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_162: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_163: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_164: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_165: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_166: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_167: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_168: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_169: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_170: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_171: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_172: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_173: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_174: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_175: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_176: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_177: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_178: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_179: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_180: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_181: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_182: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_183: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_184: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_185: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_186: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_187: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_188: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_189: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_190: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_191: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_192: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_193: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_194: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_195: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_196: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_197: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_198: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_199: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_200: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_201: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_202: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_203: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_204: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_205: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_206: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_207: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_208: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_209: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_210: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_211: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_212: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_213: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_214: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_215: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_216: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_217: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_218: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_219: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_220: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_221: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_222: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_223: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_224: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_225: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_226: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_227: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_228: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_229: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_230: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_231: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_232: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_233: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_234: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_235: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_236: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_237: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_238: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_239: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_240: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_241: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_242: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_243: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_244: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_245: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_246: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_247: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_248: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_249: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_250: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_251: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_252: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_253: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_254: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-    pub shard_255: Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>,
-}
\ No newline at end of file
+    pub shards: Vec<Arc<RwLock<BTreeMap<InfoHash, TorrentEntry>>>>,
+    pub shard_bits: u32,
+    pub expiry: Vec<PeersExpiry>,
+    pub shard_stats: Vec<Mutex<ShardStats>>,
+}
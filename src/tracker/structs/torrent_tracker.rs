@@ -1,10 +1,13 @@
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
-use parking_lot::RwLock;
+use actix_web::web::Bytes;
+use parking_lot::{Mutex, RwLock};
+use crate::common::structs::cidr::Cidr;
 use crate::config::structs::configuration::Configuration;
-use crate::database::structs::database_connector::DatabaseConnector;
+use crate::database::traits::DatabaseBackend;
 use crate::stats::structs::stats_atomics::StatsAtomics;
 use crate::tracker::enums::updates_action::UpdatesAction;
+use crate::tracker::structs::flush_state::FlushState;
 use crate::tracker::structs::info_hash::InfoHash;
 use crate::tracker::structs::torrent_sharding::TorrentSharding;
 use crate::tracker::structs::user_entry_item::UserEntryItem;
@@ -12,12 +15,13 @@ use crate::tracker::structs::user_id::UserId;
 use crate::tracker::types::keys_updates::KeysUpdates;
 use crate::tracker::types::torrents_updates::TorrentsUpdates;
 use crate::tracker::types::users_updates::UsersUpdates;
+use crate::udp::structs::connection_id_secrets::ConnectionIdSecrets;
 
 
 #[derive(Debug)]
 pub struct TorrentTracker {
     pub config: Arc<Configuration>,
-    pub sqlx: DatabaseConnector,
+    pub sqlx: Box<dyn DatabaseBackend>,
     pub torrents_sharding: Arc<TorrentSharding>,
     pub torrents_updates: TorrentsUpdates,
     pub torrents_whitelist: Arc<RwLock<Vec<InfoHash>>>,
@@ -28,5 +32,9 @@ pub struct TorrentTracker {
     pub keys_updates: KeysUpdates,
     pub users: Arc<RwLock<BTreeMap<UserId, UserEntryItem>>>,
     pub users_updates: UsersUpdates,
+    pub flush_state: Arc<Mutex<FlushState>>,
     pub stats: Arc<StatsAtomics>,
+    pub full_scrape: Arc<RwLock<Vec<Bytes>>>,
+    pub full_scrape_cidrs: Arc<Vec<Cidr>>,
+    pub udp_connection_id_secrets: Arc<RwLock<ConnectionIdSecrets>>,
 }
\ No newline at end of file
@@ -0,0 +1,751 @@
+#![cfg(test)]
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use async_trait::async_trait;
+use bip_bencode::{BDecodeOpt, BRefAccess, BencodeRef};
+use crate::common::structs::number_of_bytes::NumberOfBytes;
+use crate::config::structs::configuration::Configuration;
+use crate::database::traits::DatabaseBackend;
+use crate::tracker::enums::announce_event::AnnounceEvent;
+use crate::tracker::enums::peers_selection::PeersSelection;
+use crate::tracker::enums::request_interval_throttle::RequestIntervalThrottle;
+use crate::tracker::enums::torrent_peers_type::TorrentPeersType;
+use crate::tracker::enums::updates_action::UpdatesAction;
+use crate::tracker::structs::announce_query_request::AnnounceQueryRequest;
+use crate::tracker::structs::announce_response::AnnounceResponse;
+use crate::tracker::structs::info_hash::InfoHash;
+use crate::tracker::structs::peer_id::PeerId;
+use crate::tracker::structs::torrent_entry::TorrentEntry;
+use crate::tracker::structs::torrent_peer::TorrentPeer;
+use crate::tracker::structs::torrent_tracker::TorrentTracker;
+use crate::tracker::structs::user_entry_item::UserEntryItem;
+use crate::tracker::structs::user_id::UserId;
+
+async fn test_tracker(peers_selection: PeersSelection) -> Arc<TorrentTracker>
+{
+    let mut config = Configuration::init();
+    config.database.path = String::from("sqlite::memory:");
+    config.tracker_config.peers_selection = peers_selection;
+    Arc::new(TorrentTracker::new(Arc::new(config), false).await)
+}
+
+#[derive(Debug, Default)]
+struct MemoryBackend {
+    whitelist: Arc<parking_lot::Mutex<Vec<InfoHash>>>,
+    torrents: Arc<parking_lot::Mutex<BTreeMap<InfoHash, u64>>>,
+    offline: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl DatabaseBackend for MemoryBackend {
+    async fn load_torrents(&self, _tracker: Arc<TorrentTracker>) -> Result<(u64, u64), sqlx::Error> { Ok((0, 0)) }
+
+    async fn load_whitelist(&self, tracker: Arc<TorrentTracker>) -> Result<u64, sqlx::Error>
+    {
+        let whitelist = self.whitelist.lock().clone();
+        for info_hash in whitelist.iter() {
+            tracker.add_whitelist(*info_hash);
+        }
+        Ok(whitelist.len() as u64)
+    }
+
+    async fn load_blacklist(&self, _tracker: Arc<TorrentTracker>) -> Result<u64, sqlx::Error> { Ok(0) }
+
+    async fn load_keys(&self, _tracker: Arc<TorrentTracker>) -> Result<u64, sqlx::Error> { Ok(0) }
+
+    async fn load_users(&self, _tracker: Arc<TorrentTracker>) -> Result<u64, sqlx::Error> { Ok(0) }
+
+    async fn save_whitelist(&self, _tracker: Arc<TorrentTracker>, whitelists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, sqlx::Error>
+    {
+        if self.offline.load(Ordering::SeqCst) {
+            return Err(sqlx::Error::PoolTimedOut);
+        }
+        let mut whitelist = self.whitelist.lock();
+        for (info_hash, updates_action) in whitelists.iter() {
+            match updates_action {
+                UpdatesAction::Remove => { whitelist.retain(|hash| hash != info_hash); }
+                UpdatesAction::Add | UpdatesAction::Update => { whitelist.push(*info_hash); }
+            }
+        }
+        Ok(whitelists.len() as u64)
+    }
+
+    async fn save_blacklist(&self, _tracker: Arc<TorrentTracker>, blacklists: Vec<(InfoHash, UpdatesAction)>) -> Result<u64, sqlx::Error> { Ok(blacklists.len() as u64) }
+
+    async fn save_keys(&self, _tracker: Arc<TorrentTracker>, keys: BTreeMap<InfoHash, (i64, UpdatesAction)>) -> Result<u64, sqlx::Error> { Ok(keys.len() as u64) }
+
+    async fn save_torrents(&self, _tracker: Arc<TorrentTracker>, torrents: BTreeMap<InfoHash, (TorrentEntry, UpdatesAction)>) -> Result<(), sqlx::Error>
+    {
+        if self.offline.load(Ordering::SeqCst) {
+            return Err(sqlx::Error::PoolTimedOut);
+        }
+        let mut stored = self.torrents.lock();
+        for (info_hash, (torrent_entry, updates_action)) in torrents.into_iter() {
+            match updates_action {
+                UpdatesAction::Remove => { stored.remove(&info_hash); }
+                UpdatesAction::Add | UpdatesAction::Update => { stored.insert(info_hash, torrent_entry.completed); }
+            }
+        }
+        Ok(())
+    }
+
+    async fn save_users(&self, _tracker: Arc<TorrentTracker>, _users: BTreeMap<UserId, (UserEntryItem, UpdatesAction)>) -> Result<(), sqlx::Error> { Ok(()) }
+
+    async fn reset_seeds_peers(&self, _tracker: Arc<TorrentTracker>) -> Result<(), sqlx::Error> { Ok(()) }
+}
+
+fn test_peer(index: u16, seed: bool) -> (PeerId, TorrentPeer)
+{
+    let mut peer_id = [0u8; 20];
+    peer_id[..2].copy_from_slice(&index.to_be_bytes());
+    let torrent_peer = TorrentPeer {
+        peer_id: PeerId(peer_id),
+        peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, (index >> 8) as u8, index as u8)), 6881),
+        peer_addr_alternate: None,
+        tracker_id: None,
+        updated: std::time::Instant::now(),
+        uploaded: NumberOfBytes(0),
+        downloaded: NumberOfBytes(0),
+        left: NumberOfBytes(if seed { 0 } else { 1 }),
+        event: AnnounceEvent::Started,
+    };
+    (PeerId(peer_id), torrent_peer)
+}
+
+fn fill_swarm(tracker: &TorrentTracker, info_hash: InfoHash, seeds: u16, peers: u16)
+{
+    for index in 0..seeds {
+        let (peer_id, torrent_peer) = test_peer(index, true);
+        tracker.add_torrent_peer(info_hash, peer_id, torrent_peer, false);
+    }
+    for index in seeds..seeds + peers {
+        let (peer_id, torrent_peer) = test_peer(index, false);
+        tracker.add_torrent_peer(info_hash, peer_id, torrent_peer, false);
+    }
+}
+
+/// Announces the requester and returns the peers selected for it.
+fn announce_selection(tracker: &TorrentTracker, info_hash: InfoHash, requester: (PeerId, TorrentPeer), type_ip: TorrentPeersType, amount: usize) -> Vec<(PeerId, TorrentPeer)>
+{
+    let (peer_id, mut torrent_peer) = requester;
+    tracker.announce_torrent_peer(info_hash, peer_id, &mut torrent_peer, type_ip, amount, false).1.peers_selection
+}
+
+fn count_selections(tracker: &TorrentTracker, info_hash: InfoHash, announces: usize, amount: usize) -> HashMap<PeerId, usize>
+{
+    let mut counts = HashMap::new();
+    for _ in 0..announces {
+        let selection = announce_selection(tracker, info_hash, test_peer(1000, false), TorrentPeersType::IPv4, amount);
+        assert_eq!(selection.len(), amount);
+        for (peer_id, _) in selection {
+            *counts.entry(peer_id).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+#[tokio::test]
+async fn peers_selection_random_is_uniform()
+{
+    let tracker = test_tracker(PeersSelection::random).await;
+    let info_hash = InfoHash([1u8; 20]);
+    fill_swarm(&tracker, info_hash, 20, 80);
+
+    let counts = count_selections(&tracker, info_hash, 10_000, 10);
+    assert_eq!(counts.len(), 100);
+    for count in counts.values() {
+        assert!((750..=1250).contains(count), "peer selected {} times, expected about 1000", count);
+    }
+}
+
+#[tokio::test]
+async fn peers_selection_round_robin_rotates_evenly()
+{
+    let tracker = test_tracker(PeersSelection::round_robin).await;
+    let info_hash = InfoHash([2u8; 20]);
+    fill_swarm(&tracker, info_hash, 20, 80);
+
+    let counts = count_selections(&tracker, info_hash, 100, 10);
+    assert_eq!(counts.len(), 100);
+    assert!(counts.values().all(|count| *count == 10));
+}
+
+#[tokio::test]
+async fn peers_selection_prefer_seeds_fills_with_seeds_first()
+{
+    let tracker = test_tracker(PeersSelection::prefer_seeds).await;
+    let info_hash = InfoHash([3u8; 20]);
+    fill_swarm(&tracker, info_hash, 5, 95);
+
+    let counts = count_selections(&tracker, info_hash, 9_500, 10);
+    for index in 0..5u16 {
+        assert_eq!(counts[&test_peer(index, true).0], 9_500);
+    }
+    for index in 5..100u16 {
+        let count = counts[&test_peer(index, false).0];
+        assert!((350..=650).contains(&count), "peer selected {} times, expected about 500", count);
+    }
+}
+
+#[tokio::test]
+async fn peers_selection_skips_seeds_for_seeders_and_self()
+{
+    let tracker = test_tracker(PeersSelection::random).await;
+    let info_hash = InfoHash([4u8; 20]);
+    fill_swarm(&tracker, info_hash, 10, 10);
+
+    let self_ip = test_peer(15, false).1.peer_addr.ip();
+    let selection = announce_selection(&tracker, info_hash, test_peer(15, true), TorrentPeersType::IPv4, 72);
+    assert_eq!(selection.len(), 9);
+    assert!(selection.iter().all(|(_, torrent_peer)| torrent_peer.left != NumberOfBytes(0) && torrent_peer.peer_addr.ip() != self_ip));
+    assert!(announce_selection(&tracker, info_hash, test_peer(16, false), TorrentPeersType::IPv6, 72).is_empty());
+}
+
+#[tokio::test]
+async fn peers_selection_matches_dual_stack_peers_in_both_families()
+{
+    let tracker = test_tracker(PeersSelection::random).await;
+    let info_hash = InfoHash([5u8; 20]);
+    let (peer_id, mut torrent_peer) = test_peer(1, true);
+    torrent_peer.peer_addr_alternate = Some(SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 6881));
+    tracker.add_torrent_peer(info_hash, peer_id, torrent_peer.clone(), false);
+
+    for peers_type in [TorrentPeersType::IPv4, TorrentPeersType::IPv6, TorrentPeersType::All] {
+        assert_eq!(announce_selection(&tracker, info_hash, test_peer(2, false), peers_type, 72).len(), 1);
+    }
+    tracker.remove_torrent_peer(info_hash, test_peer(2, false).0, false, false);
+    let (requester_id, mut requester) = test_peer(3, false);
+    requester.peer_addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 6882);
+    assert!(announce_selection(&tracker, info_hash, (requester_id, requester), TorrentPeersType::All, 72).is_empty());
+    assert_eq!(torrent_peer.peer_addr_ipv6(), torrent_peer.peer_addr_alternate);
+}
+
+#[tokio::test]
+async fn announce_throttle_only_applies_to_regular_announces_within_minimum_interval()
+{
+    let mut config = Configuration::init();
+    config.database.path = String::from("sqlite::memory:");
+    config.tracker_config.request_interval_throttle = RequestIntervalThrottle::cached;
+    let tracker = Arc::new(TorrentTracker::new(Arc::new(config), false).await);
+    let info_hash = InfoHash([6u8; 20]);
+    let (peer_id, torrent_peer) = test_peer(1, false);
+    let mut announce_query = AnnounceQueryRequest {
+        info_hash,
+        peer_id,
+        port: 6881,
+        uploaded: 0,
+        downloaded: 0,
+        left: 1,
+        compact: true,
+        no_peer_id: false,
+        event: AnnounceEvent::Started,
+        remote_addr: torrent_peer.peer_addr.ip(),
+        remote_addr_alternate: None,
+        ip: None,
+        ipv4: None,
+        ipv6: None,
+        tracker_id: None,
+        numwant: 72,
+    };
+
+    assert!(tracker.check_announce_throttle(&announce_query, TorrentPeersType::IPv4).is_none());
+    tracker.add_torrent_peer(info_hash, peer_id, torrent_peer, false);
+    assert!(tracker.check_announce_throttle(&announce_query, TorrentPeersType::IPv4).is_some());
+    announce_query.event = AnnounceEvent::Stopped;
+    assert!(tracker.check_announce_throttle(&announce_query, TorrentPeersType::IPv4).is_none());
+    assert_eq!(tracker.get_stats().announces_throttled, 1);
+}
+
+#[tokio::test]
+async fn announce_throttle_leaves_the_swarm_untouched()
+{
+    let mut config = Configuration::init();
+    config.database.path = String::from("sqlite::memory:");
+    config.tracker_config.peers_selection = PeersSelection::round_robin;
+    config.tracker_config.request_interval_throttle = RequestIntervalThrottle::cached;
+    let tracker = Arc::new(TorrentTracker::new(Arc::new(config), false).await);
+    let info_hash = InfoHash([6u8; 20]);
+    fill_swarm(&tracker, info_hash, 10, 40);
+    let (peer_id, torrent_peer) = test_peer(1000, false);
+    tracker.add_torrent_peer(info_hash, peer_id, torrent_peer.clone(), false);
+    announce_selection(&tracker, info_hash, (peer_id, torrent_peer.clone()), TorrentPeersType::IPv4, 5);
+    let before = tracker.get_torrent(info_hash).unwrap();
+    let announce_query = AnnounceQueryRequest {
+        info_hash,
+        peer_id,
+        port: 6881,
+        uploaded: 0,
+        downloaded: 0,
+        left: 1,
+        compact: true,
+        no_peer_id: false,
+        event: AnnounceEvent::Started,
+        remote_addr: torrent_peer.peer_addr.ip(),
+        remote_addr_alternate: None,
+        ip: None,
+        ipv4: None,
+        ipv6: None,
+        tracker_id: None,
+        numwant: 10,
+    };
+
+    for _ in 0..3 {
+        let (throttled_peer, announce_selection) = tracker.check_announce_throttle(&announce_query, TorrentPeersType::IPv4).unwrap();
+        assert_eq!(throttled_peer.updated, torrent_peer.updated);
+        assert_eq!((announce_selection.seeds, announce_selection.peers), (10, 41));
+        assert_eq!(announce_selection.peers_selection.len(), 10);
+    }
+    let after = tracker.get_torrent(info_hash).unwrap();
+    assert_eq!(after.peers_cursor, before.peers_cursor);
+    assert_eq!(after.updated, before.updated);
+    assert_eq!((after.seeds.len(), after.peers.len(), after.completed), (before.seeds.len(), before.peers.len(), before.completed));
+    assert_eq!(after.peers.get(&peer_id).unwrap().updated, torrent_peer.updated);
+    assert_eq!(tracker.get_stats().announces_throttled, 3);
+}
+
+#[tokio::test]
+async fn announce_torrent_peer_returns_counts_and_selection_only()
+{
+    let tracker = test_tracker(PeersSelection::random).await;
+    let info_hash = InfoHash([7u8; 20]);
+    fill_swarm(&tracker, info_hash, 30, 70);
+
+    let (peer_id, mut torrent_peer) = test_peer(100, false);
+    torrent_peer.event = AnnounceEvent::Completed;
+    torrent_peer.left = NumberOfBytes(0);
+    let (found, announce_selection, torrent_entry) = tracker.announce_torrent_peer(info_hash, peer_id, &mut torrent_peer, TorrentPeersType::IPv4, 10, false);
+    assert!(found);
+    assert!(torrent_entry.is_none());
+    assert_eq!((announce_selection.seeds, announce_selection.peers, announce_selection.completed), (31, 70, 1));
+    assert_eq!(announce_selection.peers_selection.len(), 10);
+    assert!(announce_selection.peers_selection.iter().all(|(selected, _)| *selected != peer_id));
+
+    torrent_peer.event = AnnounceEvent::Stopped;
+    let (found, announce_selection, _) = tracker.announce_torrent_peer(info_hash, peer_id, &mut torrent_peer, TorrentPeersType::IPv4, 10, false);
+    assert!(found);
+    assert_eq!((announce_selection.seeds, announce_selection.peers), (30, 70));
+    let (found, _, _) = tracker.announce_torrent_peer(info_hash, peer_id, &mut torrent_peer, TorrentPeersType::IPv4, 10, false);
+    assert!(!found);
+
+    let lone_hash = InfoHash([8u8; 20]);
+    let (peer_id, mut torrent_peer) = test_peer(1, false);
+    tracker.add_torrent_peer(lone_hash, peer_id, torrent_peer.clone(), false);
+    torrent_peer.event = AnnounceEvent::Stopped;
+    let (found, announce_selection, _) = tracker.announce_torrent_peer(lone_hash, peer_id, &mut torrent_peer, TorrentPeersType::IPv4, 10, false);
+    assert!(!found);
+    assert_eq!((announce_selection.seeds, announce_selection.peers), (0, 0));
+    assert!(tracker.get_torrent(lone_hash).is_none());
+}
+
+#[tokio::test]
+async fn announce_torrent_peer_resolves_tracker_ids_against_the_previous_peer()
+{
+    let tracker = test_tracker(PeersSelection::random).await;
+    let info_hash = InfoHash([7u8; 20]);
+    let (peer_id, mut torrent_peer) = test_peer(1, false);
+
+    tracker.announce_torrent_peer(info_hash, peer_id, &mut torrent_peer, TorrentPeersType::IPv4, 10, true);
+    let issued = torrent_peer.tracker_id.unwrap();
+    assert_eq!(tracker.get_torrent_peer(info_hash, peer_id).unwrap().tracker_id, Some(issued));
+
+    torrent_peer.tracker_id = None;
+    tracker.announce_torrent_peer(info_hash, peer_id, &mut torrent_peer, TorrentPeersType::IPv4, 10, true);
+    assert_eq!(torrent_peer.tracker_id, Some(issued));
+    tracker.announce_torrent_peer(info_hash, peer_id, &mut torrent_peer, TorrentPeersType::IPv4, 10, true);
+    assert_eq!(torrent_peer.tracker_id, Some(issued));
+
+    torrent_peer.tracker_id = Some(issued.wrapping_add(1));
+    tracker.announce_torrent_peer(info_hash, peer_id, &mut torrent_peer, TorrentPeersType::IPv4, 10, true);
+    assert_ne!(torrent_peer.tracker_id, Some(issued.wrapping_add(1)));
+    assert_eq!(tracker.get_torrent_peer(info_hash, peer_id).unwrap().tracker_id, torrent_peer.tracker_id);
+
+    let stats = tracker.get_stats();
+    assert_eq!((stats.tcp_tracker_id_issued, stats.tcp_tracker_id_recognized), (2, 1));
+}
+
+#[tokio::test]
+async fn expire_peers_only_removes_due_peers_and_skips_stale_entries()
+{
+    let tracker = test_tracker(PeersSelection::random).await;
+    let info_hash = InfoHash([9u8; 20]);
+    let shard = tracker.torrents_sharding.shard_index(info_hash);
+    let expired = std::time::Instant::now() - std::time::Duration::from_secs(120);
+    for index in 0..6u16 {
+        let (peer_id, mut torrent_peer) = test_peer(index, index < 2);
+        if index < 4 {
+            torrent_peer.updated = expired;
+        }
+        tracker.add_torrent_peer(info_hash, peer_id, torrent_peer, false);
+    }
+    let (peer_id, torrent_peer) = test_peer(3, false);
+    tracker.add_torrent_peer(info_hash, peer_id, torrent_peer, false);
+    assert_eq!(tracker.get_stats().expiry_queue, 7);
+
+    assert_eq!(tracker.expire_peers(shard, std::time::Duration::from_secs(60), false), (0, 2, 1));
+    let stats = tracker.get_stats();
+    assert_eq!((stats.seeds, stats.peers, stats.torrents), (0, 3, 1));
+    assert_eq!((stats.peers_expired, stats.torrents_expired, stats.expiry_stale, stats.expiry_queue), (3, 0, 1, 3));
+    assert!(tracker.get_torrent_peer(info_hash, test_peer(3, false).0).is_some());
+
+    let lone_hash = InfoHash([10u8; 20]);
+    let (peer_id, mut torrent_peer) = test_peer(1, false);
+    torrent_peer.updated = expired;
+    tracker.add_torrent_peer(lone_hash, peer_id, torrent_peer, false);
+    assert_eq!(tracker.expire_peers(tracker.torrents_sharding.shard_index(lone_hash), std::time::Duration::from_secs(60), false), (1, 0, 1));
+    assert!(tracker.get_torrent(lone_hash).is_none());
+    assert_eq!(tracker.get_stats().torrents_expired, 1);
+}
+
+#[tokio::test]
+async fn peers_snapshot_restores_peers_within_the_timeout()
+{
+    let path = std::env::temp_dir().join(format!("torrust-actix-peers-snapshot-{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    let tracker = test_tracker(PeersSelection::random).await;
+    let info_hash = InfoHash([11u8; 20]);
+    let expired_hash = InfoHash([12u8; 20]);
+    fill_swarm(&tracker, info_hash, 2, 3);
+    let (peer_id, mut torrent_peer) = test_peer(7, false);
+    torrent_peer.updated = std::time::Instant::now() - std::time::Duration::from_secs(120);
+    torrent_peer.tracker_id = Some(42);
+    tracker.add_torrent_peer(expired_hash, peer_id, torrent_peer, false);
+    assert_eq!(tracker.save_peers_snapshot(path).unwrap(), 6);
+
+    let mut config = Configuration::init();
+    config.database.path = String::from("sqlite::memory:");
+    config.tracker_config.peers_timeout = 60;
+    let restored = Arc::new(TorrentTracker::new(Arc::new(config), false).await);
+    assert_eq!(restored.load_peers_snapshot(path).unwrap(), (1, 5));
+    std::fs::remove_file(path).unwrap();
+
+    let torrent_entry = restored.get_torrent(info_hash).unwrap();
+    assert_eq!((torrent_entry.seeds.len(), torrent_entry.peers.len()), (2, 3));
+    assert_eq!(torrent_entry.seeds[&test_peer(0, true).0].peer_addr, test_peer(0, true).1.peer_addr);
+    assert!(torrent_entry.peers.values().all(|torrent_peer| torrent_peer.updated.elapsed() < std::time::Duration::from_secs(5)));
+    assert!(restored.get_torrent(expired_hash).is_none());
+    let stats = restored.get_stats();
+    assert_eq!((stats.torrents, stats.seeds, stats.peers, stats.expiry_queue), (1, 2, 3, 5));
+    assert_eq!(restored.load_peers_snapshot(path).unwrap_err().kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn peers_snapshot_keeps_peers_older_than_the_monotonic_clock()
+{
+    let now = std::time::Instant::now();
+    let age = std::time::Duration::from_secs(u64::MAX);
+    assert!(now.checked_sub(age).is_none());
+    assert_eq!(TorrentTracker::restored_peer_updated(now, age, std::time::Duration::MAX), Some(now));
+    assert_eq!(TorrentTracker::restored_peer_updated(now, std::time::Duration::from_secs(30), std::time::Duration::from_secs(60)), Some(now - std::time::Duration::from_secs(30)));
+    assert_eq!(TorrentTracker::restored_peer_updated(now, std::time::Duration::from_secs(61), std::time::Duration::from_secs(60)), None);
+}
+
+#[tokio::test]
+async fn evict_torrents_removes_dead_torrents_except_whitelisted()
+{
+    let mut config = Configuration::init();
+    config.database.path = String::from("sqlite::memory:");
+    config.database.persistent = true;
+    let tracker = Arc::new(TorrentTracker::new(Arc::new(config), false).await);
+    let dead = std::time::Instant::now() - std::time::Duration::from_secs(7200);
+    let (dead_hash, whitelisted_hash, fresh_hash, active_hash) = (InfoHash([13u8; 20]), InfoHash([14u8; 20]), InfoHash([15u8; 20]), InfoHash([16u8; 20]));
+    for info_hash in [dead_hash, whitelisted_hash, active_hash] {
+        let mut torrent_entry = TorrentEntry::new();
+        torrent_entry.updated = dead;
+        tracker.add_torrent(info_hash, torrent_entry);
+    }
+    tracker.add_torrent(fresh_hash, TorrentEntry::new());
+    let (peer_id, mut torrent_peer) = test_peer(1, true);
+    torrent_peer.updated = dead;
+    tracker.add_torrent_peer(active_hash, peer_id, torrent_peer, false);
+    tracker.add_whitelist(whitelisted_hash);
+
+    let evicted: u64 = (0..tracker.torrents_sharding.shards_amount()).map(|shard| tracker.evict_torrents(shard, std::time::Duration::from_secs(3600), true)).sum();
+    assert_eq!(evicted, 1);
+    assert!(tracker.get_torrent(dead_hash).is_none());
+    assert!(tracker.get_torrent(whitelisted_hash).is_some());
+    assert!(tracker.get_torrent(fresh_hash).is_some());
+    assert!(tracker.get_torrent(active_hash).is_some());
+    let stats = tracker.get_stats();
+    assert_eq!((stats.torrents, stats.torrents_evicted), (3, 1));
+    let updates: Vec<(InfoHash, UpdatesAction)> = tracker.get_torrent_updates().into_values().map(|(info_hash, _, updates_action)| (info_hash, updates_action)).collect();
+    assert_eq!(updates, vec![(dead_hash, UpdatesAction::Remove)]);
+}
+
+#[tokio::test]
+async fn shard_stats_count_the_swarm_and_estimate_memory()
+{
+    let tracker = test_tracker(PeersSelection::random).await;
+    let info_hash = InfoHash([17u8; 20]);
+    fill_swarm(&tracker, info_hash, 3, 5);
+    let shard = tracker.torrents_sharding.shard_index(info_hash);
+
+    let shard_stats = tracker.update_shard_stats(shard);
+    assert_eq!((shard_stats.torrents, shard_stats.seeds, shard_stats.peers), (1, 3, 5));
+    assert!(shard_stats.memory > 0);
+    assert_eq!(tracker.get_shard_stats()[shard], shard_stats);
+    assert_eq!(tracker.get_shard_stats().iter().map(|shard_stats| shard_stats.torrents).sum::<i64>(), 1);
+
+    tracker.add_whitelist(info_hash);
+    tracker.update_memory_stats();
+    let stats = tracker.get_stats();
+    assert_eq!(stats.memory_torrents, shard_stats.memory);
+    assert!(stats.memory_whitelist >= size_of::<InfoHash>() as i64);
+    assert_eq!(stats.memory_blacklist, 0);
+}
+
+#[tokio::test]
+async fn tracker_loads_and_saves_through_a_custom_database_backend()
+{
+    let backend = MemoryBackend::default();
+    let whitelist = backend.whitelist.clone();
+    let (loaded_hash, added_hash) = (InfoHash([18u8; 20]), InfoHash([19u8; 20]));
+    whitelist.lock().push(loaded_hash);
+    let tracker = Arc::new(TorrentTracker::new_with_backend(Arc::new(Configuration::init()), Box::new(backend)));
+
+    tracker.load_whitelist(tracker.clone()).await;
+    assert!(tracker.check_whitelist(loaded_hash));
+    assert!(tracker.save_whitelist(tracker.clone(), vec![(added_hash, UpdatesAction::Add), (loaded_hash, UpdatesAction::Remove)]).await.is_ok());
+    assert_eq!(*whitelist.lock(), vec![added_hash]);
+}
+
+#[tokio::test]
+async fn pending_updates_flush_the_newest_action_per_key()
+{
+    let backend = MemoryBackend::default();
+    let whitelist = backend.whitelist.clone();
+    let tracker = Arc::new(TorrentTracker::new_with_backend(Arc::new(Configuration::init()), Box::new(backend)));
+    let (removed_hash, added_hash) = (InfoHash([23u8; 20]), InfoHash([24u8; 20]));
+    tracker.add_whitelist_update(removed_hash, UpdatesAction::Add);
+    tracker.add_whitelist_update(added_hash, UpdatesAction::Remove);
+    tracker.add_whitelist_update(removed_hash, UpdatesAction::Remove);
+    tracker.add_whitelist_update(added_hash, UpdatesAction::Add);
+
+    assert!(tracker.save_whitelist_updates(tracker.clone()).await.is_ok());
+    assert_eq!(*whitelist.lock(), vec![added_hash]);
+    assert!(tracker.get_whitelist_updates().is_empty());
+}
+
+#[tokio::test]
+async fn failed_flushes_back_off_spill_into_the_journal_and_replay()
+{
+    let journal = std::env::temp_dir().join(format!("torrust-actix-flush-journal-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&journal);
+    let mut config = Configuration::init();
+    config.tracker_config.whitelist_enabled = true;
+    config.database.flush_journal_file = journal.to_string_lossy().to_string();
+    let backend = MemoryBackend::default();
+    let (whitelist, torrents, offline) = (backend.whitelist.clone(), backend.torrents.clone(), backend.offline.clone());
+    offline.store(true, Ordering::SeqCst);
+    let tracker = Arc::new(TorrentTracker::new_with_backend(Arc::new(config), Box::new(backend)));
+    let (info_hash, listed_hash) = (InfoHash([25u8; 20]), InfoHash([26u8; 20]));
+    let mut torrent_entry = TorrentEntry::new();
+    torrent_entry.completed = 5;
+    tracker.add_torrent_update(info_hash, torrent_entry, UpdatesAction::Add);
+    tracker.add_whitelist_update(listed_hash, UpdatesAction::Add);
+
+    assert!(tracker.flush_updates(tracker.clone(), false).await.is_err());
+    assert_eq!((tracker.get_stats().flush_queue, tracker.get_stats().flush_failures), (2, 1));
+
+    // Still backing off, so the recovered database is not tried before the next retry.
+    offline.store(false, Ordering::SeqCst);
+    assert!(tracker.flush_updates(tracker.clone(), false).await.is_err());
+    assert!(torrents.lock().is_empty());
+
+    offline.store(true, Ordering::SeqCst);
+    assert!(tracker.flush_updates(tracker.clone(), true).await.is_err());
+    assert!(journal.exists());
+    assert_eq!(tracker.count_flush_queue(), 0);
+    assert_eq!((tracker.get_stats().flush_journal, tracker.get_stats().flush_failures), (2, 2));
+
+    offline.store(false, Ordering::SeqCst);
+    tracker.flush_state.lock().retry_at = None;
+    assert!(tracker.flush_updates(tracker.clone(), false).await.is_ok());
+    assert!(!journal.exists());
+    assert_eq!(torrents.lock().get(&info_hash), Some(&5));
+    assert_eq!(*whitelist.lock(), vec![listed_hash]);
+    let stats = tracker.get_stats();
+    assert_eq!((stats.flush_queue, stats.flush_journal, stats.flush_replayed), (0, 0, 2));
+}
+
+#[tokio::test]
+async fn full_scrape_snapshot_is_sorted_valid_bencode()
+{
+    let tracker = test_tracker(PeersSelection::random).await;
+    fill_swarm(&tracker, InfoHash([0xf0u8; 20]), 2, 3);
+    fill_swarm(&tracker, InfoHash([0x0fu8; 20]), 1, 0);
+    tracker.update_full_scrape();
+
+    let mut encoded = b"d5:filesd".to_vec();
+    for chunk in tracker.get_full_scrape() {
+        encoded.extend_from_slice(&chunk);
+    }
+    encoded.extend_from_slice(b"ee");
+    let decoded = BencodeRef::decode(&encoded, BDecodeOpt::default()).unwrap();
+    let files = decoded.dict().unwrap().lookup(b"files").unwrap().dict().unwrap();
+    let entry = files.lookup(&[0xf0u8; 20]).unwrap().dict().unwrap();
+    assert_eq!(entry.lookup(b"complete").unwrap().int(), Some(2));
+    assert_eq!(entry.lookup(b"incomplete").unwrap().int(), Some(3));
+    assert_eq!(files.to_list().len(), 2);
+}
+
+#[tokio::test]
+async fn sharding_follows_the_configured_shard_count()
+{
+    let mut config = Configuration::init();
+    config.database.path = String::from("sqlite::memory:");
+    config.tracker_config.shards = 4096;
+    let tracker = Arc::new(TorrentTracker::new(Arc::new(config), false).await);
+    assert_eq!(tracker.torrents_sharding.shards_amount(), 4096);
+
+    let mut info_hashes = Vec::new();
+    for (first, second) in [(0x00u8, 0x00u8), (0x00, 0x10), (0x7f, 0xff), (0xff, 0xf0), (0xff, 0xff)] {
+        let mut info_hash = [0u8; 20];
+        info_hash[0] = first;
+        info_hash[1] = second;
+        info_hashes.push(InfoHash(info_hash));
+        fill_swarm(&tracker, InfoHash(info_hash), 1, 0);
+    }
+    let shards: Vec<usize> = info_hashes.iter().map(|info_hash| tracker.torrents_sharding.shard_index(*info_hash)).collect();
+    assert_eq!(shards, vec![0, 1, 2047, 4095, 4095]);
+    assert_eq!(tracker.torrents_sharding.get_shard_content(4095).len(), 2);
+    assert_eq!(tracker.torrents_sharding.get_torrents_amount(), 5);
+    assert_eq!(tracker.torrents_sharding.get_all_content().keys().copied().collect::<Vec<InfoHash>>(), info_hashes);
+    assert!(tracker.torrents_sharding.contains_torrent(info_hashes[2]));
+    assert!(tracker.torrents_sharding.contains_peer(info_hashes[2], test_peer(0, true).0));
+
+    tracker.update_full_scrape();
+    assert_eq!(tracker.get_full_scrape().len(), 4096);
+}
+
+#[test]
+#[should_panic(expected = "shards")]
+fn sharding_rejects_shard_counts_outside_the_allowed_powers_of_two()
+{
+    let mut config = Configuration::init();
+    config.tracker_config.shards = 300;
+    Configuration::validate(config);
+}
+
+#[test]
+#[should_panic(expected = "full_scrape_cidrs")]
+fn full_scrape_cidrs_reject_invalid_entries()
+{
+    let mut config = Configuration::init();
+    config.tracker_config.full_scrape_cidrs = vec![String::from("127.0.0.1/32"), String::from("10.0.0.0/33")];
+    Configuration::validate(config);
+}
+
+fn announce_response_fixture() -> AnnounceResponse
+{
+    let (peer_id_1, mut torrent_peer_1) = test_peer(1, false);
+    torrent_peer_1.peer_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)), 51413);
+    let (peer_id_2, mut torrent_peer_2) = test_peer(2, false);
+    torrent_peer_2.peer_addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 6882);
+    torrent_peer_2.peer_addr_alternate = Some(SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 2)), 6883));
+    AnnounceResponse::new(vec![(peer_id_1, torrent_peer_1), (peer_id_2, torrent_peer_2)])
+}
+
+#[test]
+fn announce_response_compact_uses_each_peers_port()
+{
+    let announce_response = announce_response_fixture();
+    assert_eq!(announce_response.compact_ipv4(), vec![192, 0, 2, 1, 0xc8, 0xd5, 192, 0, 2, 2, 0x1a, 0xe3]);
+
+    let mut compact_ipv6 = Ipv6Addr::LOCALHOST.octets().to_vec();
+    compact_ipv6.extend_from_slice(&6882u16.to_be_bytes());
+    assert_eq!(announce_response.compact_ipv6(), compact_ipv6);
+}
+
+#[test]
+fn announce_response_dict_uses_each_peers_port()
+{
+    let announce_response = announce_response_fixture();
+    let encoded = announce_response.dict_ipv4(false).encode();
+    let decoded = BencodeRef::decode(&encoded, BDecodeOpt::default()).unwrap();
+    let peers = decoded.list().unwrap();
+    assert_eq!(peers.len(), 2);
+    let peer = peers.get(1).unwrap().dict().unwrap();
+    assert_eq!(peer.lookup(b"ip").unwrap().str(), Some("192.0.2.2"));
+    assert_eq!(peer.lookup(b"port").unwrap().int(), Some(6883));
+    assert!(peer.lookup(b"peer id").is_some());
+
+    let encoded = announce_response.dict_ipv6(true).encode();
+    let decoded = BencodeRef::decode(&encoded, BDecodeOpt::default()).unwrap();
+    let peers = decoded.list().unwrap();
+    assert_eq!(peers.len(), 1);
+    let peer = peers.get(0).unwrap().dict().unwrap();
+    assert_eq!(peer.lookup(b"ip").unwrap().str(), Some("::1"));
+    assert_eq!(peer.lookup(b"port").unwrap().int(), Some(6882));
+    assert!(peer.lookup(b"peer id").is_none());
+}
+
+#[test]
+fn announce_response_udp_uses_each_peers_port()
+{
+    let announce_response = announce_response_fixture();
+    let peers = announce_response.response_peers_ipv4();
+    assert_eq!(peers.iter().map(|peer| (peer.ip_address, peer.port.0)).collect::<Vec<_>>(), vec![
+        (Ipv4Addr::new(192, 0, 2, 1), 51413),
+        (Ipv4Addr::new(192, 0, 2, 2), 6883)
+    ]);
+    let peers6 = announce_response.response_peers_ipv6();
+    assert_eq!(peers6.iter().map(|peer| (peer.ip_address, peer.port.0)).collect::<Vec<_>>(), vec![(Ipv6Addr::LOCALHOST, 6882)]);
+}
+
+fn announce_query(extra: &[(&str, &str)]) -> HashMap<String, Vec<Vec<u8>>>
+{
+    let mut query = HashMap::new();
+    query.insert(String::from("info_hash"), vec![vec![1u8; 20]]);
+    query.insert(String::from("peer_id"), vec![vec![2u8; 20]]);
+    for (key, value) in [("port", "6881"), ("uploaded", "0"), ("downloaded", "0"), ("left", "0")].iter().chain(extra.iter()) {
+        query.insert(key.to_string(), vec![value.as_bytes().to_vec()]);
+    }
+    query
+}
+
+#[tokio::test]
+async fn announce_numwant_is_capped_at_peers_numwant_max()
+{
+    let tracker = test_tracker(PeersSelection::random).await;
+    let numwant_max = tracker.config.tracker_config.peers_numwant_max;
+    let remote_addr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+
+    let announce = tracker.validate_announce(remote_addr, announce_query(&[])).await.unwrap();
+    assert_eq!(announce.numwant, numwant_max);
+    let announce = tracker.validate_announce(remote_addr, announce_query(&[("numwant", "0")])).await.unwrap();
+    assert_eq!(announce.numwant, 0);
+    let announce = tracker.validate_announce(remote_addr, announce_query(&[("numwant", "10")])).await.unwrap();
+    assert_eq!(announce.numwant, 10);
+    let announce = tracker.validate_announce(remote_addr, announce_query(&[("numwant", &numwant_max.to_string())])).await.unwrap();
+    assert_eq!(announce.numwant, numwant_max);
+    let announce = tracker.validate_announce(remote_addr, announce_query(&[("numwant", &(numwant_max + 1).to_string())])).await.unwrap();
+    assert_eq!(announce.numwant, numwant_max);
+    assert!(tracker.validate_announce(remote_addr, announce_query(&[("numwant", "-1")])).await.is_err());
+}
+
+#[tokio::test]
+async fn announce_no_peer_id_omits_peer_ids_from_the_dict_response()
+{
+    let tracker = test_tracker(PeersSelection::random).await;
+    let remote_addr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+    let announce_response = announce_response_fixture();
+
+    let announce = tracker.validate_announce(remote_addr, announce_query(&[])).await.unwrap();
+    assert!(!announce.no_peer_id);
+    let announce = tracker.validate_announce(remote_addr, announce_query(&[("no_peer_id", "1")])).await.unwrap();
+    assert!(announce.no_peer_id);
+
+    let encoded = announce_response.dict_ipv4(announce.no_peer_id).encode();
+    let decoded = BencodeRef::decode(&encoded, BDecodeOpt::default()).unwrap();
+    let peers = decoded.list().unwrap();
+    assert_eq!(peers.len(), 2);
+    for index in 0..peers.len() {
+        let peer = peers.get(index).unwrap().dict().unwrap();
+        assert!(peer.lookup(b"peer id").is_none());
+        assert!(peer.lookup(b"ip").is_some());
+        assert!(peer.lookup(b"port").is_some());
+    }
+}
@@ -1,3 +1,4 @@
 pub mod torrents_updates;
 pub mod keys_updates;
-pub mod users_updates;
\ No newline at end of file
+pub mod users_updates;
+pub mod peers_expiry;
\ No newline at end of file
@@ -0,0 +1,9 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::Arc;
+use std::time::Instant;
+use parking_lot::Mutex;
+use crate::tracker::structs::info_hash::InfoHash;
+use crate::tracker::structs::peer_id::PeerId;
+
+pub type PeersExpiry = Arc<Mutex<BinaryHeap<Reverse<(Instant, InfoHash, PeerId)>>>>;
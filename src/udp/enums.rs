@@ -1,4 +1,5 @@
 pub mod request_parse_error;
 pub mod request;
 pub mod response;
-pub mod server_error;
\ No newline at end of file
+pub mod server_error;pub mod announce_option;
+pub mod ip_address_policy;
@@ -0,0 +1,7 @@
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum AnnounceOption {
+    EndOfOptions,
+    Nop,
+    UrlData(Vec<u8>),
+    Unknown(u8, Vec<u8>),
+}
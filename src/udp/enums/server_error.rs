@@ -1,6 +1,7 @@
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+/// Errors answered to UDP and HTTP requests, also the `kind` of the per family error counters.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ServerError {
     #[error("internal server error")]
     InternalServerError,
@@ -40,4 +41,10 @@ pub enum ServerError {
 
     #[error("maintenance mode enabled, please try again later")]
     MaintenanceMode,
+
+    #[error("announce interval too short")]
+    AnnounceThrottled,
+
+    #[error("connection id expired or invalid")]
+    ConnectionIdInvalid,
 }
\ No newline at end of file
@@ -3,4 +3,6 @@ pub mod request;
 pub mod response;
 pub mod ipv4_addr;
 pub mod ipv6_addr;
-pub mod udp_server;
\ No newline at end of file
+pub mod udp_server;
+pub mod connection_id_secrets;pub mod udp_packet_batch;
+pub mod server_error;
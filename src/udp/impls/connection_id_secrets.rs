@@ -0,0 +1,58 @@
+use std::net::{IpAddr, SocketAddr};
+use sha1::{Digest, Sha1};
+use crate::udp::structs::connection_id::ConnectionId;
+use crate::udp::structs::connection_id_secrets::ConnectionIdSecrets;
+
+impl ConnectionIdSecrets {
+    /// Length of one time bucket in seconds, a connection ID stays valid for the bucket it was issued in
+    /// and the one after, which covers the two minutes BEP 15 asks trackers to accept it for.
+    pub const BUCKET_SECONDS: u64 = 120;
+
+    pub fn new(bucket: u64) -> ConnectionIdSecrets
+    {
+        ConnectionIdSecrets {
+            bucket,
+            current: rand::random(),
+            previous: rand::random(),
+        }
+    }
+
+    /// Moves the secrets forward to the given bucket, a fresh random secret is drawn for every bucket.
+    pub fn rotate(&mut self, bucket: u64)
+    {
+        if bucket <= self.bucket {
+            return;
+        }
+        self.previous = if bucket == self.bucket + 1 { self.current } else { rand::random() };
+        self.current = rand::random();
+        self.bucket = bucket;
+    }
+
+    pub fn needs_rotation(&self, bucket: u64) -> bool
+    {
+        bucket > self.bucket
+    }
+
+    pub fn connection_id(&self, remote_addr: &SocketAddr) -> ConnectionId
+    {
+        Self::keyed_hash(&self.current, remote_addr)
+    }
+
+    pub fn validate(&self, connection_id: ConnectionId, remote_addr: &SocketAddr) -> bool
+    {
+        Self::keyed_hash(&self.current, remote_addr) == connection_id || Self::keyed_hash(&self.previous, remote_addr) == connection_id
+    }
+
+    fn keyed_hash(secret: &[u8; 32], remote_addr: &SocketAddr) -> ConnectionId
+    {
+        let mut hasher = Sha1::new();
+        hasher.update(secret);
+        match remote_addr.ip() {
+            IpAddr::V4(ip) => { hasher.update(ip.octets()); }
+            IpAddr::V6(ip) => { hasher.update(ip.octets()); }
+        }
+        hasher.update(remote_addr.port().to_be_bytes());
+        let hash = hasher.finalize();
+        ConnectionId(i64::from_be_bytes(<[u8; 8]>::try_from(&hash[0..8]).unwrap()))
+    }
+}
@@ -6,6 +6,7 @@ use crate::common::structs::number_of_bytes::NumberOfBytes;
 use crate::tracker::enums::announce_event::AnnounceEvent;
 use crate::tracker::structs::info_hash::InfoHash;
 use crate::tracker::structs::peer_id::PeerId;
+use crate::udp::enums::announce_option::AnnounceOption;
 use crate::udp::enums::request::Request;
 use crate::udp::enums::request_parse_error::RequestParseError;
 use crate::udp::structs::announce_request::AnnounceRequest;
@@ -65,6 +66,29 @@ impl Request {
                 bytes.write_u32::<NetworkEndian>(r.key.0)?;
                 bytes.write_i32::<NetworkEndian>(r.peers_wanted.0)?;
                 bytes.write_u16::<NetworkEndian>(r.port.0)?;
+
+                for option in r.options {
+                    match option {
+                        AnnounceOption::EndOfOptions => { bytes.write_u8(0)?; }
+                        AnnounceOption::Nop => { bytes.write_u8(1)?; }
+                        AnnounceOption::UrlData(data) => {
+                            // BEP 41 concatenates consecutive URLData options, so longer data is split up
+                            for chunk in data.chunks(u8::MAX as usize) {
+                                bytes.write_u8(2)?;
+                                bytes.write_u8(chunk.len() as u8)?;
+                                bytes.write_all(chunk)?;
+                            }
+                        }
+                        AnnounceOption::Unknown(option_type, data) => {
+                            let length = u8::try_from(data.len()).map_err(|_| {
+                                io::Error::new(io::ErrorKind::InvalidInput, format!("announce option {} longer than 255 bytes", option_type))
+                            })?;
+                            bytes.write_u8(option_type)?;
+                            bytes.write_u8(length)?;
+                            bytes.write_all(&data)?;
+                        }
+                    }
+                }
             }
 
             Request::Scrape(r) => {
@@ -156,20 +180,14 @@ impl Request {
                     Some(Ipv4Addr::from(ip))
                 };
 
-                let option_byte = cursor.read_u8();
-                let option_size = cursor.read_u8();
-                let mut path: &str = "";
-                let mut path_array = vec![];
-
-                let option_byte_value = option_byte.unwrap_or_default();
-                let option_size_value = option_size.unwrap_or_default();
-                if option_byte_value == 2 {
-                    path_array.resize(option_size_value as usize, 0u8);
-                    cursor.read_exact(&mut path_array).map_err(|err| {
-                        RequestParseError::sendable_io(err, connection_id, transaction_id)
-                    })?;
-                    path = std::str::from_utf8(&path_array).unwrap_or_default();
-                }
+                let options = Request::read_announce_options(&mut cursor).map_err(|err| {
+                    RequestParseError::sendable_io(err, connection_id, transaction_id)
+                })?;
+                let path_array: Vec<u8> = options.iter().filter_map(|option| match option {
+                    AnnounceOption::UrlData(data) => { Some(data.as_slice()) }
+                    _ => { None }
+                }).flatten().copied().collect();
+                let path = String::from_utf8(path_array).unwrap_or_default();
 
                 Ok((AnnounceRequest {
                     connection_id: ConnectionId(connection_id),
@@ -184,7 +202,8 @@ impl Request {
                     key: PeerKey(key),
                     peers_wanted: NumberOfPeers(peers_wanted),
                     port: Port(port),
-                    path: path.to_string(),
+                    options,
+                    path,
                 })
                     .into())
             }
@@ -223,4 +242,31 @@ impl Request {
             )),
         }
     }
+
+    /// Reads the BEP 41 options trailing an announce request until the end of the packet or an EndOfOptions.
+    /// Every option except EndOfOptions and NOP carries a length byte, so unknown types are kept and skipped.
+    #[tracing::instrument(skip(cursor), level = "debug")]
+    pub fn read_announce_options(cursor: &mut Cursor<&[u8]>) -> Result<Vec<AnnounceOption>, io::Error> {
+        let mut options = vec![];
+        while (cursor.position() as usize) < cursor.get_ref().len() {
+            let option_type = cursor.read_u8()?;
+            match option_type {
+                0 => {
+                    options.push(AnnounceOption::EndOfOptions);
+                    break;
+                }
+                1 => { options.push(AnnounceOption::Nop); }
+                _ => {
+                    let option_size = cursor.read_u8()?;
+                    let mut data = vec![0u8; option_size as usize];
+                    cursor.read_exact(&mut data)?;
+                    options.push(match option_type {
+                        2 => { AnnounceOption::UrlData(data) }
+                        _ => { AnnounceOption::Unknown(option_type, data) }
+                    });
+                }
+            }
+        }
+        Ok(options)
+    }
 }
\ No newline at end of file
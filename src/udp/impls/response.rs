@@ -48,6 +48,23 @@ impl From<ErrorResponse> for Response {
 }
 
 impl Response {
+    pub const ANNOUNCE_HEADER_SIZE: usize = 20;
+
+    /// Number of peers an announce response can carry without exceeding `max_size` bytes.
+    pub fn announce_peers_fitting(max_size: usize, ipv6: bool) -> usize {
+        let peer_size = if ipv6 { 18 } else { 6 };
+        max_size.saturating_sub(Self::ANNOUNCE_HEADER_SIZE) / peer_size
+    }
+
+    /// Drops trailing peers from an announce response so it encodes within `max_size` bytes.
+    pub fn truncate_peers(&mut self, max_size: usize) {
+        match self {
+            Response::AnnounceIpv4(r) => { r.peers.truncate(Self::announce_peers_fitting(max_size, false)); }
+            Response::AnnounceIpv6(r) => { r.peers.truncate(Self::announce_peers_fitting(max_size, true)); }
+            _ => {}
+        }
+    }
+
     #[tracing::instrument(skip(bytes), level = "debug")]
     #[inline]
     pub fn write(&self, bytes: &mut impl Write) -> Result<(), io::Error> {
@@ -0,0 +1,51 @@
+use crate::stats::enums::error_family::ErrorFamily;
+use crate::udp::enums::server_error::ServerError;
+
+impl ServerError {
+    pub const ALL: [ServerError; 15] = [
+        ServerError::InternalServerError,
+        ServerError::InvalidInfoHash,
+        ServerError::UnknownInfoHash,
+        ServerError::AddressNotFound,
+        ServerError::NoPeersFound,
+        ServerError::TorrentNotWhitelisted,
+        ServerError::TorrentBlacklisted,
+        ServerError::UnknownKey,
+        ServerError::PeerNotAuthenticated,
+        ServerError::PeerKeyNotValid,
+        ServerError::ExceededInfoHashLimit,
+        ServerError::BadRequest,
+        ServerError::MaintenanceMode,
+        ServerError::AnnounceThrottled,
+        ServerError::ConnectionIdInvalid,
+    ];
+
+    /// The `kind` label of this error in the stats and metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ServerError::InternalServerError => { "internal_server_error" }
+            ServerError::InvalidInfoHash => { "invalid_info_hash" }
+            ServerError::UnknownInfoHash => { "unknown_info_hash" }
+            ServerError::AddressNotFound => { "address_not_found" }
+            ServerError::NoPeersFound => { "no_peers_found" }
+            ServerError::TorrentNotWhitelisted => { "torrent_not_whitelisted" }
+            ServerError::TorrentBlacklisted => { "torrent_blacklisted" }
+            ServerError::UnknownKey => { "unknown_key" }
+            ServerError::PeerNotAuthenticated => { "peer_not_authenticated" }
+            ServerError::PeerKeyNotValid => { "peer_key_not_valid" }
+            ServerError::ExceededInfoHashLimit => { "exceeded_info_hash_limit" }
+            ServerError::BadRequest => { "bad_request" }
+            ServerError::MaintenanceMode => { "maintenance_mode" }
+            ServerError::AnnounceThrottled => { "announce_throttled" }
+            ServerError::ConnectionIdInvalid => { "connection_id_invalid" }
+        }
+    }
+
+    /// Whether the error can occur for the family at all, connection ids only exist in the UDP protocol.
+    pub fn applies_to(&self, family: ErrorFamily) -> bool {
+        match self {
+            ServerError::ConnectionIdInvalid => { family.is_udp() }
+            _ => { true }
+        }
+    }
+}
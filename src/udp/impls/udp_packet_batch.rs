@@ -0,0 +1,213 @@
+use std::io;
+use std::io::Cursor;
+use std::net::SocketAddr;
+use tokio::net::UdpSocket;
+use crate::udp::enums::response::Response;
+use crate::udp::structs::udp_packet_batch::UdpPacketBatch;
+#[cfg(target_os = "linux")]
+use crate::udp::udp::UDP_BATCH_SIZE;
+
+impl UdpPacketBatch {
+    /// Allocates `capacity` buffers of `buffer_size` bytes once, to be reused for every batch.
+    pub fn new(capacity: usize, buffer_size: usize) -> UdpPacketBatch
+    {
+        UdpPacketBatch {
+            buffers: vec![vec![0u8; buffer_size]; capacity],
+            packets: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.packets.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.packets.is_empty()
+    }
+
+    pub fn clear(&mut self)
+    {
+        self.packets.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], SocketAddr)>
+    {
+        self.packets.iter().zip(self.buffers.iter()).map(|((length, remote_addr), buffer)| (&buffer[..*length], *remote_addr))
+    }
+
+    /// Writes the response into the next free buffer, leaving the batch untouched when it does not fit.
+    pub fn push_response(&mut self, remote_addr: SocketAddr, response: &Response) -> Result<(), io::Error>
+    {
+        let index = self.packets.len();
+        if index >= self.buffers.len() {
+            return Err(io::Error::new(io::ErrorKind::OutOfMemory, "packet batch is full"));
+        }
+        let mut cursor = Cursor::new(self.buffers[index].as_mut_slice());
+        response.write(&mut cursor)?;
+        let length = cursor.position() as usize;
+        self.packets.push((length, remote_addr));
+        Ok(())
+    }
+
+    /// Waits for at least one datagram and then drains whatever else is already queued, up to the batch capacity.
+    #[cfg(target_os = "linux")]
+    pub async fn recv_from(&mut self, socket: &UdpSocket) -> Result<usize, io::Error>
+    {
+        use std::os::fd::AsRawFd;
+        use tokio::io::Interest;
+
+        self.clear();
+        let fd = socket.as_raw_fd();
+        socket.async_io(Interest::READABLE, || self.recv_mmsg(fd)).await
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub async fn recv_from(&mut self, socket: &UdpSocket) -> Result<usize, io::Error>
+    {
+        self.clear();
+        let (length, remote_addr) = socket.recv_from(&mut self.buffers[0]).await?;
+        self.packets.push((length, remote_addr));
+        while self.packets.len() < self.buffers.len() {
+            let index = self.packets.len();
+            match socket.try_recv_from(&mut self.buffers[index]) {
+                Ok((length, remote_addr)) => { self.packets.push((length, remote_addr)); }
+                Err(_) => { break; }
+            }
+        }
+        Ok(self.packets.len())
+    }
+
+    /// Sends every queued packet, skipping destinations that fail instead of aborting the batch.
+    #[cfg(target_os = "linux")]
+    pub async fn send_to(&mut self, socket: &UdpSocket)
+    {
+        use std::os::fd::AsRawFd;
+        use tokio::io::Interest;
+
+        let fd = socket.as_raw_fd();
+        let mut sent = 0;
+        while sent < self.packets.len() {
+            match socket.async_io(Interest::WRITABLE, || self.send_mmsg(fd, sent)).await {
+                Ok(count) => { sent += count.max(1); }
+                Err(_) => { sent += 1; }
+            }
+        }
+        self.clear();
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub async fn send_to(&mut self, socket: &UdpSocket)
+    {
+        for (payload, remote_addr) in self.iter() {
+            let _ = socket.send_to(payload, remote_addr).await;
+        }
+        self.clear();
+    }
+
+    #[cfg(target_os = "linux")]
+    fn recv_mmsg(&mut self, fd: std::os::fd::RawFd) -> Result<usize, io::Error>
+    {
+        let capacity = self.buffers.len().min(UDP_BATCH_SIZE);
+        // SAFETY: all-zero is a valid representation for these plain C structs.
+        let mut addresses: [libc::sockaddr_storage; UDP_BATCH_SIZE] = unsafe { std::mem::zeroed() };
+        let mut iovecs: [libc::iovec; UDP_BATCH_SIZE] = unsafe { std::mem::zeroed() };
+        let mut headers: [libc::mmsghdr; UDP_BATCH_SIZE] = unsafe { std::mem::zeroed() };
+        for index in 0..capacity {
+            iovecs[index].iov_base = self.buffers[index].as_mut_ptr() as *mut libc::c_void;
+            iovecs[index].iov_len = self.buffers[index].len();
+            headers[index].msg_hdr.msg_name = &mut addresses[index] as *mut libc::sockaddr_storage as *mut libc::c_void;
+            headers[index].msg_hdr.msg_namelen = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+            headers[index].msg_hdr.msg_iov = &mut iovecs[index];
+            headers[index].msg_hdr.msg_iovlen = 1;
+        }
+        // SAFETY: every header points at a buffer and address slot that outlive the call.
+        let count = unsafe { libc::recvmmsg(fd, headers.as_mut_ptr(), capacity as libc::c_uint, libc::MSG_DONTWAIT, std::ptr::null_mut()) };
+        if count < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        for index in 0..count as usize {
+            // Truncated datagrams and unknown address families are dropped, the buffers are swapped so every packet
+            // keeps the buffer at its own index.
+            if headers[index].msg_hdr.msg_flags & libc::MSG_TRUNC != 0 {
+                continue;
+            }
+            if let Some(remote_addr) = sockaddr_to_socket_addr(&addresses[index]) {
+                self.buffers.swap(self.packets.len(), index);
+                self.packets.push((headers[index].msg_len as usize, remote_addr));
+            }
+        }
+        Ok(self.packets.len())
+    }
+
+    #[cfg(target_os = "linux")]
+    fn send_mmsg(&mut self, fd: std::os::fd::RawFd, offset: usize) -> Result<usize, io::Error>
+    {
+        let count = (self.packets.len() - offset).min(UDP_BATCH_SIZE);
+        // SAFETY: all-zero is a valid representation for these plain C structs.
+        let mut addresses: [libc::sockaddr_storage; UDP_BATCH_SIZE] = unsafe { std::mem::zeroed() };
+        let mut iovecs: [libc::iovec; UDP_BATCH_SIZE] = unsafe { std::mem::zeroed() };
+        let mut headers: [libc::mmsghdr; UDP_BATCH_SIZE] = unsafe { std::mem::zeroed() };
+        for index in 0..count {
+            let (length, remote_addr) = self.packets[offset + index];
+            iovecs[index].iov_base = self.buffers[offset + index].as_mut_ptr() as *mut libc::c_void;
+            iovecs[index].iov_len = length;
+            headers[index].msg_hdr.msg_namelen = socket_addr_to_sockaddr(&remote_addr, &mut addresses[index]);
+            headers[index].msg_hdr.msg_name = &mut addresses[index] as *mut libc::sockaddr_storage as *mut libc::c_void;
+            headers[index].msg_hdr.msg_iov = &mut iovecs[index];
+            headers[index].msg_hdr.msg_iovlen = 1;
+        }
+        // SAFETY: every header points at a buffer and address that outlive the call.
+        let sent = unsafe { libc::sendmmsg(fd, headers.as_mut_ptr(), count as libc::c_uint, libc::MSG_DONTWAIT) };
+        if sent < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(sent as usize)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn sockaddr_to_socket_addr(storage: &libc::sockaddr_storage) -> Option<SocketAddr>
+{
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+    match storage.ss_family as libc::c_int {
+        libc::AF_INET => {
+            // SAFETY: the family says the storage holds a sockaddr_in.
+            let address = unsafe { &*(storage as *const libc::sockaddr_storage as *const libc::sockaddr_in) };
+            Some(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::from(u32::from_be(address.sin_addr.s_addr)), u16::from_be(address.sin_port))))
+        }
+        libc::AF_INET6 => {
+            // SAFETY: the family says the storage holds a sockaddr_in6.
+            let address = unsafe { &*(storage as *const libc::sockaddr_storage as *const libc::sockaddr_in6) };
+            Some(SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::from(address.sin6_addr.s6_addr), u16::from_be(address.sin6_port), address.sin6_flowinfo, address.sin6_scope_id)))
+        }
+        _ => { None }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn socket_addr_to_sockaddr(remote_addr: &SocketAddr, storage: &mut libc::sockaddr_storage) -> libc::socklen_t
+{
+    match remote_addr {
+        SocketAddr::V4(remote_addr) => {
+            // SAFETY: sockaddr_storage is large and aligned enough to hold a sockaddr_in.
+            let address = unsafe { &mut *(storage as *mut libc::sockaddr_storage as *mut libc::sockaddr_in) };
+            address.sin_family = libc::AF_INET as libc::sa_family_t;
+            address.sin_port = remote_addr.port().to_be();
+            address.sin_addr.s_addr = u32::from(*remote_addr.ip()).to_be();
+            size_of::<libc::sockaddr_in>() as libc::socklen_t
+        }
+        SocketAddr::V6(remote_addr) => {
+            // SAFETY: sockaddr_storage is large and aligned enough to hold a sockaddr_in6.
+            let address = unsafe { &mut *(storage as *mut libc::sockaddr_storage as *mut libc::sockaddr_in6) };
+            address.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            address.sin6_port = remote_addr.port().to_be();
+            address.sin6_flowinfo = remote_addr.flowinfo();
+            address.sin6_addr.s6_addr = remote_addr.ip().octets();
+            address.sin6_scope_id = remote_addr.scope_id();
+            size_of::<libc::sockaddr_in6>() as libc::socklen_t
+        }
+    }
+}
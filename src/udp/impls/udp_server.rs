@@ -1,16 +1,24 @@
 use std::io::Cursor;
-use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::SystemTime;
 use log::{debug, info};
+use socket2::{Domain, Protocol, Socket, Type};
 use tokio::net::UdpSocket;
+use crate::common::structs::cidr::Cidr;
+use crate::config::structs::udp_trackers_config::UdpTrackersConfig;
+use crate::stats::enums::error_family::ErrorFamily;
 use crate::stats::enums::stats_event::StatsEvent;
+use crate::tracker::enums::request_interval_throttle::RequestIntervalThrottle;
 use crate::tracker::enums::torrent_peers_type::TorrentPeersType;
 use crate::tracker::structs::announce_query_request::AnnounceQueryRequest;
+use crate::tracker::structs::announce_response::AnnounceResponse as TrackerAnnounceResponse;
 use crate::tracker::structs::info_hash::InfoHash;
 use crate::tracker::structs::peer_id::PeerId;
 use crate::tracker::structs::torrent_tracker::TorrentTracker;
 use crate::tracker::structs::user_id::UserId;
+use crate::udp::enums::ip_address_policy::IpAddressPolicy;
 use crate::udp::enums::request::Request;
 use crate::udp::enums::response::Response;
 use crate::udp::enums::server_error::ServerError;
@@ -20,72 +28,118 @@ use crate::udp::structs::announce_response::AnnounceResponse;
 use crate::udp::structs::connect_request::ConnectRequest;
 use crate::udp::structs::connect_response::ConnectResponse;
 use crate::udp::structs::connection_id::ConnectionId;
+use crate::udp::structs::connection_id_secrets::ConnectionIdSecrets;
 use crate::udp::structs::error_response::ErrorResponse;
 use crate::udp::structs::number_of_downloads::NumberOfDownloads;
 use crate::udp::structs::number_of_peers::NumberOfPeers;
-use crate::udp::structs::port::Port;
-use crate::udp::structs::response_peer::ResponsePeer;
 use crate::udp::structs::scrape_request::ScrapeRequest;
 use crate::udp::structs::scrape_response::ScrapeResponse;
 use crate::udp::structs::torrent_scrape_statistics::TorrentScrapeStatistics;
 use crate::udp::structs::transaction_id::TransactionId;
 use crate::udp::structs::udp_server::UdpServer;
-use crate::udp::udp::{MAX_PACKET_SIZE, MAX_SCRAPE_TORRENTS};
+use crate::udp::structs::udp_packet_batch::UdpPacketBatch;
+use crate::udp::udp::{MAX_PACKET_SIZE, MAX_SCRAPE_TORRENTS, UDP_BATCH_SIZE, UDP_RECV_BUFFER_SIZE};
 
 impl UdpServer {
+    /// Binds one socket per worker. Where `SO_REUSEPORT` is available the kernel spreads datagrams over
+    /// the sockets, elsewhere a single socket is shared by all workers. The trusted proxy CIDRs are parsed once here,
+    /// invalid entries were already rejected when the configuration was validated.
     #[tracing::instrument(level = "debug")]
-    pub async fn new(tracker: Arc<TorrentTracker>, bind_address: SocketAddr, threads: u64) -> tokio::io::Result<UdpServer>
+    pub async fn new(tracker: Arc<TorrentTracker>, bind_address: SocketAddr, threads: u64, udp_trackers_config: Arc<UdpTrackersConfig>) -> tokio::io::Result<UdpServer>
     {
-        let socket = UdpSocket::bind(bind_address).await?;
+        let first_socket = Arc::new(UdpServer::bind_socket(bind_address)?);
+        let local_address = first_socket.local_addr()?;
+        let mut sockets = vec![first_socket.clone()];
+        for _index in 0..threads {
+            if cfg!(all(unix, not(any(target_os = "solaris", target_os = "illumos")))) {
+                sockets.push(Arc::new(UdpServer::bind_socket(local_address)?));
+            } else {
+                sockets.push(first_socket.clone());
+            }
+        }
 
+        let trusted_proxies = Arc::new(udp_trackers_config.trusted_proxies.iter().filter_map(|cidr| Cidr::from_str(cidr).ok()).collect());
         Ok(UdpServer {
-            socket: Arc::new(socket),
-            threads,
+            sockets,
             tracker,
+            udp_trackers_config,
+            trusted_proxies,
         })
     }
 
+    #[tracing::instrument(level = "debug")]
+    pub fn bind_socket(bind_address: SocketAddr) -> tokio::io::Result<UdpSocket>
+    {
+        let socket = Socket::new(Domain::for_address(bind_address), Type::DGRAM, Some(Protocol::UDP))?;
+        #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+        socket.set_reuse_port(true)?;
+        socket.set_nonblocking(true)?;
+        socket.bind(&bind_address.into())?;
+        UdpSocket::from_std(socket.into())
+    }
+
+    pub fn local_addr(&self) -> tokio::io::Result<SocketAddr>
+    {
+        self.sockets[0].local_addr()
+    }
+
     #[tracing::instrument(level = "debug")]
     pub async fn start(&self, rx: tokio::sync::watch::Receiver<bool>)
     {
-        let threads = self.threads;
-        for _index in 0..=threads {
-            let socket_clone = self.socket.clone();
+        for socket in self.sockets.iter() {
+            let socket = socket.clone();
             let tracker = self.tracker.clone();
+            let udp_trackers_config = self.udp_trackers_config.clone();
+            let trusted_proxies = self.trusted_proxies.clone();
             let mut rx = rx.clone();
-            let mut data = [0; 65507];
             tokio::spawn(async move {
+                let udp_sock = socket.local_addr().unwrap();
+                let mut requests = UdpPacketBatch::new(UDP_BATCH_SIZE, UDP_RECV_BUFFER_SIZE);
+                let mut responses = UdpPacketBatch::new(UDP_BATCH_SIZE, MAX_PACKET_SIZE);
                 loop {
-                    let udp_sock = socket_clone.local_addr().unwrap();
-                    tokio::select! {
+                    let received = tokio::select! {
                         _ = rx.changed() => {
                             info!("Stopping UDP server: {}...", udp_sock);
                             break;
                         }
-                        Ok((valid_bytes, remote_addr)) = socket_clone.recv_from(&mut data) => {
-                            let payload = data[..valid_bytes].to_vec();
+                        received = requests.recv_from(&socket) => { received }
+                    };
+                    if let Err(error) = received {
+                        debug!("Receiving from {} failed: {}", udp_sock, error);
+                        continue;
+                    }
 
-                            debug!("Received {} bytes from {}", payload.len(), remote_addr);
-                            debug!("{:?}", payload);
+                    for (payload, remote_addr) in requests.iter() {
+                        debug!("Received {} bytes from {}", payload.len(), remote_addr);
+                        debug!("{:?}", payload);
 
-                            let remote_addr_cloned = remote_addr;
-                            let payload_cloned = payload.clone();
-                            let tracker_cloned = tracker.clone();
-                            let socket_cloned = socket_clone.clone();
-                            tokio::spawn(async move {
-                                let response = UdpServer::handle_packet(remote_addr_cloned, payload_cloned, tracker_cloned.clone()).await;
-                                UdpServer::send_response(tracker_cloned.clone(), socket_cloned.clone(), remote_addr_cloned, response).await;
-                            });
-                        }
+                        let response = UdpServer::handle_packet(remote_addr, payload, tracker.clone(), udp_trackers_config.clone(), trusted_proxies.clone()).await;
+                        UdpServer::queue_response(tracker.clone(), &mut responses, remote_addr, response);
                     }
+                    responses.send_to(&socket).await;
                 }
             });
         }
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn send_response(tracker: Arc<TorrentTracker>, socket: Arc<UdpSocket>, remote_addr: SocketAddr, response: Response)
+    pub fn queue_response(tracker: Arc<TorrentTracker>, responses: &mut UdpPacketBatch, remote_addr: SocketAddr, mut response: Response)
+    {
+        response.truncate_peers(MAX_PACKET_SIZE);
+        if let Err(error) = responses.push_response(remote_addr, &response) {
+            sentry::capture_error(&error);
+            match remote_addr {
+                SocketAddr::V4(_) => { tracker.update_stats(StatsEvent::Udp4InvalidRequest, 1); }
+                SocketAddr::V6(_) => { tracker.update_stats(StatsEvent::Udp6InvalidRequest, 1); }
+            }
+            debug!("could not write response to bytes.");
+        }
+    }
+
+    #[tracing::instrument(level = "debug")]
+    pub async fn send_response(tracker: Arc<TorrentTracker>, socket: Arc<UdpSocket>, remote_addr: SocketAddr, mut response: Response)
     {
+        response.truncate_peers(MAX_PACKET_SIZE);
         debug!("sending response to: {:?}", &remote_addr);
         let sentry = sentry::TransactionContext::new("udp server", "send response");
         let transaction = sentry::start_transaction(sentry);
@@ -120,16 +174,38 @@ impl UdpServer {
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn get_connection_id(remote_address: &SocketAddr) -> ConnectionId {
-        match SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
-            Ok(duration) => ConnectionId(((duration.as_secs() / 3600) | ((remote_address.port() as u64) << 36)) as i64),
-            Err(_) => ConnectionId(0x7FFFFFFFFFFFFFFF)
+    pub fn get_connection_id_secrets(tracker: Arc<TorrentTracker>) -> ConnectionIdSecrets {
+        let bucket = match SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs() / ConnectionIdSecrets::BUCKET_SECONDS,
+            Err(_) => 0
+        };
+        let secrets = tracker.udp_connection_id_secrets.read_recursive().clone();
+        if !secrets.needs_rotation(bucket) {
+            return secrets;
         }
+        let mut lock = tracker.udp_connection_id_secrets.write();
+        lock.rotate(bucket);
+        lock.clone()
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn handle_packet(remote_addr: SocketAddr, payload: Vec<u8>, tracker: Arc<TorrentTracker>) -> Response {
-        match Request::from_bytes(&payload[..payload.len()], MAX_SCRAPE_TORRENTS).map_err(|_| ServerError::InternalServerError) {
+    pub async fn get_connection_id(remote_address: &SocketAddr, tracker: Arc<TorrentTracker>) -> ConnectionId {
+        UdpServer::get_connection_id_secrets(tracker).connection_id(remote_address)
+    }
+
+    #[tracing::instrument(level = "debug")]
+    pub async fn check_connection_id(connection_id: ConnectionId, remote_address: &SocketAddr, tracker: Arc<TorrentTracker>) -> Result<(), ServerError> {
+        if UdpServer::get_connection_id_secrets(tracker.clone()).validate(connection_id, remote_address) {
+            return Ok(());
+        }
+        debug!("[UDP ERROR] Connection ID Invalid");
+        tracker.update_stats(StatsEvent::UdpConnectionIdInvalid, 1);
+        Err(ServerError::ConnectionIdInvalid)
+    }
+
+    #[tracing::instrument(level = "debug")]
+    pub async fn handle_packet(remote_addr: SocketAddr, payload: &[u8], tracker: Arc<TorrentTracker>, udp_trackers_config: Arc<UdpTrackersConfig>, trusted_proxies: Arc<Vec<Cidr>>) -> Response {
+        match Request::from_bytes(payload, MAX_SCRAPE_TORRENTS).map_err(|_| ServerError::InternalServerError) {
             Ok(request) => {
                 let transaction_id = match &request {
                     Request::Connect(connect_request) => {
@@ -143,13 +219,14 @@ impl UdpServer {
                     }
                 };
 
-                match UdpServer::handle_request(request, remote_addr, tracker.clone()).await {
+                match UdpServer::handle_request(request, remote_addr, tracker.clone(), udp_trackers_config, trusted_proxies).await {
                     Ok(response) => response,
                     Err(e) => {
                         match remote_addr {
                             SocketAddr::V4(_) => { tracker.update_stats(StatsEvent::Udp4InvalidRequest, 1); }
                             SocketAddr::V6(_) => { tracker.update_stats(StatsEvent::Udp6InvalidRequest, 1); }
                         }
+                        tracker.update_error_stats(ErrorFamily::udp(remote_addr.ip()), e, 1);
                         UdpServer::handle_udp_error(e, transaction_id).await
                     }
                 }
@@ -159,13 +236,14 @@ impl UdpServer {
                     SocketAddr::V4(_) => { tracker.update_stats(StatsEvent::Udp4BadRequest, 1); }
                     SocketAddr::V6(_) => { tracker.update_stats(StatsEvent::Udp6BadRequest, 1); }
                 }
+                tracker.update_error_stats(ErrorFamily::udp(remote_addr.ip()), ServerError::BadRequest, 1);
                 UdpServer::handle_udp_error(ServerError::BadRequest, TransactionId(0)).await
             }
         }
     }
 
     #[tracing::instrument(level = "debug")]
-    pub async fn handle_request(request: Request, remote_addr: SocketAddr, tracker: Arc<TorrentTracker>) -> Result<Response, ServerError> {
+    pub async fn handle_request(request: Request, remote_addr: SocketAddr, tracker: Arc<TorrentTracker>, udp_trackers_config: Arc<UdpTrackersConfig>, trusted_proxies: Arc<Vec<Cidr>>) -> Result<Response, ServerError> {
         let sentry = sentry::TransactionContext::new("udp server", "handle packet");
         let transaction = sentry::start_transaction(sentry);
 
@@ -176,7 +254,7 @@ impl UdpServer {
             }
             Request::Announce(announce_request) => {
                 transaction.finish();
-                UdpServer::handle_udp_announce(remote_addr, &announce_request, tracker).await
+                UdpServer::handle_udp_announce(remote_addr, &announce_request, tracker, udp_trackers_config, trusted_proxies).await
             }
             Request::Scrape(scrape_request) => {
                 transaction.finish();
@@ -187,7 +265,7 @@ impl UdpServer {
 
     #[tracing::instrument(level = "debug")]
     pub async fn handle_udp_connect(remote_addr: SocketAddr, request: &ConnectRequest, tracker: Arc<TorrentTracker>) -> Result<Response, ServerError> {
-        let connection_id = UdpServer::get_connection_id(&remote_addr).await;
+        let connection_id = UdpServer::get_connection_id(&remote_addr, tracker.clone()).await;
         let response = Response::from(ConnectResponse {
             transaction_id: request.transaction_id,
             connection_id
@@ -203,8 +281,61 @@ impl UdpServer {
         Ok(response)
     }
 
+    /// Splits the BEP 41 URL data into the key and user key segments, following the HTTP routes
+    /// `/announce`, `/{key}/announce` and `/{key}/{userkey}announce`. The query string is ignored,
+    /// and paths matching none of the routes carry no keys.
+    #[tracing::instrument(level = "debug")]
+    pub fn parse_announce_path(path: &str) -> (Option<&str>, Option<&str>)
+    {
+        let path = path.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').collect();
+        match segments.as_slice() {
+            ["", key, "announce"] if !key.is_empty() => { (Some(key), None) }
+            ["", key, user_key] if !key.is_empty() && user_key.len() > "announce".len() && user_key.ends_with("announce") => {
+                (Some(key), Some(&user_key[..user_key.len() - "announce".len()]))
+            }
+            _ => { (None, None) }
+        }
+    }
+
     #[tracing::instrument(level = "debug")]
-    pub async fn handle_udp_announce(remote_addr: SocketAddr, request: &AnnounceRequest, tracker: Arc<TorrentTracker>) -> Result<Response, ServerError> {
+    pub fn decode_path_hash(segment: &str) -> Option<[u8; 20]>
+    {
+        if segment.len() != 40 {
+            return None;
+        }
+        hex::decode(segment).ok().and_then(|result| <[u8; 20]>::try_from(result.as_slice()).ok())
+    }
+
+    /// Picks the address stored for the announcing peer: the source address, or the `ip_address` field when
+    /// the listener trusts it, either always or only from the parsed proxy CIDRs.
+    pub fn announce_ip(remote_addr: SocketAddr, ip_address: Option<Ipv4Addr>, ip_address_policy: IpAddressPolicy, trusted_proxies: &[Cidr]) -> IpAddr
+    {
+        let trusted = match ip_address_policy {
+            IpAddressPolicy::ignore => { false }
+            IpAddressPolicy::trust => { true }
+            IpAddressPolicy::trust_proxies => { Cidr::contains_any(trusted_proxies, remote_addr.ip()) }
+        };
+        match ip_address {
+            Some(ip_address) if trusted => { IpAddr::V4(ip_address) }
+            _ => { remote_addr.ip() }
+        }
+    }
+
+    /// Resolves the peers wanted by a UDP announce: negative means the listener default of `peers_wanted_max`, zero
+    /// asks for no peers like an HTTP `numwant=0`, and the result never exceeds what fits in a single response datagram.
+    pub fn announce_peers_wanted(peers_wanted: NumberOfPeers, peers_wanted_max: u64, ipv6: bool) -> usize
+    {
+        let wanted = match u64::try_from(peers_wanted.0) {
+            Ok(wanted) => { wanted.min(peers_wanted_max) }
+            Err(_) => { peers_wanted_max }
+        };
+        (wanted as usize).min(Response::announce_peers_fitting(MAX_PACKET_SIZE, ipv6))
+    }
+
+    #[tracing::instrument(level = "debug")]
+    pub async fn handle_udp_announce(remote_addr: SocketAddr, request: &AnnounceRequest, tracker: Arc<TorrentTracker>, udp_trackers_config: Arc<UdpTrackersConfig>, trusted_proxies: Arc<Vec<Cidr>>) -> Result<Response, ServerError> {
+        UdpServer::check_connection_id(request.connection_id, &remote_addr, tracker.clone()).await?;
         if tracker.config.tracker_config.clone().whitelist_enabled && !tracker.check_whitelist(InfoHash(request.info_hash.0)) {
             debug!("[UDP ERROR] Torrent Not Whitelisted");
             return Err(ServerError::TorrentNotWhitelisted);
@@ -213,21 +344,16 @@ impl UdpServer {
             debug!("[UDP ERROR] Torrent Blacklisted");
             return Err(ServerError::TorrentBlacklisted);
         }
+        let (path_key, path_user_key) = UdpServer::parse_announce_path(&request.path);
         if tracker.config.tracker_config.clone().keys_enabled {
-            if request.path.len() < 50 {
-                debug!("[UDP ERROR] Unknown Key");
-                return Err(ServerError::UnknownKey);
-            }
-            let key_path_extract = &request.path[10..50];
-            match hex::decode(key_path_extract) {
-                Ok(result) => {
-                    let key = <[u8; 20]>::try_from(result[0..20].as_ref()).unwrap();
+            match path_key.and_then(UdpServer::decode_path_hash) {
+                Some(key) => {
                     if !tracker.check_key(InfoHash::from(key)) {
                         debug!("[UDP ERROR] Unknown Key");
                         return Err(ServerError::UnknownKey);
                     }
                 }
-                Err(_) => {
+                None => {
                     debug!("[UDP ERROR] Unknown Key");
                     return Err(ServerError::UnknownKey);
                 }
@@ -235,36 +361,29 @@ impl UdpServer {
         }
         let mut user_key: Option<UserId> = None;
         if tracker.config.tracker_config.clone().users_enabled {
-            let mut user_key_path_extract = None;
-            if tracker.config.tracker_config.clone().users_enabled && request.path.len() >= 91 {
-                user_key_path_extract = Some(&request.path[51..=91]);
-            }
-            if !tracker.config.tracker_config.clone().users_enabled && request.path.len() >= 50 {
-                user_key_path_extract = Some(&request.path[10..=50])
-            }
-            if user_key_path_extract.is_some() {
-                match hex::decode(user_key_path_extract.unwrap()) {
-                    Ok(result) => {
-                        let key = <[u8; 20]>::try_from(result[0..20].as_ref()).unwrap();
-                        user_key = match tracker.check_user_key(UserId::from(key)) {
-                            None => {
-                                debug!("[UDP ERROR] Peer Key Not Valid");
-                                return Err(ServerError::PeerKeyNotValid);
-                            }
-                            Some(user_id) => {
-                                Some(user_id)
-                            }
-                        };
-                    }
-                    Err(error) => {
-                        debug!("[UDP ERROR] Hex Decode Error");
-                        debug!("{:#?}", error);
+            let user_key_path_extract = match path_user_key {
+                Some(path_user_key) => { Some(path_user_key) }
+                None if !tracker.config.tracker_config.clone().keys_enabled => { path_key }
+                None => { None }
+            };
+            if let Some(user_key_path_extract) = user_key_path_extract {
+                user_key = match UdpServer::decode_path_hash(user_key_path_extract).and_then(|key| tracker.check_user_key(UserId::from(key))) {
+                    None => {
+                        debug!("[UDP ERROR] Peer Key Not Valid");
                         return Err(ServerError::PeerKeyNotValid);
                     }
-                }
+                    Some(user_id) => {
+                        Some(user_id)
+                    }
+                };
             }
         }
-        let torrent = match tracker.handle_announce(tracker.clone(), AnnounceQueryRequest {
+        let announce_ip = UdpServer::announce_ip(remote_addr, request.ip_address, udp_trackers_config.ip_address_policy, &trusted_proxies);
+        if announce_ip != remote_addr.ip() {
+            info!("[UDP] Announce from {} stored with ip_address {}", remote_addr, announce_ip);
+            tracker.update_stats(StatsEvent::UdpAnnounceIpOverridden, 1);
+        }
+        let announce_query = AnnounceQueryRequest {
             info_hash: InfoHash(request.info_hash.0),
             peer_id: PeerId(request.peer_id.0),
             port: request.port.0,
@@ -274,103 +393,49 @@ impl UdpServer {
             compact: false,
             no_peer_id: false,
             event: request.event,
-            remote_addr: remote_addr.ip(),
-            numwant: request.peers_wanted.0 as u64,
-        }, user_key).await {
-            Ok(result) => { result.1 }
-            Err(error) => {
-                debug!("[UDP ERROR] Handle Announce - Internal Server Error");
-                debug!("{:#?}", error);
-                return Err(ServerError::InternalServerError);
-            }
+            remote_addr: announce_ip,
+            remote_addr_alternate: None,
+            ip: None,
+            ipv4: None,
+            ipv6: None,
+            tracker_id: None,
+            numwant: UdpServer::announce_peers_wanted(request.peers_wanted, udp_trackers_config.peers_wanted_max, remote_addr.is_ipv6()) as u64,
         };
-        let torrent_peers = tracker.get_torrent_peers(request.info_hash, 72, TorrentPeersType::All, Some(remote_addr.ip()));
-        let mut peers: Vec<ResponsePeer<Ipv4Addr>> = Vec::new();
-        let mut peers6: Vec<ResponsePeer<Ipv6Addr>> = Vec::new();
-        let mut count = 0;
-        if request.bytes_left.0 as u64 != 0 {
-            if remote_addr.is_ipv4()  {
-                match torrent_peers {
-                    None => {}
-                    Some(ref torrent_peers_unwrapped) => {
-                        for (_, torrent_peer) in torrent_peers_unwrapped.seeds_ipv4.iter() {
-                            if count > 72 {
-                                break;
-                            }
-                            peers.push(ResponsePeer::<Ipv4Addr> {
-                                ip_address: torrent_peer.peer_addr.ip().to_string().parse::<Ipv4Addr>().unwrap(),
-                                port: Port(torrent_peer.peer_addr.port()),
-                            });
-                            count += 1;
-                        }
-                    }
-                }
-            } else {
-                match torrent_peers {
-                    None => {}
-                    Some(ref torrent_peers_unwrapped) => {
-                        for (_, torrent_peer) in torrent_peers_unwrapped.seeds_ipv6.iter() {
-                            if count > 72 {
-                                break;
-                            }
-                            peers6.push(ResponsePeer::<Ipv6Addr> {
-                                ip_address: torrent_peer.peer_addr.ip().to_string().parse::<Ipv6Addr>().unwrap(),
-                                port: Port(torrent_peer.peer_addr.port()),
-                            });
-                            count += 1;
-                        }
-                    }
-                }
-            }
+        let peers_type = if remote_addr.is_ipv4() { TorrentPeersType::IPv4 } else { TorrentPeersType::IPv6 };
+        let throttled = tracker.check_announce_throttle(&announce_query, peers_type);
+        if throttled.is_some() && tracker.config.tracker_config.request_interval_throttle == RequestIntervalThrottle::failure {
+            debug!("[UDP ERROR] Announce Throttled");
+            return Err(ServerError::AnnounceThrottled);
         }
-        if remote_addr.is_ipv4() {
-            match torrent_peers {
-                None => {}
-                Some(ref torrent_peers_unwrapped) => {
-                    for (_, torrent_peer) in torrent_peers_unwrapped.peers_ipv4.iter() {
-                        if count > 72 {
-                            break;
-                        }
-                        peers.push(ResponsePeer::<Ipv4Addr> {
-                            ip_address: torrent_peer.peer_addr.ip().to_string().parse::<Ipv4Addr>().unwrap(),
-                            port: Port(torrent_peer.peer_addr.port()),
-                        });
-                        count += 1;
-                    }
-                }
-            }
-        } else {
-            match torrent_peers {
-                None => {}
-                Some(ref torrent_peers_unwrapped) => {
-                    for (_, torrent_peer) in torrent_peers_unwrapped.peers_ipv6.iter() {
-                        if count > 72 {
-                            break;
-                        }
-                        peers6.push(ResponsePeer::<Ipv6Addr> {
-                            ip_address: torrent_peer.peer_addr.ip().to_string().parse::<Ipv6Addr>().unwrap(),
-                            port: Port(torrent_peer.peer_addr.port()),
-                        });
-                        count += 1;
+        let announce_selection = match throttled {
+            Some((_, announce_selection)) => { announce_selection }
+            None => {
+                match tracker.handle_announce(tracker.clone(), announce_query, peers_type, user_key, false).await {
+                    Ok(result) => { result.1 }
+                    Err(error) => {
+                        debug!("[UDP ERROR] Handle Announce - Internal Server Error");
+                        debug!("{:#?}", error);
+                        return Err(ServerError::InternalServerError);
                     }
                 }
             }
-        }
+        };
+        let peers_response = TrackerAnnounceResponse::new(announce_selection.peers_selection);
 
         let mut announce_response = Response::from(AnnounceResponse {
             transaction_id: request.transaction_id,
             announce_interval: AnnounceInterval(tracker.config.tracker_config.clone().request_interval as i32),
-            leechers: NumberOfPeers(torrent.peers.len() as i32),
-            seeders: NumberOfPeers(torrent.seeds.len() as i32),
-            peers,
+            leechers: NumberOfPeers(announce_selection.peers as i32),
+            seeders: NumberOfPeers(announce_selection.seeds as i32),
+            peers: peers_response.response_peers_ipv4(),
         });
         if remote_addr.is_ipv6() {
             announce_response = Response::from(AnnounceResponse {
                 transaction_id: request.transaction_id,
                 announce_interval: AnnounceInterval(tracker.config.tracker_config.clone().request_interval as i32),
-                leechers: NumberOfPeers(torrent.peers.len() as i32),
-                seeders: NumberOfPeers(torrent.seeds.len() as i32),
-                peers: peers6
+                leechers: NumberOfPeers(announce_selection.peers as i32),
+                seeders: NumberOfPeers(announce_selection.seeds as i32),
+                peers: peers_response.response_peers_ipv6()
             });
         }
         if remote_addr.is_ipv4() {
@@ -383,6 +448,7 @@ impl UdpServer {
 
     #[tracing::instrument(level = "debug")]
     pub async fn handle_udp_scrape(remote_addr: SocketAddr, request: &ScrapeRequest, tracker: Arc<TorrentTracker>) -> Result<Response, ServerError> {
+        UdpServer::check_connection_id(request.connection_id, &remote_addr, tracker.clone()).await?;
         let mut torrent_stats: Vec<TorrentScrapeStatistics> = Vec::new();
         for info_hash in request.info_hashes.iter() {
             let info_hash = InfoHash(info_hash.0);
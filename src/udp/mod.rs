@@ -3,4 +3,5 @@ pub mod impls;
 pub mod structs;
 pub mod traits;
 #[allow(clippy::module_inception)]
-pub mod udp;
\ No newline at end of file
+pub mod udp;
+pub mod tests;
\ No newline at end of file
@@ -14,4 +14,5 @@ pub mod number_of_downloads;
 pub mod port;
 pub mod peer_key;
 pub mod response_peer;
-pub mod udp_server;
\ No newline at end of file
+pub mod udp_server;
+pub mod connection_id_secrets;pub mod udp_packet_batch;
@@ -3,6 +3,7 @@ use crate::common::structs::number_of_bytes::NumberOfBytes;
 use crate::tracker::enums::announce_event::AnnounceEvent;
 use crate::tracker::structs::info_hash::InfoHash;
 use crate::tracker::structs::peer_id::PeerId;
+use crate::udp::enums::announce_option::AnnounceOption;
 use crate::udp::structs::connection_id::ConnectionId;
 use crate::udp::structs::number_of_peers::NumberOfPeers;
 use crate::udp::structs::peer_key::PeerKey;
@@ -23,5 +24,6 @@ pub struct AnnounceRequest {
     pub key: PeerKey,
     pub peers_wanted: NumberOfPeers,
     pub port: Port,
+    pub options: Vec<AnnounceOption>,
     pub path: String,
 }
\ No newline at end of file
@@ -0,0 +1,6 @@
+#[derive(Debug, Clone)]
+pub struct ConnectionIdSecrets {
+    pub(crate) bucket: u64,
+    pub(crate) current: [u8; 32],
+    pub(crate) previous: [u8; 32],
+}
@@ -0,0 +1,7 @@
+use std::net::SocketAddr;
+
+#[derive(Debug)]
+pub struct UdpPacketBatch {
+    pub(crate) buffers: Vec<Vec<u8>>,
+    pub(crate) packets: Vec<(usize, SocketAddr)>,
+}
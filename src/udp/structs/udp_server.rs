@@ -1,10 +1,13 @@
 use std::sync::Arc;
 use tokio::net::UdpSocket;
+use crate::common::structs::cidr::Cidr;
+use crate::config::structs::udp_trackers_config::UdpTrackersConfig;
 use crate::tracker::structs::torrent_tracker::TorrentTracker;
 
 #[derive(Debug)]
 pub struct UdpServer {
-    pub(crate) socket: Arc<UdpSocket>,
-    pub(crate) threads: u64,
+    pub(crate) sockets: Vec<Arc<UdpSocket>>,
     pub(crate) tracker: Arc<TorrentTracker>,
+    pub(crate) udp_trackers_config: Arc<UdpTrackersConfig>,
+    pub(crate) trusted_proxies: Arc<Vec<Cidr>>,
 }
\ No newline at end of file
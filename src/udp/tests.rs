@@ -0,0 +1,368 @@
+#![cfg(test)]
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::common::structs::cidr::Cidr;
+use crate::common::structs::number_of_bytes::NumberOfBytes;
+use crate::config::structs::configuration::Configuration;
+use crate::stats::enums::error_family::ErrorFamily;
+use crate::tracker::enums::announce_event::AnnounceEvent;
+use crate::udp::enums::ip_address_policy::IpAddressPolicy;
+use crate::tracker::structs::info_hash::InfoHash;
+use crate::tracker::structs::peer_id::PeerId;
+use crate::tracker::structs::torrent_tracker::TorrentTracker;
+use crate::udp::enums::announce_option::AnnounceOption;
+use crate::udp::enums::request::Request;
+use crate::udp::enums::request_parse_error::RequestParseError;
+use crate::udp::enums::response::Response;
+use crate::udp::enums::server_error::ServerError;
+use crate::udp::structs::announce_interval::AnnounceInterval;
+use crate::udp::structs::announce_request::AnnounceRequest;
+use crate::udp::structs::announce_response::AnnounceResponse;
+use crate::udp::structs::connection_id::ConnectionId;
+use crate::udp::structs::connect_request::ConnectRequest;
+use crate::udp::structs::connect_response::ConnectResponse;
+use crate::udp::structs::connection_id_secrets::ConnectionIdSecrets;
+use crate::udp::structs::number_of_peers::NumberOfPeers;
+use crate::udp::structs::peer_key::PeerKey;
+use crate::udp::structs::port::Port;
+use crate::udp::structs::response_peer::ResponsePeer;
+use crate::udp::structs::transaction_id::TransactionId;
+use crate::udp::structs::udp_packet_batch::UdpPacketBatch;
+use crate::udp::structs::udp_server::UdpServer;
+use crate::udp::udp::MAX_PACKET_SIZE;
+
+async fn test_tracker() -> Arc<TorrentTracker>
+{
+    let mut config = Configuration::init();
+    config.database.path = String::from("sqlite::memory:");
+    Arc::new(TorrentTracker::new(Arc::new(config), false).await)
+}
+
+fn announce_bytes(options: &[u8]) -> Vec<u8>
+{
+    let mut bytes = vec![];
+    Request::from(AnnounceRequest {
+        connection_id: ConnectionId(1),
+        transaction_id: TransactionId(2),
+        info_hash: InfoHash([3; 20]),
+        peer_id: PeerId([4; 20]),
+        bytes_downloaded: NumberOfBytes(0),
+        bytes_uploaded: NumberOfBytes(0),
+        bytes_left: NumberOfBytes(0),
+        event: AnnounceEvent::Started,
+        ip_address: None,
+        key: PeerKey(5),
+        peers_wanted: NumberOfPeers(-1),
+        port: Port(6881),
+        options: vec![],
+        path: String::new(),
+    }).write(&mut bytes).unwrap();
+    bytes.extend_from_slice(options);
+    bytes
+}
+
+fn parse_announce(options: &[u8]) -> Result<AnnounceRequest, RequestParseError>
+{
+    match Request::from_bytes(&announce_bytes(options), 74)? {
+        Request::Announce(request) => { Ok(request) }
+        request => { panic!("unexpected request {:?}", request) }
+    }
+}
+
+#[test]
+fn connection_id_is_bound_to_address_and_expires_after_two_buckets()
+{
+    let remote_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)), 6881);
+    let other_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)), 6882);
+    let mut secrets = ConnectionIdSecrets::new(100);
+    let connection_id = secrets.connection_id(&remote_addr);
+
+    assert!(secrets.validate(connection_id, &remote_addr));
+    assert!(!secrets.validate(connection_id, &other_addr));
+    assert!(!secrets.validate(ConnectionId(connection_id.0 ^ 1), &remote_addr));
+
+    secrets.rotate(101);
+    assert!(secrets.validate(connection_id, &remote_addr));
+    assert_ne!(secrets.connection_id(&remote_addr), connection_id);
+
+    secrets.rotate(102);
+    assert!(!secrets.validate(connection_id, &remote_addr));
+}
+
+#[test]
+fn announce_options_concatenate_url_data_and_stop_at_end_of_options()
+{
+    let request = parse_announce(&[2, 5, b'/', b'a', b'b', b'c', b'/', 1, 2, 9, b'a', b'n', b'n', b'o', b'u', b'n', b'c', b'e', b'?', 0, 2, 1, b'x']).unwrap();
+    assert_eq!(request.path, "/abc/announce?");
+    assert_eq!(request.options, vec![
+        AnnounceOption::UrlData(b"/abc/".to_vec()),
+        AnnounceOption::Nop,
+        AnnounceOption::UrlData(b"announce?".to_vec()),
+        AnnounceOption::EndOfOptions,
+    ]);
+
+    let request = parse_announce(&[]).unwrap();
+    assert!(request.options.is_empty());
+    assert_eq!(request.path, "");
+
+    let request = parse_announce(&[1, 7, 2, 0xaa, 0xbb, 2, 1, b'/']).unwrap();
+    assert_eq!(request.options, vec![AnnounceOption::Nop, AnnounceOption::Unknown(7, vec![0xaa, 0xbb]), AnnounceOption::UrlData(b"/".to_vec())]);
+    assert_eq!(request.path, "/");
+}
+
+#[test]
+fn announce_options_reject_truncated_streams()
+{
+    assert!(matches!(parse_announce(&[2]), Err(RequestParseError::Sendable { .. })));
+    assert!(matches!(parse_announce(&[2, 4, b'/', b'a']), Err(RequestParseError::Sendable { .. })));
+    assert!(matches!(parse_announce(&[2, 1, b'/', 9]), Err(RequestParseError::Sendable { .. })));
+    assert!(parse_announce(&[2, 1, b'/', 0, 2, 4]).is_ok());
+}
+
+#[test]
+fn announce_options_round_trip()
+{
+    let bytes = announce_bytes(&[1, 2, 3, b'/', b'a', b'/', 2, 8, b'a', b'n', b'n', b'o', b'u', b'n', b'c', b'e', 0]);
+    let request = Request::from_bytes(&bytes, 74).unwrap();
+    let mut written = vec![];
+    request.write(&mut written).unwrap();
+    assert_eq!(written, bytes);
+}
+
+#[test]
+fn announce_options_split_url_data_longer_than_an_option()
+{
+    let path = format!("/{}/announce", "a".repeat(300));
+    let mut request = match Request::from_bytes(&announce_bytes(&[]), 74).unwrap() {
+        Request::Announce(request) => { request }
+        request => { panic!("unexpected request {:?}", request) }
+    };
+    request.options = vec![AnnounceOption::UrlData(path.as_bytes().to_vec())];
+    let mut written = vec![];
+    Request::from(request.clone()).write(&mut written).unwrap();
+
+    let parsed = match Request::from_bytes(&written, 74).unwrap() {
+        Request::Announce(request) => { request }
+        request => { panic!("unexpected request {:?}", request) }
+    };
+    assert_eq!(parsed.path, path);
+    assert_eq!(parsed.options, vec![
+        AnnounceOption::UrlData(path.as_bytes()[..255].to_vec()),
+        AnnounceOption::UrlData(path.as_bytes()[255..].to_vec()),
+    ]);
+    let mut rewritten = vec![];
+    Request::from(parsed).write(&mut rewritten).unwrap();
+    assert_eq!(rewritten, written);
+
+    request.options = vec![AnnounceOption::Unknown(7, vec![0; 256])];
+    assert!(Request::from(request).write(&mut vec![]).is_err());
+}
+
+#[test]
+fn announce_path_matches_http_routes()
+{
+    let key = "0123456789abcdef0123456789abcdef01234567";
+    let user_key = "89abcdef0123456789abcdef0123456789abcdef";
+    assert_eq!(UdpServer::parse_announce_path(""), (None, None));
+    assert_eq!(UdpServer::parse_announce_path("/announce"), (None, None));
+    assert_eq!(UdpServer::parse_announce_path(&format!("/{key}/announce")), (Some(key), None));
+    assert_eq!(UdpServer::parse_announce_path(&format!("/{key}/announce?info=1")), (Some(key), None));
+    assert_eq!(UdpServer::parse_announce_path(&format!("/{key}/{user_key}announce")), (Some(key), Some(user_key)));
+    assert_eq!(UdpServer::parse_announce_path(&format!("//{user_key}announce")), (None, None));
+    assert_eq!(UdpServer::parse_announce_path(&format!("/{key}/scrape")), (None, None));
+    assert_eq!(UdpServer::parse_announce_path(&format!("/{key}/{user_key}/announce")), (None, None));
+
+    assert_eq!(UdpServer::decode_path_hash(key), Some(hex::decode(key).unwrap().try_into().unwrap()));
+    assert_eq!(UdpServer::decode_path_hash(&key[..38]), None);
+    assert_eq!(UdpServer::decode_path_hash("zz23456789abcdef0123456789abcdef01234567"), None);
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn packet_batch_drops_truncated_datagrams_and_keeps_buffers_aligned()
+{
+    let server = tokio::net::UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).await.unwrap();
+    let client = tokio::net::UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).await.unwrap();
+    let server_addr = server.local_addr().unwrap();
+    let client_addr = client.local_addr().unwrap();
+    client.send_to(&[1u8; 32], server_addr).await.unwrap();
+    client.send_to(&[2u8; 8], server_addr).await.unwrap();
+    client.send_to(&[3u8; 16], server_addr).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut requests = UdpPacketBatch::new(4, 16);
+    assert_eq!(requests.recv_from(&server).await.unwrap(), 2);
+    let packets: Vec<(Vec<u8>, SocketAddr)> = requests.iter().map(|(payload, remote_addr)| (payload.to_vec(), remote_addr)).collect();
+    assert_eq!(packets, vec![(vec![2u8; 8], client_addr), (vec![3u8; 16], client_addr)]);
+
+    let mut responses = UdpPacketBatch::new(4, MAX_PACKET_SIZE);
+    for transaction_id in 0..2 {
+        responses.push_response(client_addr, &Response::from(ConnectResponse { connection_id: ConnectionId(9), transaction_id: TransactionId(transaction_id) })).unwrap();
+    }
+    responses.send_to(&server).await;
+    let mut buffer = [0u8; 1500];
+    for transaction_id in 0..2 {
+        let (length, remote_addr) = tokio::time::timeout(Duration::from_secs(5), client.recv_from(&mut buffer)).await.unwrap().unwrap();
+        assert_eq!(remote_addr, server_addr);
+        assert_eq!(Response::from_bytes(&buffer[..length], true).unwrap(), Response::from(ConnectResponse { connection_id: ConnectionId(9), transaction_id: TransactionId(transaction_id) }));
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn udp_server_answers_batched_requests()
+{
+    let udp_server = UdpServer::new(test_tracker().await, SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0), 2, Arc::new(Configuration::init().udp_server[0].clone())).await.unwrap();
+    let server_addr = udp_server.local_addr().unwrap();
+    let (tx, rx) = tokio::sync::watch::channel(false);
+    udp_server.start(rx).await;
+
+    let client = tokio::net::UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).await.unwrap();
+    for transaction_id in 0..16 {
+        let mut bytes = vec![];
+        Request::from(ConnectRequest { transaction_id: TransactionId(transaction_id) }).write(&mut bytes).unwrap();
+        client.send_to(&bytes, server_addr).await.unwrap();
+    }
+    let mut transaction_ids = vec![];
+    let mut buffer = [0u8; 1500];
+    for _ in 0..16 {
+        let (length, _) = tokio::time::timeout(Duration::from_secs(5), client.recv_from(&mut buffer)).await.unwrap().unwrap();
+        match Response::from_bytes(&buffer[..length], true).unwrap() {
+            Response::Connect(response) => { transaction_ids.push(response.transaction_id.0); }
+            response => { panic!("unexpected response {:?}", response) }
+        }
+    }
+    transaction_ids.sort();
+    assert_eq!(transaction_ids, (0..16).collect::<Vec<i32>>());
+    tx.send(true).unwrap();
+}
+
+#[test]
+fn announce_peers_wanted_defaults_and_fits_in_a_datagram()
+{
+    assert_eq!(UdpServer::announce_peers_wanted(NumberOfPeers(-1), 72, false), 72);
+    assert_eq!(UdpServer::announce_peers_wanted(NumberOfPeers(10), 72, false), 10);
+    assert_eq!(UdpServer::announce_peers_wanted(NumberOfPeers(0), 72, false), 0);
+    assert_eq!(UdpServer::announce_peers_wanted(NumberOfPeers(72), 72, false), 72);
+    assert_eq!(UdpServer::announce_peers_wanted(NumberOfPeers(73), 72, false), 72);
+    assert_eq!(UdpServer::announce_peers_wanted(NumberOfPeers(i32::MAX), 72, false), 72);
+    assert_eq!(UdpServer::announce_peers_wanted(NumberOfPeers(i32::MIN), 72, false), 72);
+    assert_eq!(UdpServer::announce_peers_wanted(NumberOfPeers(5), 0, false), 0);
+    assert_eq!(UdpServer::announce_peers_wanted(NumberOfPeers(100), 72, true), 72);
+    assert_eq!(UdpServer::announce_peers_wanted(NumberOfPeers(-1), 1000, false), 246);
+    assert_eq!(UdpServer::announce_peers_wanted(NumberOfPeers(500), 1000, true), 82);
+}
+
+#[tokio::test]
+async fn send_response_truncates_oversized_announces_instead_of_dropping_them()
+{
+    let tracker = test_tracker().await;
+    let server = Arc::new(tokio::net::UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).await.unwrap());
+    let client = tokio::net::UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).await.unwrap();
+    let client_addr = client.local_addr().unwrap();
+    let mut buffer = [0u8; 65507];
+
+    let response = Response::from(AnnounceResponse {
+        transaction_id: TransactionId(1),
+        announce_interval: AnnounceInterval(1800),
+        leechers: NumberOfPeers(500),
+        seeders: NumberOfPeers(0),
+        peers: (0..500u32).map(|index| ResponsePeer { ip_address: Ipv4Addr::from(0x0a00_0000 + index), port: Port(6881) }).collect(),
+    });
+    UdpServer::send_response(tracker.clone(), server.clone(), client_addr, response).await;
+    let (length, _) = tokio::time::timeout(Duration::from_secs(5), client.recv_from(&mut buffer)).await.unwrap().unwrap();
+    assert!(length <= MAX_PACKET_SIZE);
+    match Response::from_bytes(&buffer[..length], true).unwrap() {
+        Response::AnnounceIpv4(response) => { assert_eq!(response.peers.len(), 246); }
+        response => { panic!("unexpected response {:?}", response) }
+    }
+
+    let response = Response::from(AnnounceResponse {
+        transaction_id: TransactionId(2),
+        announce_interval: AnnounceInterval(1800),
+        leechers: NumberOfPeers(500),
+        seeders: NumberOfPeers(0),
+        peers: (0..500u16).map(|index| ResponsePeer { ip_address: Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, index), port: Port(6881) }).collect(),
+    });
+    UdpServer::send_response(tracker.clone(), server.clone(), client_addr, response).await;
+    let (length, _) = tokio::time::timeout(Duration::from_secs(5), client.recv_from(&mut buffer)).await.unwrap().unwrap();
+    assert!(length <= MAX_PACKET_SIZE);
+    match Response::from_bytes(&buffer[..length], false).unwrap() {
+        Response::AnnounceIpv6(response) => { assert_eq!(response.peers.len(), 82); }
+        response => { panic!("unexpected response {:?}", response) }
+    }
+}
+
+#[test]
+fn announce_ip_follows_the_listener_policy()
+{
+    let proxy_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 6881);
+    let direct_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)), 6881);
+    let announced = Ipv4Addr::new(198, 51, 100, 7);
+    let trusted_proxies = vec![Cidr::from_str("10.0.0.0/8").unwrap()];
+
+    assert_eq!(UdpServer::announce_ip(proxy_addr, Some(announced), IpAddressPolicy::ignore, &trusted_proxies), proxy_addr.ip());
+
+    assert_eq!(UdpServer::announce_ip(direct_addr, Some(announced), IpAddressPolicy::trust, &[]), IpAddr::V4(announced));
+    assert_eq!(UdpServer::announce_ip(direct_addr, None, IpAddressPolicy::trust, &[]), direct_addr.ip());
+
+    assert_eq!(UdpServer::announce_ip(proxy_addr, Some(announced), IpAddressPolicy::trust_proxies, &trusted_proxies), IpAddr::V4(announced));
+    assert_eq!(UdpServer::announce_ip(direct_addr, Some(announced), IpAddressPolicy::trust_proxies, &trusted_proxies), direct_addr.ip());
+    assert_eq!(UdpServer::announce_ip(proxy_addr, Some(announced), IpAddressPolicy::trust_proxies, &[]), proxy_addr.ip());
+}
+
+#[tokio::test]
+async fn announce_counts_ip_address_overrides()
+{
+    let tracker = test_tracker().await;
+    let mut udp_trackers_config = Configuration::init().udp_server[0].clone();
+    udp_trackers_config.ip_address_policy = IpAddressPolicy::trust;
+    let udp_trackers_config = Arc::new(udp_trackers_config);
+    let remote_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)), 6881);
+    let mut request = parse_announce(&[]).unwrap();
+    request.connection_id = UdpServer::get_connection_id(&remote_addr, tracker.clone()).await;
+
+    request.ip_address = Some(Ipv4Addr::new(192, 0, 2, 1));
+    UdpServer::handle_udp_announce(remote_addr, &request, tracker.clone(), udp_trackers_config.clone(), Arc::new(vec![])).await.unwrap();
+    assert_eq!(tracker.get_stats().udp_announce_ip_overridden, 0);
+
+    request.ip_address = Some(Ipv4Addr::new(198, 51, 100, 7));
+    UdpServer::handle_udp_announce(remote_addr, &request, tracker.clone(), udp_trackers_config, Arc::new(vec![])).await.unwrap();
+    assert_eq!(tracker.get_stats().udp_announce_ip_overridden, 1);
+}
+
+#[test]
+#[should_panic(expected = "trusted_proxies")]
+fn trusted_proxies_reject_invalid_entries()
+{
+    let mut config = Configuration::init();
+    config.udp_server[0].trusted_proxies = vec![String::from("10.0.0.0/8"), String::from("proxy.example")];
+    Configuration::validate(config);
+}
+
+#[tokio::test]
+async fn handle_packet_counts_errors_per_kind_and_family()
+{
+    let tracker = test_tracker().await;
+    let udp_trackers_config = Arc::new(Configuration::init().udp_server[0].clone());
+    let remote_ipv4 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)), 6881);
+    let remote_ipv6 = SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)), 6881);
+
+    let _ = UdpServer::handle_packet(remote_ipv4, &announce_bytes(&[]), tracker.clone(), udp_trackers_config.clone(), Arc::new(vec![])).await;
+    let _ = UdpServer::handle_packet(remote_ipv6, &[0u8; 4], tracker.clone(), udp_trackers_config.clone(), Arc::new(vec![])).await;
+
+    let stats = tracker.get_stats();
+    assert_eq!(stats.errors.get(ErrorFamily::Udp4, ServerError::ConnectionIdInvalid), 1);
+    assert_eq!(stats.udp4_invalid_request, 1);
+    assert_eq!(stats.errors.get(ErrorFamily::Udp6, ServerError::ConnectionIdInvalid), 0);
+    assert_eq!(stats.errors.get(ErrorFamily::Udp6, ServerError::BadRequest), 1);
+    assert_eq!(stats.udp6_bad_request, 1);
+
+    let errors = serde_json::to_value(stats.errors).unwrap();
+    assert_eq!(errors["udp4"]["connection_id_invalid"], 1);
+    assert_eq!(errors["udp6"]["bad_request"], 1);
+    assert_eq!(errors["tcp4"]["bad_request"], 0);
+    assert!(errors["tcp4"].get("connection_id_invalid").is_none());
+}
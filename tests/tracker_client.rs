@@ -0,0 +1,113 @@
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use torrust_actix::client::enums::tracker_client::TrackerClient;
+use torrust_actix::client::structs::client_announce_request::ClientAnnounceRequest;
+use torrust_actix::client::structs::load_generator::LoadGenerator;
+use torrust_actix::config::structs::configuration::Configuration;
+use torrust_actix::http::enums::client_address_policy::ClientAddressPolicy;
+use torrust_actix::http::http::http_service;
+use torrust_actix::tracker::enums::announce_event::AnnounceEvent;
+use torrust_actix::tracker::structs::info_hash::InfoHash;
+use torrust_actix::tracker::structs::peer_id::PeerId;
+use torrust_actix::tracker::structs::torrent_tracker::TorrentTracker;
+use torrust_actix::udp::enums::ip_address_policy::IpAddressPolicy;
+use torrust_actix::udp::udp::udp_service;
+
+fn free_port() -> u16
+{
+    let tcp = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = tcp.local_addr().unwrap().port();
+    if std::net::UdpSocket::bind(("127.0.0.1", port)).is_ok() {
+        return port;
+    }
+    free_port()
+}
+
+fn announce(info_hash: InfoHash, index: u8, ip: Ipv4Addr, left: u64) -> ClientAnnounceRequest
+{
+    ClientAnnounceRequest {
+        info_hash,
+        peer_id: PeerId([index; 20]),
+        port: 6880 + index as u16,
+        uploaded: 0,
+        downloaded: 0,
+        left,
+        event: AnnounceEvent::Started,
+        ip: Some(ip),
+        numwant: None,
+        key: index as u32,
+    }
+}
+
+/// Runs the real UDP and HTTP services on localhost and drives them with the tracker client.
+#[test]
+fn tracker_client_drives_udp_and_http_services()
+{
+    let tokio_core = Runtime::new().unwrap();
+    let tokio_udp = Arc::new(Runtime::new().unwrap());
+    let port = free_port();
+    let udp_url = format!("udp://127.0.0.1:{}/announce", port);
+    let http_url = format!("http://127.0.0.1:{}/announce", port);
+
+    let (udp_tx, udp_rx) = tokio::sync::watch::channel(false);
+    tokio_core.block_on(async {
+        let mut config = Configuration::init();
+        config.database.path = String::from("sqlite::memory:");
+        let tracker = Arc::new(TorrentTracker::new(Arc::new(config.clone()), false).await);
+        let address = SocketAddr::from(([127, 0, 0, 1], port));
+
+        let mut udp_server_object = config.udp_server[0].clone();
+        udp_server_object.ip_address_policy = IpAddressPolicy::trust;
+        let _udp_handle = udp_service(address, 1, tracker.clone(), udp_server_object, udp_rx, tokio_udp.clone()).await;
+
+        let mut http_server_object = config.http_server[0].clone();
+        http_server_object.threads = 1;
+        http_server_object.client_address_policy = ClientAddressPolicy::trust;
+        let (http_handle, http_future) = http_service(address, tracker.clone(), http_server_object).await;
+        tokio::spawn(http_future);
+
+        let info_hash = InfoHash([0x42; 20]);
+        let mut udp_client = TrackerClient::new(&udp_url, Duration::from_secs(5), 2).await.unwrap();
+        let response = udp_client.announce(&announce(info_hash, 1, Ipv4Addr::new(192, 0, 2, 1), 0)).await.unwrap();
+        assert_eq!((response.seeders, response.leechers), (1, 0));
+        let response = udp_client.announce(&announce(info_hash, 2, Ipv4Addr::new(192, 0, 2, 2), 100)).await.unwrap();
+        assert_eq!((response.seeders, response.leechers), (1, 1));
+        assert_eq!(response.peers, vec![SocketAddr::from(([192, 0, 2, 1], 6881))]);
+        let scrape = udp_client.scrape(&[info_hash, InfoHash([0x43; 20])]).await.unwrap();
+        assert_eq!(scrape.files.len(), 2);
+        assert_eq!((scrape.files[0].1.seeders, scrape.files[0].1.leechers), (1, 1));
+        assert_eq!((scrape.files[1].1.seeders, scrape.files[1].1.leechers), (0, 0));
+
+        let mut http_client = TrackerClient::new(&http_url, Duration::from_secs(5), 2).await.unwrap();
+        let response = http_client.announce(&announce(info_hash, 3, Ipv4Addr::new(192, 0, 2, 3), 100)).await.unwrap();
+        assert_eq!((response.seeders, response.leechers), (1, 2));
+        assert_eq!(response.peers.len(), 2);
+        let scrape = http_client.scrape(&[info_hash]).await.unwrap();
+        assert_eq!(scrape.files, vec![(info_hash, scrape.files[0].1)]);
+        assert_eq!((scrape.files[0].1.seeders, scrape.files[0].1.leechers), (1, 2));
+
+        for tracker_url in [udp_url.clone(), http_url.clone()] {
+            let report = LoadGenerator {
+                tracker: tracker_url,
+                swarms: 4,
+                peers: 20,
+                churn: 0.2,
+                seed_ratio: 0.5,
+                rounds: 2,
+                concurrency: 2,
+                timeout: 5,
+                retransmit_max: 2,
+            }.run().await.unwrap();
+            assert_eq!(report.errors, 0);
+            assert!(report.announces >= 40);
+            assert_eq!(report.scrapes, 4);
+        }
+
+        udp_tx.send(true).unwrap();
+        http_handle.stop(false).await;
+    });
+    drop(tokio_core);
+    drop(Arc::try_unwrap(tokio_udp).ok());
+}